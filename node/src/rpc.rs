@@ -43,6 +43,7 @@ where
   C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
   C::Api: operations_rpc::OperationsRuntimeApi<Block>,
   C::Api: workflows_rpc::WorkflowsRuntimeApi<Block>,
+  C::Api: poe_rpc::PoeRuntimeApi<Block, AccountId, BlockNumber, u32, u32>,
   C::Api: verification_rpc::VerificationRuntimeApi<Block, AccountId>,
   C::Api: tipping_rpc::TippingRuntimeApi<Block, Balance, AccountId, BlockNumber>,
   C::Api: BlockBuilder<Block>,
@@ -50,6 +51,7 @@ where
 {
   use operations_rpc::{Operations, OperationsApiServer};
   use pallet_transaction_payment_rpc::{TransactionPayment, TransactionPaymentApiServer};
+  use poe_rpc::{Poe, PoeApiServer};
   use substrate_frame_rpc_system::{System, SystemApiServer};
   use tipping_rpc::{Tipping, TippingApiServer};
   use verification_rpc::{Verification, VerificationApiServer};
@@ -67,6 +69,7 @@ where
 
   module.merge(Operations::new(client.clone()).into_rpc())?;
   module.merge(Workflows::new(client.clone()).into_rpc())?;
+  module.merge(Poe::new(client.clone()).into_rpc())?;
   module.merge(Verification::new(client.clone()).into_rpc())?;
   module.merge(Tipping::new(client).into_rpc())?;
 
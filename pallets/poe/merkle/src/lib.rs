@@ -0,0 +1,154 @@
+// This file is part of Anagolay Network.
+// Copyright (C) 2019-2023 Anagolay Network.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Merkle trees anchoring batches of Proofs on chain.
+//!
+//! The leaves of a tree are the CIDs of the `ProofData` of the batch. Leaves and inner nodes are
+//! hashed with blake2-256 using distinct prefixes, and the two children of an inner node are
+//! sorted before hashing, so that a Merkle path is just the sequence of sibling hashes from the
+//! leaf up to the root. A node without sibling is promoted to the upper layer unchanged.
+//!
+//! Verification is available in `no_std`, while [`MerkleTree`], building the trees and the paths,
+//! requires the `std` feature.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use sp_core::hashing::blake2_256;
+pub use sp_core::H256;
+use sp_std::vec::Vec;
+
+/// Prefix of the hashed leaves
+const LEAF_PREFIX: u8 = 0;
+/// Prefix of the hashed inner nodes
+const NODE_PREFIX: u8 = 1;
+
+/// Hash a leaf of the tree
+///
+/// # Arguments
+/// * proof_cid - The CID of the `ProofData`, as bytes
+///
+/// # Return
+/// The hash of the leaf
+pub fn leaf_hash(proof_cid: &[u8]) -> H256 {
+  let mut data = Vec::with_capacity(proof_cid.len() + 1);
+  data.push(LEAF_PREFIX);
+  data.extend_from_slice(proof_cid);
+  H256(blake2_256(&data))
+}
+
+/// Hash an inner node of the tree out of its children, in any order
+///
+/// # Arguments
+/// * a - The hash of a child
+/// * b - The hash of the other child
+///
+/// # Return
+/// The hash of the inner node
+pub fn node_hash(a: &H256, b: &H256) -> H256 {
+  let (left, right) = if a <= b { (a, b) } else { (b, a) };
+  let mut data = Vec::with_capacity(65);
+  data.push(NODE_PREFIX);
+  data.extend_from_slice(left.as_bytes());
+  data.extend_from_slice(right.as_bytes());
+  H256(blake2_256(&data))
+}
+
+/// Compute the root of the tree a leaf belongs to, given its Merkle path
+///
+/// # Arguments
+/// * proof_cid - The CID of the `ProofData`, as bytes
+/// * merkle_path - The sibling hashes from the leaf up to the root
+///
+/// # Return
+/// The root of the tree
+pub fn compute_root(proof_cid: &[u8], merkle_path: &[H256]) -> H256 {
+  merkle_path
+    .iter()
+    .fold(leaf_hash(proof_cid), |hash, sibling| node_hash(&hash, sibling))
+}
+
+/// A Merkle tree built over the CIDs of a batch of `ProofData`
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MerkleTree {
+  /// The layers of the tree, from the hashed leaves up to the root
+  layers: Vec<Vec<H256>>,
+}
+
+#[cfg(feature = "std")]
+impl MerkleTree {
+  /// Build the tree
+  ///
+  /// # Arguments
+  /// * proof_cids - The CIDs of the `ProofData` of the batch, as bytes
+  ///
+  /// # Return
+  /// The tree, having no root if there are no leaves
+  pub fn new<I: AsRef<[u8]>>(proof_cids: &[I]) -> Self {
+    let mut layers = vec![proof_cids
+      .iter()
+      .map(|proof_cid| leaf_hash(proof_cid.as_ref()))
+      .collect::<Vec<H256>>()];
+    while layers.last().map_or(false, |layer| layer.len() > 1) {
+      let layer = layers.last().expect("checked above; qed");
+      let upper_layer = layer
+        .chunks(2)
+        .map(|pair| match pair {
+          [a, b] => node_hash(a, b),
+          [a] => *a,
+          _ => unreachable!("chunks are made of one or two nodes; qed"),
+        })
+        .collect();
+      layers.push(upper_layer);
+    }
+    MerkleTree { layers }
+  }
+
+  /// # Return
+  /// The root of the tree, None if the tree has no leaves
+  pub fn root(&self) -> Option<H256> {
+    self.layers.last().and_then(|layer| layer.first()).copied()
+  }
+
+  /// # Return
+  /// The count of leaves of the tree
+  pub fn leaves(&self) -> usize {
+    self.layers.first().map_or(0, |layer| layer.len())
+  }
+
+  /// Build the Merkle path of a leaf
+  ///
+  /// # Arguments
+  /// * index - The index of the leaf, in the order the CIDs were provided
+  ///
+  /// # Return
+  /// The sibling hashes from the leaf up to the root, None if the index is out of bounds
+  pub fn path(&self, index: usize) -> Option<Vec<H256>> {
+    if index >= self.leaves() {
+      return None;
+    }
+    let mut index = index;
+    let mut path = Vec::new();
+    for layer in self.layers.iter().take(self.layers.len() - 1) {
+      let sibling = index ^ 1;
+      if let Some(hash) = layer.get(sibling) {
+        path.push(*hash);
+      }
+      index /= 2;
+    }
+    Some(path)
+  }
+}
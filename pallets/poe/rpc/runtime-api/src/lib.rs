@@ -0,0 +1,173 @@
+// This file is part of Anagolay Network.
+// Copyright (C) 2019-2023 Anagolay Network.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::{Decode, Encode};
+use core::fmt::Debug;
+use frame_support::sp_std::vec::Vec;
+use poe::types::{
+  MerkleRoot, PhashAlgorithm, PhashInfo, ProofBatchInfo, ProofId, ProofInfo, ProofNftInfo, SimilarPhash,
+};
+use verification::types::VerificationContext;
+
+sp_api::decl_runtime_apis! {
+    /// Poe RPC Api
+    ///
+    /// # Type arguments
+    /// - AccountId: the `AccountId` from the runtime `Config`
+    /// - BlockNumber: the `BlockNumber` from the runtime `Config`
+    /// - CollectionId: the identifier of the collection of the Proof NFTs
+    /// - ItemId: the identifier of a Proof NFT inside its collection
+    pub trait PoeApi<
+        AccountId: Debug + Decode + Encode,
+        BlockNumber: Debug + Decode + Encode,
+        CollectionId: Debug + Decode + Encode,
+        ItemId: Debug + Decode + Encode,
+    > {
+        /// Get a subset of Proofs representing a page, given the full set of the ids to paginate
+        /// and the pagination information
+        ///
+        /// # Arguments
+        ///  * proof_ids - The full set of ProofIds. If empty, all Proofs will be considered
+        ///  * offset - The index, inside the resulting Proofs, of the first Proof on the page
+        ///  * limit - The count of Proofs on the page
+        ///
+        /// # Return
+        /// Collection of [`ProofInfo`]
+        fn get_proofs_by_ids (
+            proof_ids: Vec<ProofId>,
+            offset: u64,
+            limit: u16,
+        ) -> Vec<ProofInfo<AccountId, BlockNumber>>;
+
+        /// Get a subset of the Proofs owned by an account representing a page
+        ///
+        /// # Arguments
+        ///  * account_id - The owner of the Proofs
+        ///  * offset - The index, inside the Proofs of the account, of the first Proof on the page
+        ///  * limit - The count of Proofs on the page
+        ///
+        /// # Return
+        /// Collection of [`ProofInfo`]
+        fn get_proofs_by_account (
+            account_id: AccountId,
+            offset: u64,
+            limit: u16,
+        ) -> Vec<ProofInfo<AccountId, BlockNumber>>;
+
+        /// Get a subset of the Proofs associated to a [`VerificationContext`] representing a page
+        ///
+        /// # Arguments
+        ///  * context - The [`VerificationContext`] of the Proofs
+        ///  * offset - The index, inside the Proofs of the context, of the first Proof on the page
+        ///  * limit - The count of Proofs on the page
+        ///
+        /// # Return
+        /// Collection of [`ProofInfo`]
+        fn get_proofs_by_context (
+            context: VerificationContext,
+            offset: u64,
+            limit: u16,
+        ) -> Vec<ProofInfo<AccountId, BlockNumber>>;
+
+        /// Get the derivation tree a Proof belongs to, from the root Proof down through the
+        /// derived Proofs
+        ///
+        /// # Arguments
+        ///  * proof_id - The id of any Proof of the tree
+        ///
+        /// # Return
+        /// Collection of [`ProofInfo`] in breadth-first order from the root
+        fn get_proof_tree (
+            proof_id: ProofId,
+        ) -> Vec<ProofInfo<AccountId, BlockNumber>>;
+
+        /// Get a subset of the perceptual hashes representing a page, given optional filters on
+        /// their owner and on the Proof they are associated to
+        ///
+        /// # Arguments
+        ///  * account_id - Additional filter on the owner of the perceptual hashes
+        ///  * proof_id - Additional filter on the Proof associated to the perceptual hashes
+        ///  * offset - The index, inside the filtered perceptual hashes, of the first one on the page
+        ///  * limit - The count of perceptual hashes on the page
+        ///
+        /// # Return
+        /// Collection of [`PhashInfo`]
+        fn get_phashes (
+            account_id: Option<AccountId>,
+            proof_id: Option<ProofId>,
+            offset: u64,
+            limit: u16,
+        ) -> Vec<PhashInfo>;
+
+        /// Find the perceptual hashes within a given Hamming distance from a searched one, along
+        /// with their owners. Only perceptual hashes computed by the same algorithm are compared
+        ///
+        /// # Arguments
+        ///  * algorithm - The algorithm that computed the searched perceptual hash
+        ///  * p_hash - The searched perceptual hash
+        ///  * max_hamming_distance - The maximum count of differing bits
+        ///
+        /// # Return
+        /// Collection of [`SimilarPhash`] sorted by increasing distance
+        fn find_similar_phashes (
+            algorithm: PhashAlgorithm,
+            p_hash: Vec<u8>,
+            max_hamming_distance: u32,
+        ) -> Vec<SimilarPhash<AccountId>>;
+
+        /// Verify that a Proof is part of some anchored batch
+        ///
+        /// # Arguments
+        ///  * proof_cid - The CID of the `ProofData`
+        ///  * merkle_path - The sibling hashes from the leaf up to the root of the Merkle tree
+        ///
+        /// # Return
+        /// Collection of the [`ProofBatchInfo`] anchoring the Proof, empty if it is not part of
+        /// any batch
+        fn verify_inclusion (
+            proof_cid: ProofId,
+            merkle_path: Vec<MerkleRoot>,
+        ) -> Vec<ProofBatchInfo<AccountId, BlockNumber>>;
+
+        /// Get the NFT representing the Proof claimed by an account, along with the Proof and the
+        /// current owner of the NFT
+        ///
+        /// # Arguments
+        ///  * proof_id - The id of the Proof
+        ///  * account_id - The account that claimed the Proof
+        ///
+        /// # Return
+        /// The [`ProofNftInfo`], if the NFT was minted
+        fn get_proof_nft (
+            proof_id: ProofId,
+            account_id: AccountId,
+        ) -> Option<ProofNftInfo<AccountId, BlockNumber, CollectionId, ItemId>>;
+
+        /// Get the Proof represented by an NFT, along with the NFT and its current owner
+        ///
+        /// # Arguments
+        ///  * item_id - The identifier of the NFT inside the collection of the Proof NFTs
+        ///
+        /// # Return
+        /// The [`ProofNftInfo`], if the NFT represents a Proof
+        fn get_proof_by_nft (
+            item_id: ItemId,
+        ) -> Option<ProofNftInfo<AccountId, BlockNumber, CollectionId, ItemId>>;
+    }
+}
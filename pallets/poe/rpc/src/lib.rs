@@ -0,0 +1,293 @@
+// This file is part of Anagolay Network.
+// Copyright (C) 2019-2023 Anagolay Network.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use anagolay_support::rpc::Error;
+use codec::{Decode, Encode};
+use core::fmt::Debug;
+use frame_support::sp_std::vec::Vec;
+use poe::types::{
+  MerkleRoot, PhashAlgorithm, PhashInfo, ProofBatchInfo, ProofId, ProofInfo, ProofNftInfo, SimilarPhash,
+};
+pub use poe_rpc_runtime_api::PoeApi as PoeRuntimeApi;
+use sp_api::{ApiError, ProvideRuntimeApi};
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::sync::Arc;
+use verification::types::VerificationContext;
+
+use jsonrpsee::{
+  core::{async_trait, RpcResult},
+  proc_macros::rpc,
+  types::error::{CallError, ErrorObject},
+};
+
+#[rpc(client, server)]
+pub trait PoeApi<
+  BlockHash,
+  AccountId: Debug + Decode + Encode,
+  BlockNumber: Debug + Decode + Encode,
+  CollectionId: Debug + Decode + Encode,
+  ItemId: Debug + Decode + Encode,
+>
+{
+  #[method(name = "poe_getProofsByIds")]
+  fn get_proofs_by_ids(
+    &self,
+    proof_ids: Vec<ProofId>,
+    offset: u64,
+    limit: u16,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<ProofInfo<AccountId, BlockNumber>>>;
+
+  #[method(name = "poe_getProofsByAccount")]
+  fn get_proofs_by_account(
+    &self,
+    account_id: AccountId,
+    offset: u64,
+    limit: u16,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<ProofInfo<AccountId, BlockNumber>>>;
+
+  #[method(name = "poe_getProofsByContext")]
+  fn get_proofs_by_context(
+    &self,
+    context: VerificationContext,
+    offset: u64,
+    limit: u16,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<ProofInfo<AccountId, BlockNumber>>>;
+
+  #[method(name = "poe_getProofTree")]
+  fn get_proof_tree(
+    &self,
+    proof_id: ProofId,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<ProofInfo<AccountId, BlockNumber>>>;
+
+  #[method(name = "poe_getPhashes")]
+  fn get_phashes(
+    &self,
+    account_id: Option<AccountId>,
+    proof_id: Option<ProofId>,
+    offset: u64,
+    limit: u16,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<PhashInfo>>;
+
+  #[method(name = "poe_findSimilarPhashes")]
+  fn find_similar_phashes(
+    &self,
+    algorithm: PhashAlgorithm,
+    p_hash: Vec<u8>,
+    max_hamming_distance: u32,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<SimilarPhash<AccountId>>>;
+
+  #[method(name = "poe_verifyInclusion")]
+  fn verify_inclusion(
+    &self,
+    proof_cid: ProofId,
+    merkle_path: Vec<MerkleRoot>,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<ProofBatchInfo<AccountId, BlockNumber>>>;
+
+  #[method(name = "poe_getProofNft")]
+  fn get_proof_nft(
+    &self,
+    proof_id: ProofId,
+    account_id: AccountId,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Option<ProofNftInfo<AccountId, BlockNumber, CollectionId, ItemId>>>;
+
+  #[method(name = "poe_getProofByNft")]
+  fn get_proof_by_nft(
+    &self,
+    item_id: ItemId,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Option<ProofNftInfo<AccountId, BlockNumber, CollectionId, ItemId>>>;
+}
+
+/// A struct that implements the `PoeApi`.
+pub struct Poe<C, M> {
+  client: Arc<C>,
+  _marker: std::marker::PhantomData<M>,
+}
+
+impl<C, M> Poe<C, M> {
+  /// Create new `Poe` instance with the given reference to the client.
+  pub fn new(client: Arc<C>) -> Self {
+    Self {
+      client,
+      _marker: Default::default(),
+    }
+  }
+}
+
+/// Mapper function to transform Runtime API error into RPC error
+///
+/// # Arguments
+/// * e - API error
+///
+/// # Return
+/// JSON RPC error
+fn map_jsonrpc_err(e: ApiError) -> jsonrpsee::core::Error {
+  CallError::Custom(ErrorObject::owned(
+    Error::RuntimeError.into(),
+    "Unable to query Poe.",
+    Some(e.to_string()),
+  ))
+  .into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, BlockNumber, CollectionId, ItemId>
+  PoeApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber, CollectionId, ItemId> for Poe<C, Block>
+where
+  Block: BlockT,
+  AccountId: Debug + Decode + Encode,
+  BlockNumber: Debug + Decode + Encode,
+  CollectionId: Debug + Decode + Encode,
+  ItemId: Debug + Decode + Encode,
+  C: Send + Sync + 'static,
+  C: ProvideRuntimeApi<Block>,
+  C: HeaderBackend<Block>,
+  C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber, CollectionId, ItemId>,
+{
+  fn get_proofs_by_ids(
+    &self,
+    proof_ids: Vec<ProofId>,
+    offset: u64,
+    limit: u16,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<ProofInfo<AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .get_proofs_by_ids(&at, proof_ids, offset, limit)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn get_proofs_by_account(
+    &self,
+    account_id: AccountId,
+    offset: u64,
+    limit: u16,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<ProofInfo<AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .get_proofs_by_account(&at, account_id, offset, limit)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn get_proofs_by_context(
+    &self,
+    context: VerificationContext,
+    offset: u64,
+    limit: u16,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<ProofInfo<AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .get_proofs_by_context(&at, context, offset, limit)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn get_proof_tree(
+    &self,
+    proof_id: ProofId,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<ProofInfo<AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api.get_proof_tree(&at, proof_id).map_err(map_jsonrpc_err)
+  }
+
+  fn get_phashes(
+    &self,
+    account_id: Option<AccountId>,
+    proof_id: Option<ProofId>,
+    offset: u64,
+    limit: u16,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<PhashInfo>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .get_phashes(&at, account_id, proof_id, offset, limit)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn find_similar_phashes(
+    &self,
+    algorithm: PhashAlgorithm,
+    p_hash: Vec<u8>,
+    max_hamming_distance: u32,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<SimilarPhash<AccountId>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .find_similar_phashes(&at, algorithm, p_hash, max_hamming_distance)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn verify_inclusion(
+    &self,
+    proof_cid: ProofId,
+    merkle_path: Vec<MerkleRoot>,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<ProofBatchInfo<AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .verify_inclusion(&at, proof_cid, merkle_path)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn get_proof_nft(
+    &self,
+    proof_id: ProofId,
+    account_id: AccountId,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Option<ProofNftInfo<AccountId, BlockNumber, CollectionId, ItemId>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api.get_proof_nft(&at, proof_id, account_id).map_err(map_jsonrpc_err)
+  }
+
+  fn get_proof_by_nft(
+    &self,
+    item_id: ItemId,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Option<ProofNftInfo<AccountId, BlockNumber, CollectionId, ItemId>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api.get_proof_by_nft(&at, item_id).map_err(map_jsonrpc_err)
+  }
+}
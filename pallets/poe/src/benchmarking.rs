@@ -21,7 +21,7 @@
 #![cfg(feature = "runtime-benchmarks")]
 
 use super::*;
-use crate::types::ProofData;
+use crate::types::{MerkleRoot, PhashAlgorithm, ProofData};
 #[allow(unused)]
 use crate::Pallet as Poe;
 use anagolay_support::{AnagolayArtifactStructure, AnagolayStructureData, ArtifactId};
@@ -29,12 +29,14 @@ use core::convert::TryInto;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
 use frame_support::{sp_std::prelude::*, traits::UnixTime};
 use frame_system::RawOrigin;
+use operations::types::OperationVersionId;
 use workflows::types::{
   Workflow, WorkflowArtifactType, WorkflowData, WorkflowId, WorkflowVersion, WorkflowVersionData, WorkflowVersionExtra,
   WorkflowVersionId,
 };
 
 const PERCEPTUAL_HASH: &[u8] = b"0x30303030303030303031313030303030303030303030303030303031313130303031313131313030313131313131313031313131313131313131313131313130303031313130303030303030303030303131313131313130303030303030303031313131313131313130303030303030313131313131313131313131313030303131313131313131313131313031313131313131313131313130313030313130313131303030303030303130303030303030303030303031303030303030303031313131313131313131313131313131313131313131313130303030303030303131313130303030303030303030303031313131303030303030303030303030";
+const PERCEPTUAL_HASH_OPERATION_VERSION_ID: &str = "bafkr4ih2xmsije6aa6yfwjdfmztnnkbb6ip56g3ojfcyfgjx6jsh6bogoe";
 
 pub fn mock_request<T: workflows::Config>() -> (Workflow, WorkflowVersion) {
   let wf = Workflow {
@@ -70,6 +72,7 @@ pub fn mock_request<T: workflows::Config>() -> (Workflow, WorkflowVersion) {
 pub fn build_default_proof_data(workflow_id: WorkflowId) -> ProofData {
   let mut proof_data = ProofData::default();
   proof_data.workflow_id = workflow_id;
+  proof_data.creator = "tester".into();
   proof_data
 }
 
@@ -79,7 +82,11 @@ benchmarks! {
         let (wf, wf_ver) = mock_request::<T>();
         let wf_id = wf.data.clone().to_cid();
         workflows::Pallet::<T>::create(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone())), wf.data, wf_ver.data).unwrap();
-        let proof_data = build_default_proof_data(wf_id);
+        let parent_proof_data = build_default_proof_data(wf_id.clone());
+        let parent_proof_id = parent_proof_data.to_cid();
+        crate::Pallet::<T>::create_proof(RawOrigin::Signed(caller.clone()).into(), parent_proof_data)?;
+        let mut proof_data = build_default_proof_data(wf_id);
+        proof_data.prev_id = Some(parent_proof_id);
     }: _(RawOrigin::Signed(caller), proof_data)
 
     save_phash {
@@ -91,11 +98,52 @@ benchmarks! {
 
         let phash = PERCEPTUAL_HASH.to_vec();
         let p_hash_payload = PhashInfo {
+            algorithm: PhashAlgorithm::Operation(OperationVersionId::from(PERCEPTUAL_HASH_OPERATION_VERSION_ID)),
             p_hash: phash.clone().try_into().unwrap(),
             proof_id: proof_data.to_cid(),
         };
         crate::Pallet::<T>::create_proof(RawOrigin::Signed(caller.clone()).into(), proof_data)?;
     }: _(RawOrigin::Signed(caller), p_hash_payload)
+
+    revoke_proof {
+        let p in 0 .. T::MAX_PHASHES_PER_PROOF;
+        let caller: T::AccountId = whitelisted_caller();
+        let (wf, wf_ver) = mock_request::<T>();
+        let wf_id = wf.data.clone().to_cid();
+        workflows::Pallet::<T>::create(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone())), wf.data, wf_ver.data).unwrap();
+        let proof_data = build_default_proof_data(wf_id);
+        let proof_id = proof_data.to_cid();
+        crate::Pallet::<T>::create_proof(RawOrigin::Signed(caller.clone()).into(), proof_data)?;
+
+        for i in 0 .. p {
+            let mut phash = PERCEPTUAL_HASH.to_vec();
+            phash[0] = i as u8;
+            let p_hash_payload = PhashInfo {
+                algorithm: PhashAlgorithm::Operation(OperationVersionId::from(PERCEPTUAL_HASH_OPERATION_VERSION_ID)),
+                p_hash: phash.try_into().unwrap(),
+                proof_id: proof_id.clone(),
+            };
+            crate::Pallet::<T>::save_phash(RawOrigin::Signed(caller.clone()).into(), p_hash_payload)?;
+        }
+    }: _(RawOrigin::Signed(caller), proof_id, false)
+
+    create_proof_batch {
+        let caller: T::AccountId = whitelisted_caller();
+        let (wf, wf_ver) = mock_request::<T>();
+        let wf_id = wf.data.clone().to_cid();
+        workflows::Pallet::<T>::create(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone())), wf.data, wf_ver.data).unwrap();
+        let merkle_root = MerkleRoot::repeat_byte(1);
+    }: _(RawOrigin::Signed(caller), wf_id, merkle_root, 1000)
+
+    mint_proof_nft {
+        let caller: T::AccountId = whitelisted_caller();
+        let (wf, wf_ver) = mock_request::<T>();
+        let wf_id = wf.data.clone().to_cid();
+        workflows::Pallet::<T>::create(<T as frame_system::Config>::RuntimeOrigin::from(RawOrigin::Signed(caller.clone())), wf.data, wf_ver.data).unwrap();
+        let proof_data = build_default_proof_data(wf_id);
+        let proof_id = proof_data.to_cid();
+        crate::Pallet::<T>::create_proof(RawOrigin::Signed(caller.clone()).into(), proof_data)?;
+    }: _(RawOrigin::Signed(caller), proof_id)
 }
 
 impl_benchmark_test_suite!(Poe, crate::mock::new_test_ext(), crate::mock::Test);
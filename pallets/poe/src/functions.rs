@@ -16,19 +16,36 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use super::*;
-use crate::{constants::*, types::ProofRecord};
+use crate::types::{
+  MerkleRoot, PhashAlgorithm, ProofBatchInfo, ProofInfo, ProofNft, ProofNftInfo, ProofRecord, SimilarPhash,
+};
+use anagolay_support::generic_id::GenericId;
 use core::convert::TryInto;
-use frame_support::{sp_std::vec::Vec, BoundedVec};
+use frame_support::{
+  sp_runtime::traits::{AccountIdConversion, Hash, One, Saturating},
+  sp_std::{vec, vec::Vec},
+  traits::Get,
+  BoundedVec,
+};
 use verification::types::{VerificationContext, VerificationStatus};
 
 impl<T: Config> Pallet<T> {
+  /// The account of the pallet, owning the collection of the Proof NFTs
+  ///
+  /// # Return
+  /// The AccountId derived from the `PALLET_ID`
+  pub fn account_id() -> T::AccountId {
+    T::PALLET_ID.into_account_truncating()
+  }
+
   /// # Arguments
   ///  * proof_id - The Proof id to validate
   ///  * account_id - The owner of the proof
   ///
   /// # Return
   /// True if the proof is indeed owned by the given account and associated to the context, false
-  /// otherwise
+  /// otherwise. Since the context accumulates the Proofs of all its holders, the verification
+  /// request of the given account must be successful as well
   pub fn is_proof_id_valid_for_verification_context(proof_id: &ProofId, account_id: &T::AccountId) -> bool {
     match ProofByProofIdAndAccountId::<T>::get(proof_id, account_id) {
       Some(proof_by_account) => match (
@@ -53,7 +70,8 @@ impl<T: Config> Pallet<T> {
     }
   }
 
-  /// Inserts the Proof into the `ProofByProofIdAndAccountId` storage
+  /// Inserts the Proof into the `ProofByProofIdAndAccountId` storage and indexes it into the
+  /// `ProofIdsByAccountId` storage
   /// Increases the `ProofTotal` count
   ///
   /// Does no checks.
@@ -70,11 +88,47 @@ impl<T: Config> Pallet<T> {
     };
 
     ProofByProofIdAndAccountId::<T>::insert(&proof.id, account_id, record);
+    ProofIdsByAccountId::<T>::insert(account_id, &proof.id, ());
 
     ProofTotal::<T>::put(Self::proof_total().saturating_add(1));
   }
 
-  /// Inserts the Phash into the `PhashByHashAndAccountId` storage
+  /// Inserts the batch of Proofs into the `ProofBatchByMerkleRootAndAccountId` storage
+  ///
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * batch - The batch of Proofs to insert
+  pub fn do_create_proof_batch(batch: &ProofBatchInfo<T::AccountId, T::BlockNumber>) {
+    ProofBatchByMerkleRootAndAccountId::<T>::insert(batch.merkle_root, &batch.account_id, batch.clone());
+  }
+
+  /// Inserts the derived Proof into the `DerivedProofIdsByProofId` storage of its parent, unless
+  /// already present
+  ///
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * prev_id - The id of the parent Proof
+  ///  * proof_id - The id of the derived Proof
+  ///
+  /// # Return
+  /// A unit-type `Result` if the derived Proof was associated to its parent, `Error` otherwise
+  pub fn do_derive_proof(prev_id: &ProofId, proof_id: &ProofId) -> Result<(), Error<T>> {
+    DerivedProofIdsByProofId::<T>::try_mutate(prev_id, |derived_proof_ids| {
+      if derived_proof_ids.contains(proof_id) {
+        return Ok(());
+      }
+      derived_proof_ids
+        .try_push(proof_id.clone())
+        .map_err(|_| Error::<T>::MaxDerivedProofsPerProofLimitReached)
+    })
+  }
+
+  /// Inserts the Phash into the `PhashByHashAndAccountId` storage and indexes it into the
+  /// `PhashDigestsByAccountId` storage
+  /// Associates the Phash to its Proof into the `PhashDigestsByProofIdAndAccountId` storage
+  /// Indexes the Phash bands into the `PhashesByIndexBand` storage
   /// Increases the `PhashTotal` count
   ///
   /// Does no checks.
@@ -83,40 +137,600 @@ impl<T: Config> Pallet<T> {
   ///  * phash - The perceptual hash to save
   ///  * hash - encoded perceptual hash to use as key
   ///  * account_id - The owner of the Proof
-  pub fn do_save_phash(phash: &PhashInfo, hash: &<T as frame_system::Config>::Hash, account_id: &T::AccountId) {
+  ///
+  /// # Return
+  /// A unit-type `Result` if the Phash was saved, `Error` otherwise
+  pub fn do_save_phash(
+    phash: &PhashInfo,
+    hash: &<T as frame_system::Config>::Hash,
+    account_id: &T::AccountId,
+  ) -> Result<(), Error<T>> {
+    PhashDigestsByProofIdAndAccountId::<T>::try_mutate(&phash.proof_id, account_id, |digests| {
+      digests
+        .try_push(*hash)
+        .map_err(|_| Error::<T>::MaxPhashesPerProofLimitReached)
+    })?;
     PhashByHashAndAccountId::<T>::insert(hash, account_id, phash.clone());
+    PhashDigestsByAccountId::<T>::insert(account_id, hash, ());
+    Self::do_index_phash(phash, hash, account_id);
 
     PhashTotal::<T>::put(Self::phash_total().saturating_add(1));
+    Ok(())
   }
 
-  /// Inserts the Proofs into ProofIdsByVerificationContext and by calling `do_create_proof()`] also
-  /// inserts the Proof into the `ProofByProofIdAndAccountId` storage and increases the `PhashTotal`
-  /// count.
+  /// Removes the Proof from the `ProofByProofIdAndAccountId`, `ProofIdsByAccountId`,
+  /// `PendingProofIdByAccountIdAndVerificationContext` and `ProofIdsByVerificationContext` storages
+  /// Removes the Phashes associated to the Proof along with their indexes
+  /// Removes the Proof from the `DerivedProofIdsByProofId` storage of its parent, if no other
+  /// account owns it
+  /// Decreases the `ProofTotal` and the `PhashTotal` counts
   ///
-  /// Overwrites any existing link between the [`VerificationContext`] and the existing Proofs
-  /// associated to it, but does not remove the existing Proofs.
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * proof - The Proof to revoke
+  ///  * account_id - The owner of the Proof
+  pub fn do_revoke_proof(proof: &Proof, account_id: &T::AccountId) {
+    let digests = PhashDigestsByProofIdAndAccountId::<T>::take(&proof.id, account_id);
+    digests.iter().for_each(|hash| {
+      let phash = PhashByHashAndAccountId::<T>::take(hash, account_id);
+      PhashDigestsByAccountId::<T>::remove(account_id, hash);
+      PhashInfo::index_bands(&phash.algorithm, &phash.p_hash)
+        .iter()
+        .for_each(|band| {
+          let band_digest = <T as frame_system::Config>::Hashing::hash(band);
+          PhashesByIndexBand::<T>::mutate_exists(band_digest, |phashes| {
+            if let Some(indexed) = phashes {
+              indexed.retain(|(indexed_hash, owner)| indexed_hash != hash || owner != account_id);
+              if indexed.is_empty() {
+                *phashes = None;
+              }
+            }
+          });
+        });
+    });
+    PhashTotal::<T>::put(Self::phash_total().saturating_sub(digests.len() as u128));
+
+    if proof.data.context != VerificationContext::Unbounded {
+      PendingProofIdByAccountIdAndVerificationContext::<T>::mutate_exists(
+        account_id,
+        &proof.data.context,
+        |pending_proof_id| {
+          if pending_proof_id.as_ref() == Some(&proof.id) {
+            *pending_proof_id = None;
+          }
+        },
+      );
+      ProofIdsByVerificationContext::<T>::mutate_exists(&proof.data.context, |proof_ids| {
+        if let Some(ids) = proof_ids {
+          ids.retain(|proof_id| *proof_id != proof.id);
+          if ids.is_empty() {
+            *proof_ids = None;
+          }
+        }
+      });
+    }
+
+    ProofByProofIdAndAccountId::<T>::remove(&proof.id, account_id);
+    ProofIdsByAccountId::<T>::remove(account_id, &proof.id);
+    ProofTotal::<T>::put(Self::proof_total().saturating_sub(1));
+
+    if let Some(prev_id) = &proof.data.prev_id {
+      if ProofByProofIdAndAccountId::<T>::iter_key_prefix(&proof.id)
+        .next()
+        .is_none()
+      {
+        DerivedProofIdsByProofId::<T>::mutate_exists(prev_id, |derived_proof_ids| {
+          if let Some(ids) = derived_proof_ids {
+            ids.retain(|proof_id| *proof_id != proof.id);
+            if ids.is_empty() {
+              *derived_proof_ids = None;
+            }
+          }
+        });
+      }
+    }
+  }
+
+  /// Inserts the digest and the owner of the Phash into the `PhashesByIndexBand` storage, once for
+  /// each of its bands. A band already holding `MAX_PHASHES_PER_INDEX_BAND` perceptual hashes is
+  /// left untouched
+  ///
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * phash - The perceptual hash to index
+  ///  * hash - encoded perceptual hash used as key in `PhashByHashAndAccountId`
+  ///  * account_id - The owner of the Proof
+  pub fn do_index_phash(phash: &PhashInfo, hash: &<T as frame_system::Config>::Hash, account_id: &T::AccountId) {
+    PhashInfo::index_bands(&phash.algorithm, &phash.p_hash)
+      .iter()
+      .for_each(|band| {
+        let band_digest = <T as frame_system::Config>::Hashing::hash(band);
+        PhashesByIndexBand::<T>::mutate(band_digest, |phashes| {
+          // A full band is detected by the similarity search, which then performs a full scan
+          let _ = phashes.try_push((*hash, account_id.clone()));
+        });
+      });
+  }
+
+  /// Inserts the Proof of a verification request into the `ProofByProofIdAndAccountId` storage by
+  /// calling `do_create_proof()`, which increases the `ProofTotal` count, unless the holder
+  /// already owns it, as it happens when the holder verifies the same context again. The Proof is
+  /// recorded as pending in the `PendingProofIdByAccountIdAndVerificationContext` storage until
+  /// the verification is successful, so that the pending requests do not fill the context.
   ///
   /// Does no checks.
   ///
   /// # Arguments
   ///  * holder - The account of the verification holder (owner of the Proof)
-  ///  * context - The [`VerificationContext`] to which the proofs will be associated
-  ///  * proofs - The proofs to insert
-  pub fn do_create_proofs_of_verification(holder: &T::AccountId, context: &VerificationContext, proofs: Vec<Proof>) {
+  ///  * context - The [`VerificationContext`] to which the proof will be associated
+  ///  * proof - The proof to insert
+  pub fn do_create_proof_of_verification(holder: &T::AccountId, context: &VerificationContext, proof: Proof) {
     let current_block = <frame_system::Pallet<T>>::block_number();
 
-    // Old proofs, if any, will no longer be associated to this verification context
-    ProofIdsByVerificationContext::<T>::remove(context);
-    let proof_ids: BoundedVec<ProofId, MaxProofsPerWorkflowGet<T>> = proofs
-      .iter()
-      .map(|proof| proof.id.clone())
-      .collect::<Vec<ProofId>>()
-      .try_into()
-      .unwrap_or_default();
-    ProofIdsByVerificationContext::<T>::insert(context, proof_ids);
+    if !ProofByProofIdAndAccountId::<T>::contains_key(&proof.id, holder) {
+      Self::do_create_proof(&proof, holder, current_block)
+    }
+    PendingProofIdByAccountIdAndVerificationContext::<T>::insert(holder, context, proof.id);
+  }
+
+  /// Appends the pending Proof of the holder to ProofIdsByVerificationContext, once the
+  /// verification of the holder is successful.
+  ///
+  /// The Proofs already associated to the [`VerificationContext`] are kept, so that the context
+  /// accumulates the Proofs of all the holders verifying it. A Proof already associated to the
+  /// context is not appended twice, and a Proof revoked meanwhile is not appended at all.
+  ///
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * holder - The account of the verification holder (owner of the Proof)
+  ///  * context - The [`VerificationContext`] to which the proof will be associated
+  ///
+  /// # Return
+  /// A unit-type `Result` if the Proof was associated to the context, `Error` otherwise
+  pub fn do_confirm_proof_of_verification(
+    holder: &T::AccountId,
+    context: &VerificationContext,
+  ) -> Result<(), Error<T>> {
+    let proof_id = match PendingProofIdByAccountIdAndVerificationContext::<T>::get(holder, context) {
+      Some(proof_id) if ProofByProofIdAndAccountId::<T>::contains_key(&proof_id, holder) => proof_id,
+      _ => return Ok(()),
+    };
+
+    ProofIdsByVerificationContext::<T>::try_mutate(context, |proof_ids| {
+      let ids = proof_ids.get_or_insert_with(BoundedVec::default);
+      if ids.contains(&proof_id) {
+        return Ok(());
+      }
+      ids
+        .try_push(proof_id)
+        .map_err(|_| Error::<T>::MaxProofsPerVerificationContextLimitReached)
+    })?;
+    PendingProofIdByAccountIdAndVerificationContext::<T>::remove(holder, context);
+    Ok(())
+  }
+
+  /// Removes the Proofs owned by the holder from the `ProofIdsByVerificationContext` storage of
+  /// the [`VerificationContext`], along with the pending one, once the verification of the holder
+  /// failed or was invalidated. The Proofs of the other holders of the context are kept, and the
+  /// Proofs themselves are not revoked.
+  ///
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * holder - The account of the verification holder (owner of the Proofs)
+  ///  * context - The [`VerificationContext`] the proofs are associated to
+  pub fn do_remove_proofs_of_verification(holder: &T::AccountId, context: &VerificationContext) {
+    PendingProofIdByAccountIdAndVerificationContext::<T>::remove(holder, context);
+    ProofIdsByVerificationContext::<T>::mutate_exists(context, |proof_ids| {
+      if let Some(ids) = proof_ids {
+        ids.retain(|proof_id| !ProofByProofIdAndAccountId::<T>::contains_key(proof_id, holder));
+        if ids.is_empty() {
+          *proof_ids = None;
+        }
+      }
+    });
+  }
 
-    proofs
+  /// Creates the collection of the Proof NFTs through the `ProofNftMinter`, owned by the pallet
+  /// account, and stores its identifier in the `ProofNftCollectionId` storage
+  ///
+  /// Does no checks.
+  ///
+  /// # Return
+  /// The identifier of the created collection if ok, `Error` otherwise
+  pub fn do_create_proof_nft_collection() -> Result<ProofNftCollectionIdOf<T>, Error<T>> {
+    let collection_id = T::ProofNftCollectionId::get();
+    T::ProofNftMinter::create_collection(&collection_id, &Self::account_id())
+      .map_err(|_| Error::<T>::ProofNftMintError)?;
+    ProofNftCollectionId::<T>::put(collection_id);
+    Ok(collection_id)
+  }
+
+  /// Mints the NFT representing the Proof into the account of its claimer through the
+  /// `ProofNftMinter`, creating the collection of the Proof NFTs first if needed
+  /// Inserts the NFT into the `ProofNftByProofIdAndAccountId` and `ProofClaimByProofNftItemId`
+  /// storages
+  ///
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * proof_id - The id of the Proof to represent
+  ///  * account_id - The account that claimed the Proof, receiving the NFT
+  ///
+  /// # Return
+  /// The minted [`ProofNft`] if ok, `Error` otherwise
+  pub fn do_mint_proof_nft(
+    proof_id: &ProofId,
+    account_id: &T::AccountId,
+  ) -> Result<ProofNft<ProofNftCollectionIdOf<T>, ProofNftItemIdOf<T>>, Error<T>> {
+    let collection_id = match ProofNftCollectionId::<T>::get() {
+      Some(collection_id) => collection_id,
+      None => Self::do_create_proof_nft_collection()?,
+    };
+
+    let item_id = NextProofNftItemId::<T>::get();
+    T::ProofNftMinter::mint(&collection_id, &item_id, account_id, proof_id.as_bytes().to_vec())
+      .map_err(|_| Error::<T>::ProofNftMintError)?;
+    NextProofNftItemId::<T>::put(item_id.saturating_add(One::one()));
+
+    let nft = ProofNft { collection_id, item_id };
+    ProofNftByProofIdAndAccountId::<T>::insert(proof_id, account_id, nft);
+    ProofClaimByProofNftItemId::<T>::insert(item_id, (proof_id.clone(), account_id.clone()));
+    Ok(nft)
+  }
+
+  /// Get a subset of Proofs representing a page, given the full set of the ids to paginate and
+  /// the pagination information. A Proof claimed by several accounts is returned once for each
+  /// owner
+  ///
+  /// # Arguments
+  ///  * proof_ids - The full set of ProofIds. If empty, all Proofs will be considered
+  ///  * offset - The index, inside the resulting Proofs, of the first Proof on the page
+  ///  * limit - The count of Proofs on the page
+  ///
+  /// # Return
+  /// Collection of [`ProofInfo`]
+  pub fn get_proofs_by_ids(
+    proof_ids: Vec<ProofId>,
+    offset: u64,
+    limit: u16,
+  ) -> Vec<ProofInfo<T::AccountId, T::BlockNumber>> {
+    if proof_ids.is_empty() {
+      Self::page_proofs(ProofByProofIdAndAccountId::<T>::iter_values(), offset, limit)
+    } else {
+      Self::page_proofs(
+        proof_ids
+          .iter()
+          .flat_map(ProofByProofIdAndAccountId::<T>::iter_prefix_values),
+        offset,
+        limit,
+      )
+    }
+  }
+
+  /// Get a subset of the Proofs owned by an account representing a page, given the pagination
+  /// information
+  ///
+  /// # Arguments
+  ///  * account_id - The owner of the Proofs
+  ///  * offset - The index, inside the Proofs of the account, of the first Proof on the page
+  ///  * limit - The count of Proofs on the page
+  ///
+  /// # Return
+  /// Collection of [`ProofInfo`]
+  pub fn get_proofs_by_account(
+    account_id: T::AccountId,
+    offset: u64,
+    limit: u16,
+  ) -> Vec<ProofInfo<T::AccountId, T::BlockNumber>> {
+    Self::page_proofs(
+      ProofIdsByAccountId::<T>::iter_key_prefix(&account_id)
+        .filter_map(|proof_id| ProofByProofIdAndAccountId::<T>::get(proof_id, &account_id)),
+      offset,
+      limit,
+    )
+  }
+
+  /// Get a subset of the Proofs associated to a [`VerificationContext`] representing a page, given
+  /// the pagination information
+  ///
+  /// # Arguments
+  ///  * context - The [`VerificationContext`] of the Proofs
+  ///  * offset - The index, inside the Proofs of the context, of the first Proof on the page
+  ///  * limit - The count of Proofs on the page
+  ///
+  /// # Return
+  /// Collection of [`ProofInfo`]
+  pub fn get_proofs_by_context(
+    context: VerificationContext,
+    offset: u64,
+    limit: u16,
+  ) -> Vec<ProofInfo<T::AccountId, T::BlockNumber>> {
+    let proof_ids = ProofIdsByVerificationContext::<T>::get(context).unwrap_or_default();
+    Self::page_proofs(
+      proof_ids
+        .iter()
+        .flat_map(ProofByProofIdAndAccountId::<T>::iter_prefix_values),
+      offset,
+      limit,
+    )
+  }
+
+  /// Get the derivation tree a Proof belongs to, starting from the root Proof that is not derived
+  /// from any other and descending through the derived Proofs. A Proof claimed by several
+  /// accounts is returned once for each owner
+  ///
+  /// # Arguments
+  ///  * proof_id - The id of any Proof of the tree
+  ///
+  /// # Return
+  /// Collection of [`ProofInfo`] in breadth-first order from the root. The parent of each Proof
+  /// can be found in its `prev_id`
+  pub fn get_proof_tree(proof_id: ProofId) -> Vec<ProofInfo<T::AccountId, T::BlockNumber>> {
+    // Ascend to the root, guarding against cycles
+    let mut root = proof_id;
+    let mut ancestors = vec![root.clone()];
+    while let Some(record) = ProofByProofIdAndAccountId::<T>::iter_prefix_values(&root).next() {
+      match record.record.data.prev_id {
+        Some(prev_id) if !ancestors.contains(&prev_id) => {
+          ancestors.push(prev_id.clone());
+          root = prev_id;
+        }
+        _ => break,
+      }
+    }
+
+    // Descend breadth-first through the derived Proofs
+    let mut tree = vec![root];
+    let mut index = 0;
+    while index < tree.len() {
+      DerivedProofIdsByProofId::<T>::get(&tree[index])
+        .into_iter()
+        .for_each(|derived_proof_id| {
+          if !tree.contains(&derived_proof_id) {
+            tree.push(derived_proof_id);
+          }
+        });
+      index += 1;
+    }
+
+    tree
       .iter()
-      .for_each(|proof| Self::do_create_proof(proof, holder, current_block));
+      .flat_map(ProofByProofIdAndAccountId::<T>::iter_prefix_values)
+      .map(Self::to_proof_info)
+      .collect()
+  }
+
+  /// Verify that a Proof is part of some anchored batch, computing the root of the Merkle tree out
+  /// of the CID of its `ProofData` and the provided Merkle path
+  ///
+  /// # Arguments
+  ///  * proof_cid - The CID of the `ProofData`
+  ///  * merkle_path - The sibling hashes from the leaf up to the root
+  ///
+  /// # Return
+  /// Collection of the [`ProofBatchInfo`] anchoring the resulting root, one for each owner. Empty
+  /// if the Proof is not part of any batch
+  pub fn verify_inclusion(
+    proof_cid: ProofId,
+    merkle_path: Vec<MerkleRoot>,
+  ) -> Vec<ProofBatchInfo<T::AccountId, T::BlockNumber>> {
+    let merkle_root = poe_merkle::compute_root(proof_cid.as_bytes(), &merkle_path);
+    ProofBatchByMerkleRootAndAccountId::<T>::iter_prefix_values(merkle_root).collect()
+  }
+
+  /// Get the NFT representing the Proof claimed by an account, along with the Proof and the current
+  /// owner of the NFT
+  ///
+  /// # Arguments
+  ///  * proof_id - The id of the Proof
+  ///  * account_id - The account that claimed the Proof
+  ///
+  /// # Return
+  /// The [`ProofNftInfo`], if the NFT was minted
+  pub fn get_proof_nft(
+    proof_id: ProofId,
+    account_id: T::AccountId,
+  ) -> Option<ProofNftInfo<T::AccountId, T::BlockNumber, ProofNftCollectionIdOf<T>, ProofNftItemIdOf<T>>> {
+    let nft = ProofNftByProofIdAndAccountId::<T>::get(&proof_id, &account_id)?;
+    let record = ProofByProofIdAndAccountId::<T>::get(&proof_id, &account_id)?;
+    Some(ProofNftInfo {
+      proof: Self::to_proof_info(record),
+      nft,
+      nft_owner: T::ProofNftMinter::owner(&nft.collection_id, &nft.item_id),
+    })
+  }
+
+  /// Get the Proof represented by an NFT, along with the NFT and its current owner
+  ///
+  /// # Arguments
+  ///  * item_id - The identifier of the NFT inside the collection of the Proof NFTs
+  ///
+  /// # Return
+  /// The [`ProofNftInfo`], if the NFT represents a Proof
+  pub fn get_proof_by_nft(
+    item_id: ProofNftItemIdOf<T>,
+  ) -> Option<ProofNftInfo<T::AccountId, T::BlockNumber, ProofNftCollectionIdOf<T>, ProofNftItemIdOf<T>>> {
+    let (proof_id, account_id) = ProofClaimByProofNftItemId::<T>::get(item_id)?;
+    Self::get_proof_nft(proof_id, account_id)
+  }
+
+  /// Get a subset of the perceptual hashes representing a page, given optional filters on their
+  /// owner and on the Proof they are associated to and the pagination information
+  ///
+  /// # Arguments
+  ///  * account_id - Additional filter on the owner of the perceptual hashes
+  ///  * proof_id - Additional filter on the Proof associated to the perceptual hashes
+  ///  * offset - The index, inside the filtered perceptual hashes, of the first one on the page
+  ///  * limit - The count of perceptual hashes on the page
+  ///
+  /// # Return
+  /// Collection of [`PhashInfo`]
+  pub fn get_phashes(
+    account_id: Option<T::AccountId>,
+    proof_id: Option<ProofId>,
+    offset: u64,
+    limit: u16,
+  ) -> Vec<PhashInfo> {
+    let skip = offset.try_into().unwrap_or(usize::MAX);
+    match account_id {
+      // The index of the owner avoids scanning the perceptual hashes of the other accounts
+      Some(account_id) => PhashDigestsByAccountId::<T>::iter_key_prefix(&account_id)
+        .filter_map(|hash| PhashByHashAndAccountId::<T>::try_get(hash, &account_id).ok())
+        .filter(|phash_info| {
+          proof_id
+            .as_ref()
+            .map_or(true, |proof_id| *proof_id == phash_info.proof_id)
+        })
+        .skip(skip)
+        .take(limit as usize)
+        .collect(),
+      None => PhashByHashAndAccountId::<T>::iter_values()
+        .filter(|phash_info| {
+          proof_id
+            .as_ref()
+            .map_or(true, |proof_id| *proof_id == phash_info.proof_id)
+        })
+        .skip(skip)
+        .take(limit as usize)
+        .collect(),
+    }
+  }
+
+  /// Find the perceptual hashes within a given Hamming distance from a searched one, along with
+  /// their owners. Only perceptual hashes computed by the same algorithm and of the same length as
+  /// the searched one are compared.
+  ///
+  /// When the distance is lower than the count of bands of the searched perceptual hash, the
+  /// candidates are retrieved from the multi-index hashing, since at least one of their bands
+  /// must be identical to the searched one. Otherwise, or if any of the bands involved is full,
+  /// all perceptual hashes are scanned.
+  ///
+  /// # Arguments
+  ///  * algorithm - The algorithm that computed the searched perceptual hash
+  ///  * p_hash - The searched perceptual hash
+  ///  * max_hamming_distance - The maximum count of differing bits
+  ///
+  /// # Return
+  /// Collection of [`SimilarPhash`] sorted by increasing distance
+  pub fn find_similar_phashes(
+    algorithm: PhashAlgorithm,
+    p_hash: Vec<u8>,
+    max_hamming_distance: u32,
+  ) -> Vec<SimilarPhash<T::AccountId>> {
+    let bands = PhashInfo::index_bands(&algorithm, &p_hash);
+    if bands.is_empty() {
+      return Vec::new();
+    }
+
+    let candidates = if (max_hamming_distance as usize) < bands.len() {
+      let mut candidates: Vec<(T::Hash, T::AccountId)> = Vec::new();
+      let mut saturated = false;
+      bands.iter().for_each(|band| {
+        let phashes = PhashesByIndexBand::<T>::get(<T as frame_system::Config>::Hashing::hash(band));
+        saturated |= phashes.len() as u32 >= T::MAX_PHASHES_PER_INDEX_BAND;
+        phashes.into_iter().for_each(|candidate| {
+          if !candidates.contains(&candidate) {
+            candidates.push(candidate);
+          }
+        });
+      });
+      if saturated {
+        None
+      } else {
+        Some(candidates)
+      }
+    } else {
+      None
+    };
+
+    let mut similar: Vec<SimilarPhash<T::AccountId>> = match candidates {
+      Some(candidates) => candidates
+        .into_iter()
+        .filter_map(|(hash, account_id)| {
+          PhashByHashAndAccountId::<T>::try_get(hash, &account_id)
+            .ok()
+            .map(|phash_info| (account_id, phash_info))
+        })
+        .filter_map(|(account_id, phash_info)| {
+          Self::similar_phash(&algorithm, &p_hash, max_hamming_distance, account_id, phash_info)
+        })
+        .collect(),
+      None => PhashByHashAndAccountId::<T>::iter()
+        .filter_map(|(_, account_id, phash_info)| {
+          Self::similar_phash(&algorithm, &p_hash, max_hamming_distance, account_id, phash_info)
+        })
+        .collect(),
+    };
+    similar.sort_by_key(|similar_phash| similar_phash.distance);
+    similar
+  }
+
+  /// Build a [`SimilarPhash`] if the stored perceptual hash is within the given Hamming distance
+  /// from the searched one
+  ///
+  /// # Arguments
+  ///  * algorithm - The algorithm that computed the searched perceptual hash
+  ///  * p_hash - The searched perceptual hash
+  ///  * max_hamming_distance - The maximum count of differing bits
+  ///  * account_id - The owner of the stored perceptual hash
+  ///  * phash_info - The stored perceptual hash
+  ///
+  /// # Return
+  /// The [`SimilarPhash`], or None if the stored perceptual hash is not similar
+  fn similar_phash(
+    algorithm: &PhashAlgorithm,
+    p_hash: &[u8],
+    max_hamming_distance: u32,
+    account_id: T::AccountId,
+    phash_info: PhashInfo,
+  ) -> Option<SimilarPhash<T::AccountId>> {
+    phash_info
+      .hamming_distance(algorithm, p_hash)
+      .filter(|distance| *distance <= max_hamming_distance)
+      .map(|distance| SimilarPhash {
+        phash_info,
+        account_id,
+        distance,
+      })
+  }
+
+  /// Turn a page of the provided records into [`ProofInfo`]
+  ///
+  /// # Arguments
+  ///  * records - The records to paginate
+  ///  * offset - The index, inside the records, of the first Proof on the page
+  ///  * limit - The count of Proofs on the page
+  ///
+  /// # Return
+  /// Collection of [`ProofInfo`]
+  fn page_proofs(
+    records: impl Iterator<Item = ProofRecord<T>>,
+    offset: u64,
+    limit: u16,
+  ) -> Vec<ProofInfo<T::AccountId, T::BlockNumber>> {
+    records
+      .skip(offset.try_into().unwrap_or(usize::MAX))
+      .take(limit as usize)
+      .map(Self::to_proof_info)
+      .collect()
+  }
+
+  /// Turn a record into [`ProofInfo`]
+  ///
+  /// # Arguments
+  ///  * record - The record of the Proof
+  ///
+  /// # Return
+  /// The [`ProofInfo`]
+  fn to_proof_info(record: ProofRecord<T>) -> ProofInfo<T::AccountId, T::BlockNumber> {
+    ProofInfo {
+      proof: record.record,
+      account_id: record.account_id,
+      block_number: record.block_number,
+    }
   }
 }
@@ -25,18 +25,22 @@
 
 mod benchmarking;
 mod functions;
+pub mod migration;
 mod mock;
 mod tests;
 pub mod types;
 pub mod weights;
 
 pub use pallet::*;
-use types::{PhashInfo, Proof, ProofId};
+use types::{PhashInfo, Proof, ProofId, ProofNftMinter, ProofRevocationHandler};
 pub use weights::WeightInfo;
 
 pub mod constants {
   use anagolay_support::getter_for_constant;
-  getter_for_constant!(MaxProofsPerWorkflow, u32);
+  getter_for_constant!(MaxProofsPerVerificationContext, u32);
+  getter_for_constant!(MaxPhashesPerIndexBand, u32);
+  getter_for_constant!(MaxPhashesPerProof, u32);
+  getter_for_constant!(MaxDerivedProofsPerProof, u32);
 }
 
 #[frame_support::pallet]
@@ -44,15 +48,23 @@ pub mod pallet {
   use super::*;
   use crate::{
     constants::*,
-    types::{ProofData, ProofRecord},
+    types::{MerkleRoot, ProofBatchInfo, ProofData, ProofNft, ProofRecord},
   };
   use anagolay_support::{AnagolayStructureData, Characters};
   use core::convert::TryInto;
-  use frame_support::{pallet_prelude::*, sp_runtime::traits::Hash, sp_std::prelude::*};
+  use frame_support::{pallet_prelude::*, sp_runtime::traits::Hash, sp_std::prelude::*, PalletId};
   use frame_system::pallet_prelude::*;
   use verification::types::VerificationContext;
+  use workflows::types::WorkflowId;
+
+  pub type ProofNftCollectionIdOf<T> = <<T as Config>::ProofNftMinter as ProofNftMinter<T>>::CollectionId;
+  pub type ProofNftItemIdOf<T> = <<T as Config>::ProofNftMinter as ProofNftMinter<T>>::ItemId;
+
+  /// The current storage version
+  pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(6);
 
   #[pallet::pallet]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
   /// The pallet's configuration trait.
@@ -64,15 +76,55 @@ pub mod pallet {
     /// Weight information for extrinsics for this pallet.
     type WeightInfo: WeightInfo;
 
-    /// Maximum number of Proofs a Workflow can produce as output
-    const MAX_PROOFS_PER_WORKFLOW: u32;
+    /// Handler of the revocation of a Proof for the entities of other pallets referencing it
+    type ProofRevocationHandler: ProofRevocationHandler<Self>;
+
+    /// Minter of the NFTs representing the Proofs, [`NaiveProofNftMinter`] if they are not
+    /// supported
+    type ProofNftMinter: ProofNftMinter<Self>;
+
+    /// The identifier of the collection of the Proof NFTs, created along with the first NFT
+    type ProofNftCollectionId: Get<ProofNftCollectionIdOf<Self>>;
+
+    /// The pallet id, whose account owns the collection of the Proof NFTs
+    const PALLET_ID: PalletId;
+
+    /// Maximum number of Proofs that can be associated to a single [`VerificationContext`], that
+    /// is the Proofs of verification of all the holders verifying it over time
+    const MAX_PROOFS_PER_VERIFICATION_CONTEXT: u32;
+
+    /// Maximum number of perceptual hashes indexed under the same band. Once reached, further
+    /// perceptual hashes are not indexed under that band and the similarity search falls back to
+    /// a full scan when the band is involved
+    const MAX_PHASHES_PER_INDEX_BAND: u32;
+
+    /// Maximum number of perceptual hashes an account can associate to a single Proof
+    const MAX_PHASHES_PER_PROOF: u32;
+
+    /// Maximum number of Proofs that can be derived from a single Proof
+    const MAX_DERIVED_PROOFS_PER_PROOF: u32;
   }
 
   #[pallet::extra_constants]
   impl<T: Config> Pallet<T> {
-    #[pallet::constant_name(MaxProofsPerWorkflow)]
-    fn max_proofs_per_workflow() -> u32 {
-      T::MAX_PROOFS_PER_WORKFLOW
+    #[pallet::constant_name(MaxProofsPerVerificationContext)]
+    fn max_proofs_per_verification_context() -> u32 {
+      T::MAX_PROOFS_PER_VERIFICATION_CONTEXT
+    }
+
+    #[pallet::constant_name(MaxPhashesPerIndexBand)]
+    fn max_phashes_per_index_band() -> u32 {
+      T::MAX_PHASHES_PER_INDEX_BAND
+    }
+
+    #[pallet::constant_name(MaxPhashesPerProof)]
+    fn max_phashes_per_proof() -> u32 {
+      T::MAX_PHASHES_PER_PROOF
+    }
+
+    #[pallet::constant_name(MaxDerivedProofsPerProof)]
+    fn max_derived_proofs_per_proof() -> u32 {
+      T::MAX_DERIVED_PROOFS_PER_PROOF
     }
   }
 
@@ -82,11 +134,38 @@ pub mod pallet {
   pub type ProofByProofIdAndAccountId<T: Config> =
     StorageDoubleMap<_, Blake2_128Concat, ProofId, Twox64Concat, T::AccountId, ProofRecord<T>, OptionQuery>;
 
-  /// Retrieve the ProofIds with the [`VerificationContext`]
+  /// Secondary index of `ProofByProofIdAndAccountId`: retrieve the ProofIds owned by the AccountId
+  #[pallet::storage]
+  #[pallet::getter(fn proof_ids_by_account_id)]
+  pub type ProofIdsByAccountId<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Blake2_128Concat, ProofId, (), OptionQuery>;
+
+  /// Retrieve the ProofIds with the [`VerificationContext`]. The Proofs of verification accumulate
+  /// over time, one for each holder verifying the context
   #[pallet::storage]
   #[pallet::getter(fn proof_ids_by_verification_context)]
-  pub type ProofIdsByVerificationContext<T: Config> =
-    StorageMap<_, Blake2_128Concat, VerificationContext, BoundedVec<ProofId, MaxProofsPerWorkflowGet<T>>, OptionQuery>;
+  pub type ProofIdsByVerificationContext<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    VerificationContext,
+    BoundedVec<ProofId, MaxProofsPerVerificationContextGet<T>>,
+    OptionQuery,
+  >;
+
+  /// Retrieve the ProofId created for the verification request of the AccountId on the
+  /// [`VerificationContext`], until the verification is successful and the Proof is associated to
+  /// the context in `ProofIdsByVerificationContext`
+  #[pallet::storage]
+  #[pallet::getter(fn pending_proof_id_by_account_id_and_verification_context)]
+  pub type PendingProofIdByAccountIdAndVerificationContext<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Blake2_128Concat, VerificationContext, ProofId, OptionQuery>;
+
+  /// Retrieve the ids of the Proofs derived from the Proof with the given ProofId, that is the
+  /// Proofs whose `prev_id` is the given ProofId
+  #[pallet::storage]
+  #[pallet::getter(fn derived_proof_ids_by_proof_id)]
+  pub type DerivedProofIdsByProofId<T: Config> =
+    StorageMap<_, Blake2_128Concat, ProofId, BoundedVec<ProofId, MaxDerivedProofsPerProofGet<T>>, ValueQuery>;
 
   /// Amount of saved Proofs
   #[pallet::storage]
@@ -99,11 +178,86 @@ pub mod pallet {
   pub(super) type PhashByHashAndAccountId<T: Config> =
     StorageDoubleMap<_, Blake2_128Concat, T::Hash, Twox64Concat, T::AccountId, PhashInfo, ValueQuery>;
 
+  /// Secondary index of `PhashByHashAndAccountId`: retrieve the digests of the perceptual hashes
+  /// owned by the AccountId
+  #[pallet::storage]
+  #[pallet::getter(fn phash_digests_by_account_id)]
+  pub(super) type PhashDigestsByAccountId<T: Config> =
+    StorageDoubleMap<_, Twox64Concat, T::AccountId, Blake2_128Concat, T::Hash, (), OptionQuery>;
+
+  /// Retrieve the digests of the perceptual hashes associated by an account to a Proof
+  #[pallet::storage]
+  #[pallet::getter(fn phash_digests_by_proof_id_and_account_id)]
+  pub(super) type PhashDigestsByProofIdAndAccountId<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    ProofId,
+    Twox64Concat,
+    T::AccountId,
+    BoundedVec<T::Hash, MaxPhashesPerProofGet<T>>,
+    ValueQuery,
+  >;
+
+  /// Multi-index hashing of the perceptual hashes: retrieve the digests and the owners of the
+  /// perceptual hashes with the digest of one of their bands
+  #[pallet::storage]
+  #[pallet::getter(fn phashes_by_index_band)]
+  pub(super) type PhashesByIndexBand<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    T::Hash,
+    BoundedVec<(T::Hash, T::AccountId), MaxPhashesPerIndexBandGet<T>>,
+    ValueQuery,
+  >;
+
   /// PHashes count
   #[pallet::storage]
   #[pallet::getter(fn phash_total)]
   pub(super) type PhashTotal<T: Config> = StorageValue<_, u128, ValueQuery>;
 
+  /// Retrieve the batch of Proofs with the root of its Merkle tree and the AccountId
+  #[pallet::storage]
+  #[pallet::getter(fn proof_batch_by_merkle_root_and_account_id)]
+  pub type ProofBatchByMerkleRootAndAccountId<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    MerkleRoot,
+    Twox64Concat,
+    T::AccountId,
+    ProofBatchInfo<T::AccountId, T::BlockNumber>,
+    OptionQuery,
+  >;
+
+  /// The identifier of the collection of the Proof NFTs, once created
+  #[pallet::storage]
+  #[pallet::getter(fn proof_nft_collection_id)]
+  pub type ProofNftCollectionId<T: Config> = StorageValue<_, ProofNftCollectionIdOf<T>, OptionQuery>;
+
+  /// The identifier of the next Proof NFT minted in the collection
+  #[pallet::storage]
+  #[pallet::getter(fn next_proof_nft_item_id)]
+  pub type NextProofNftItemId<T: Config> = StorageValue<_, ProofNftItemIdOf<T>, ValueQuery>;
+
+  /// Retrieve the NFT representing the Proof with the ProofId and the AccountId that claimed it
+  #[pallet::storage]
+  #[pallet::getter(fn proof_nft_by_proof_id_and_account_id)]
+  pub type ProofNftByProofIdAndAccountId<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    ProofId,
+    Twox64Concat,
+    T::AccountId,
+    ProofNft<ProofNftCollectionIdOf<T>, ProofNftItemIdOf<T>>,
+    OptionQuery,
+  >;
+
+  /// Retrieve the ProofId and the AccountId that claimed it with the identifier of the NFT
+  /// representing the Proof
+  #[pallet::storage]
+  #[pallet::getter(fn proof_claim_by_proof_nft_item_id)]
+  pub type ProofClaimByProofNftItemId<T: Config> =
+    StorageMap<_, Twox64Concat, ProofNftItemIdOf<T>, (ProofId, T::AccountId), OptionQuery>;
+
   /// Events of the Poe pallet
   #[pallet::event]
   #[pallet::generate_deposit(pub(crate) fn deposit_event)]
@@ -112,6 +266,12 @@ pub mod pallet {
     ProofCreated(T::AccountId, ProofId),
     /// Phash is created
     PhashCreated(T::AccountId, T::Hash),
+    /// Proof is revoked, along with its perceptual hashes
+    ProofRevoked(T::AccountId, ProofId),
+    /// Batch of Proofs is anchored through the root of its Merkle tree
+    ProofBatchCreated(T::AccountId, MerkleRoot),
+    /// NFT representing a Proof is minted into the account of the claimer
+    ProofNftMinted(T::AccountId, ProofId, ProofNftCollectionIdOf<T>, ProofNftItemIdOf<T>),
     /// Bad request error occurs and this event propagates a detailed description
     BadRequestError(T::AccountId, Characters),
   }
@@ -125,16 +285,71 @@ pub mod pallet {
     NoSuchProof,
     /// The Workflow associated to the proof does not exist
     NoSuchWorkflow,
-    /// The Workflow groups don't match the Proof groups
+    /// The Proof params or groups don't match the Workflow definition
     ProofWorkflowTypeMismatch,
     /// PHash and ProofId combination already exist
     PHashAndProofIdComboAlreadyExist,
     /// A parameter of the request is invalid or does not respect a given constraint
     BadRequest,
+    /// The Proof is referenced by other entities, so it cannot be revoked without cascading
+    ProofIsReferenced,
+    /// The entities referencing the Proof could not be revoked
+    ProofRevocationError,
+    /// Too many perceptual hashes are associated to the Proof
+    MaxPhashesPerProofLimitReached,
+    /// The Proof referenced as `prev_id` does not exist
+    NoSuchParentProof,
+    /// Too many Proofs are derived from the parent Proof
+    MaxDerivedProofsPerProofLimitReached,
+    /// Other Proofs are derived from the Proof, so it cannot be revoked
+    ProofHasDerivedProofs,
+    /// This batch of Proofs has already been anchored
+    ProofBatchAlreadyAnchored,
+    /// Too many Proofs are associated to the VerificationContext
+    MaxProofsPerVerificationContextLimitReached,
+    /// The NFT representing the Proof has already been minted
+    ProofNftAlreadyMinted,
+    /// The NFT representing the Proof could not be minted
+    ProofNftMintError,
+    /// The Proof is represented by an NFT, so it cannot be revoked
+    ProofHasNft,
   }
 
   #[pallet::hooks]
-  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_runtime_upgrade() -> Weight {
+      crate::migration::migrate::<T>()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+      crate::migration::pre_upgrade::<T>()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+      crate::migration::post_upgrade::<T>(state)
+    }
+
+    fn integrity_test() {
+      assert!(
+        T::MAX_PROOFS_PER_VERIFICATION_CONTEXT > 0u32,
+        "`MaxProofsPerVerificationContext` must be greater than 0"
+      );
+      assert!(
+        T::MAX_PHASHES_PER_INDEX_BAND > 0u32,
+        "`MaxPhashesPerIndexBand` must be greater than 0"
+      );
+      assert!(
+        T::MAX_PHASHES_PER_PROOF > 0u32,
+        "`MaxPhashesPerProof` must be greater than 0"
+      );
+      assert!(
+        T::MAX_DERIVED_PROOFS_PER_PROOF > 0u32,
+        "`MaxDerivedProofsPerProof` must be greater than 0"
+      );
+    }
+  }
 
   #[pallet::call]
   impl<T: Config> Pallet<T> {
@@ -145,8 +360,14 @@ pub mod pallet {
     /// * proof_data - the data section of the Proof
     ///
     /// # Errors
-    /// * `ProofWorkflowTypeMismatch` - if the Workflow groups don't match the Proof groups
+    /// * `BadRequest` - if the Proof data is not valid
+    /// * `NoSuchWorkflow` - if the Workflow referenced by the Proof does not exist
+    /// * `ProofWorkflowTypeMismatch` - if the Proof params or groups don't match the Workflow
+    ///   definition
     /// * `ProofAlreadyClaimed` - if the Proof is already registered as claimed
+    /// * `NoSuchParentProof` - if the Proof referenced as `prev_id` does not exist
+    /// * `MaxDerivedProofsPerProofLimitReached` - if too many Proofs are derived from the parent
+    ///   Proof
     ///
     /// # Return
     /// `DispatchResultWithPostInfo` containing Unit type
@@ -171,24 +392,37 @@ pub mod pallet {
 
       let workflows = workflows::Pallet::<T>::get_workflows_by_ids(vec![workflow_id], 0, 1);
       // Workflow exists?
-      ensure!(!workflows.is_empty(), Error::<T>::NoSuchWorkflow);
+      let workflow = workflows.first().ok_or(Error::<T>::NoSuchWorkflow)?;
+
+      // The Proof must match the Workflow definition
+      let workflow_validation = proof.data.validate_for_workflow(&workflow.data);
+      if let Err(ref message) = workflow_validation {
+        Self::deposit_event(Event::BadRequestError(sender.clone(), message.clone()));
+      }
+      ensure!(workflow_validation.is_ok(), Error::<T>::ProofWorkflowTypeMismatch);
 
       let current_block = <frame_system::Pallet<T>>::block_number();
 
       // @TODO somehow figure this out. we don't need it NOW but must be done before the Milestone 2 is
       // submitted ensure!(&rule_record, Error::<T>::NoSuchRule);
 
-      // The types must match
-      // if proof.data.groups != workflow.record.data.groups {
-      //   ensure!(false, Error::<T>::ProofWorkflowTypeMismatch);
-      // }
-
       // Proof exists?ProofByProofIdAndAccountId
       ensure!(
         !ProofByProofIdAndAccountId::<T>::contains_key(&proof_id, &sender),
         Error::<T>::ProofAlreadyClaimed
       );
 
+      // Parent Proof exists?
+      if let Some(prev_id) = &proof.data.prev_id {
+        ensure!(
+          ProofByProofIdAndAccountId::<T>::iter_key_prefix(prev_id)
+            .next()
+            .is_some(),
+          Error::<T>::NoSuchParentProof
+        );
+        Self::do_derive_proof(prev_id, &proof_id)?;
+      }
+
       Self::do_create_proof(&proof, &sender, current_block);
 
       // Emit an event that the proof was created
@@ -197,17 +431,21 @@ pub mod pallet {
       Ok(().into())
     }
 
-    /// INDEX storage, save the connection phash <-> proofId for hamming/leven distance calc.
-    /// Eventually refactor this, for now leave it
+    /// INDEX storage, save the connection phash <-> proofId and index the bands of the phash for
+    /// the similarity search by Hamming distance
     ///
     /// # Arguments
     /// * origin - the call origin
     /// * phash_info - the perceptive hash information
     ///
     /// # Errors
+    /// * `BadRequest` - if the algorithm is `Legacy` or the length of the perceptive hash is not
+    ///   valid for the algorithm
     /// * `NoSuchProof` - if there is no such Proof as indicated in the phash_info
     /// * `PHashAndProofIdComboAlreadyExist` - if the relation between the perceptive hash and the
     ///   proof is already existing
+    /// * `MaxPhashesPerProofLimitReached` - if the Proof already has `MAX_PHASHES_PER_PROOF`
+    ///   perceptual hashes
     ///
     /// # Return
     /// `DispatchResultWithPostInfo` containing Unit type
@@ -215,6 +453,12 @@ pub mod pallet {
     pub fn save_phash(origin: OriginFor<T>, phash_info: PhashInfo) -> DispatchResultWithPostInfo {
       let sender = ensure_signed(origin)?;
 
+      let phash_validation = phash_info.validate();
+      if let Err(ref message) = phash_validation {
+        Self::deposit_event(Event::BadRequestError(sender.clone(), message.clone()));
+      }
+      ensure!(phash_validation.is_ok(), Error::<T>::BadRequest);
+
       // Check is do we have the proof, can't add without
       ensure!(
         ProofByProofIdAndAccountId::<T>::contains_key(&phash_info.proof_id, &sender),
@@ -228,12 +472,155 @@ pub mod pallet {
         Error::<T>::PHashAndProofIdComboAlreadyExist
       );
 
-      Self::do_save_phash(&phash_info, &phash_info_digest, &sender);
+      Self::do_save_phash(&phash_info, &phash_info_digest, &sender)?;
 
       // Emit an event that the proof was created
       Self::deposit_event(Event::PhashCreated(sender, phash_info_digest));
 
       Ok(().into())
     }
+
+    /// Revoke a Proof owned by the caller, removing the perceptual hashes associated to it and its
+    /// association to a [`VerificationContext`]. While the Proof is referenced by other entities
+    /// (e.g. Statements) and the caller is its last owner, the revocation is refused unless it is
+    /// requested to cascade to those entities
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * proof_id - the id of the Proof to revoke
+    /// * cascade - whether to revoke the entities referencing the Proof as well
+    ///
+    /// # Errors
+    /// * `NoSuchProof` - if the caller does not own the Proof
+    /// * `ProofHasNft` - if the Proof is represented by an NFT
+    /// * `ProofHasDerivedProofs` - if other Proofs are derived from the Proof
+    /// * `ProofIsReferenced` - if the Proof is referenced by other entities and cascade is false
+    /// * `ProofRevocationError` - if the entities referencing the Proof cannot be revoked
+    ///
+    /// # Events
+    /// * `ProofRevoked` - the Proof was revoked
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::revoke_proof(T::MAX_PHASHES_PER_PROOF)
+      .saturating_add(if *cascade { T::ProofRevocationHandler::revoke_weight() } else { Weight::zero() }))]
+    pub fn revoke_proof(origin: OriginFor<T>, proof_id: ProofId, cascade: bool) -> DispatchResultWithPostInfo {
+      let sender = ensure_signed(origin)?;
+
+      let record = ProofByProofIdAndAccountId::<T>::get(&proof_id, &sender).ok_or(Error::<T>::NoSuchProof)?;
+      ensure!(
+        !ProofNftByProofIdAndAccountId::<T>::contains_key(&proof_id, &sender),
+        Error::<T>::ProofHasNft
+      );
+
+      // Other entities are affected only when the last owner revokes the Proof
+      let is_last_owner = !ProofByProofIdAndAccountId::<T>::iter_key_prefix(&proof_id).any(|owner| owner != sender);
+      ensure!(
+        !is_last_owner || DerivedProofIdsByProofId::<T>::get(&proof_id).is_empty(),
+        Error::<T>::ProofHasDerivedProofs
+      );
+      if is_last_owner && T::ProofRevocationHandler::is_referenced(&proof_id) {
+        ensure!(cascade, Error::<T>::ProofIsReferenced);
+        T::ProofRevocationHandler::revoke(&proof_id)?;
+      }
+
+      Self::do_revoke_proof(&record.record, &sender);
+
+      Self::deposit_event(Event::ProofRevoked(sender, proof_id));
+
+      Ok(().into())
+    }
+
+    /// Anchor a batch of Proofs through the root of the Merkle tree built over the CIDs of their
+    /// `ProofData`, so that each Proof can later be shown to be part of the batch without being
+    /// stored on chain individually
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * workflow_id - the id of the Workflow that generated the Proofs
+    /// * merkle_root - the root of the Merkle tree
+    /// * leaves - the count of Proofs in the batch
+    ///
+    /// # Errors
+    /// * `BadRequest` - if the batch is empty
+    /// * `NoSuchWorkflow` - if the Workflow does not exist
+    /// * `ProofBatchAlreadyAnchored` - if the caller already anchored the same batch
+    ///
+    /// # Events
+    /// * `ProofBatchCreated` - the batch was anchored
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::create_proof_batch())]
+    pub fn create_proof_batch(
+      origin: OriginFor<T>,
+      workflow_id: WorkflowId,
+      merkle_root: MerkleRoot,
+      leaves: u32,
+    ) -> DispatchResultWithPostInfo {
+      let sender = ensure_signed(origin)?;
+
+      ensure!(leaves > 0, Error::<T>::BadRequest);
+
+      let workflows = workflows::Pallet::<T>::get_workflows_by_ids(vec![workflow_id.clone()], 0, 1);
+      ensure!(!workflows.is_empty(), Error::<T>::NoSuchWorkflow);
+
+      ensure!(
+        !ProofBatchByMerkleRootAndAccountId::<T>::contains_key(merkle_root, &sender),
+        Error::<T>::ProofBatchAlreadyAnchored
+      );
+
+      let batch = ProofBatchInfo {
+        merkle_root,
+        workflow_id,
+        leaves,
+        account_id: sender.clone(),
+        block_number: <frame_system::Pallet<T>>::block_number(),
+      };
+      Self::do_create_proof_batch(&batch);
+
+      Self::deposit_event(Event::ProofBatchCreated(sender, merkle_root));
+
+      Ok(().into())
+    }
+
+    /// Mint an NFT representing a Proof claimed by the caller into the account of the caller, so
+    /// that it can be traded or displayed in NFT wallets. The NFT belongs to a collection owned by
+    /// the pallet account and carries the ProofId as metadata. A Proof represented by an NFT can
+    /// no longer be revoked
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * proof_id - the id of the Proof to represent
+    ///
+    /// # Errors
+    /// * `NoSuchProof` - if the caller does not own the Proof
+    /// * `ProofNftAlreadyMinted` - if the caller already minted the NFT of the Proof
+    /// * `ProofNftMintError` - if the `ProofNftMinter` fails to create the collection or the NFT
+    ///
+    /// # Events
+    /// * `ProofNftMinted` - the NFT was minted
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::mint_proof_nft())]
+    pub fn mint_proof_nft(origin: OriginFor<T>, proof_id: ProofId) -> DispatchResultWithPostInfo {
+      let sender = ensure_signed(origin)?;
+
+      ensure!(
+        ProofByProofIdAndAccountId::<T>::contains_key(&proof_id, &sender),
+        Error::<T>::NoSuchProof
+      );
+      ensure!(
+        !ProofNftByProofIdAndAccountId::<T>::contains_key(&proof_id, &sender),
+        Error::<T>::ProofNftAlreadyMinted
+      );
+
+      let nft = Self::do_mint_proof_nft(&proof_id, &sender)?;
+
+      Self::deposit_event(Event::ProofNftMinted(sender, proof_id, nft.collection_id, nft.item_id));
+
+      Ok(().into())
+    }
   }
 }
@@ -0,0 +1,390 @@
+// This file is part of Anagolay Network.
+
+// Copyright (C) 2019-2023 Anagolay Network.
+
+//! Storage migrations of the poe pallet. Each module `vN` contains the logic to upgrade the
+//! storage from version `N - 1` to version `N`.
+
+use super::*;
+use crate::types::PHASH_INDEX_BANDS;
+use core::convert::TryFrom;
+#[cfg(feature = "try-runtime")]
+use frame_support::sp_std::vec::Vec;
+use frame_support::{pallet_prelude::*, traits::StorageVersion};
+
+/// Run all the migrations needed to bring the on-chain storage to the current
+/// [`STORAGE_VERSION`]. They all run in the block of the upgrade, [`estimate_weight`] tells
+/// whether they fit in it
+///
+/// # Return
+/// The weight consumed by the migrations
+pub fn migrate<T: Config>() -> Weight {
+  let mut weight = T::DbWeight::get().reads(1);
+
+  if Pallet::<T>::on_chain_storage_version() < 1 {
+    weight = weight.saturating_add(v1::migrate::<T>());
+  }
+  if Pallet::<T>::on_chain_storage_version() < 2 {
+    weight = weight.saturating_add(v2::migrate::<T>());
+  }
+  if Pallet::<T>::on_chain_storage_version() < 3 {
+    weight = weight.saturating_add(v3::migrate::<T>());
+  }
+  if Pallet::<T>::on_chain_storage_version() < 4 {
+    weight = weight.saturating_add(v4::migrate::<T>());
+  }
+  if Pallet::<T>::on_chain_storage_version() < 5 {
+    weight = weight.saturating_add(v5::migrate::<T>());
+  }
+  if Pallet::<T>::on_chain_storage_version() < 6 {
+    weight = weight.saturating_add(v6::migrate::<T>());
+  }
+
+  weight
+}
+
+/// Estimate the weight of the migrations needed to bring the on-chain storage to the current
+/// [`STORAGE_VERSION`], out of the count of the stored entries they visit
+///
+/// # Arguments
+///  * proofs - The count of the entries of `ProofByProofIdAndAccountId`
+///  * phashes - The count of the entries of `PhashByHashAndAccountId`
+///  * bands - The count of the entries of `PhashesByIndexBand`
+///
+/// # Return
+/// The estimated weight of [`migrate`]
+pub fn estimate_weight<T: Config>(proofs: u64, phashes: u64, bands: u64) -> Weight {
+  let version = Pallet::<T>::on_chain_storage_version();
+  let indexed_bands = phashes.saturating_mul(PHASH_INDEX_BANDS as u64);
+  let mut reads = 1u64;
+  let mut writes = 0u64;
+
+  if version < 2 {
+    reads = reads.saturating_add(phashes.saturating_mul(2));
+    writes = writes.saturating_add(phashes);
+  }
+  if version < 3 {
+    reads = reads.saturating_add(proofs);
+    writes = writes.saturating_add(proofs);
+  }
+  if version < 4 {
+    reads = reads.saturating_add(proofs).saturating_add(phashes);
+    writes = writes.saturating_add(proofs).saturating_add(phashes);
+  }
+  if version < 5 {
+    reads = reads
+      .saturating_add(phashes.saturating_mul(2))
+      .saturating_add(indexed_bands);
+    writes = writes
+      .saturating_add(phashes)
+      .saturating_add(bands)
+      .saturating_add(indexed_bands);
+  }
+  if version < 6 {
+    reads = reads.saturating_add(3);
+    writes = writes.saturating_add(3);
+  }
+
+  // Every migration stores its version
+  T::DbWeight::get().reads_writes(reads, writes.saturating_add(6))
+}
+
+/// Count the entries visited by the migrations before the upgrade and check that the migrations
+/// fit in a block, since they all run in the block of the upgrade
+///
+/// # Return
+/// The encoded count of the entries of `ProofByProofIdAndAccountId` and `PhashByHashAndAccountId`
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<Vec<u8>, &'static str> {
+  let proofs = ProofByProofIdAndAccountId::<T>::iter_keys().count() as u64;
+  let phashes = PhashByHashAndAccountId::<T>::iter_keys().count() as u64;
+  let bands = PhashesByIndexBand::<T>::iter_keys().count() as u64;
+
+  let max_block = <T as frame_system::Config>::BlockWeights::get().max_block;
+  ensure!(
+    estimate_weight::<T>(proofs, phashes, bands).ref_time() <= max_block.ref_time(),
+    "The poe migrations do not fit in a block"
+  );
+
+  Ok((proofs, phashes).encode())
+}
+
+/// Check the storage after the upgrade: no Proof or perceptual hash is lost, every entry decodes
+/// in the current format and every perceptual hash is indexed by its owner and by its Proof
+///
+/// # Arguments
+///  * state - The output of [`pre_upgrade`]
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>(state: Vec<u8>) -> Result<(), &'static str> {
+  let (proofs, phashes): (u64, u64) =
+    Decode::decode(&mut &state[..]).map_err(|_| "The state of the poe pre_upgrade can not be decoded")?;
+
+  ensure!(
+    Pallet::<T>::on_chain_storage_version() == STORAGE_VERSION,
+    "The poe migrations did not complete"
+  );
+  ensure!(
+    ProofByProofIdAndAccountId::<T>::iter().count() as u64 == proofs,
+    "Proofs were lost by the migration or do not decode in the current format"
+  );
+  ensure!(
+    PhashByHashAndAccountId::<T>::iter().count() as u64 == phashes,
+    "Perceptual hashes were lost by the migration or do not decode in the current format"
+  );
+  ensure!(
+    ProofByProofIdAndAccountId::<T>::iter_keys()
+      .all(|(proof_id, account_id)| ProofIdsByAccountId::<T>::contains_key(account_id, proof_id)),
+    "Proofs are not all indexed by their owner"
+  );
+  ensure!(
+    PhashByHashAndAccountId::<T>::iter_keys()
+      .all(|(hash, account_id)| PhashDigestsByAccountId::<T>::contains_key(account_id, hash)),
+    "Perceptual hashes are not all indexed by their owner"
+  );
+  // Perceptual hashes left out of their Proof would not be removed when the Proof is revoked
+  ensure!(
+    PhashByHashAndAccountId::<T>::iter().all(|(hash, account_id, phash_info)| {
+      PhashDigestsByProofIdAndAccountId::<T>::get(&phash_info.proof_id, account_id).contains(&hash)
+    }),
+    "Perceptual hashes exceeding MAX_PHASHES_PER_PROOF are not associated to their Proof"
+  );
+
+  Ok(())
+}
+
+/// Version 1: the bands of every perceptual hash are indexed in `PhashesByIndexBand`
+pub mod v1 {
+  use super::*;
+
+  /// Bump the storage version only: the perceptual hashes are still stored without their
+  /// algorithm, so their bands are indexed by [`v5::migrate`], once they are translated
+  ///
+  /// # Return
+  /// The weight consumed by the migration
+  pub fn migrate<T: Config>() -> Weight {
+    StorageVersion::new(1).put::<Pallet<T>>();
+
+    T::DbWeight::get().writes(1)
+  }
+}
+
+/// Version 2: the digests of the perceptual hashes are associated to their Proof in
+/// `PhashDigestsByProofIdAndAccountId`
+pub mod v2 {
+  use super::*;
+
+  /// Associate every stored perceptual hash to its Proof. Perceptual hashes exceeding
+  /// `MAX_PHASHES_PER_PROOF` for the same Proof are not associated, hence they would not be removed
+  /// along with the Proof: [`post_upgrade`] fails if any is found
+  ///
+  /// # Return
+  /// The weight consumed by the migration
+  pub fn migrate<T: Config>() -> Weight {
+    let mut associated = 0u64;
+
+    // The perceptual hashes are still stored without their algorithm
+    v5::PhashByHashAndAccountId::<T>::iter().for_each(|(hash, account_id, phash_info)| {
+      associated += 1;
+      PhashDigestsByProofIdAndAccountId::<T>::mutate(&phash_info.proof_id, &account_id, |digests| {
+        let _ = digests.try_push(hash);
+      });
+    });
+
+    StorageVersion::new(2).put::<Pallet<T>>();
+
+    T::DbWeight::get().reads_writes(associated.saturating_mul(2), associated.saturating_add(1))
+  }
+}
+
+/// Version 3: the `prev_id` of a [`ProofData`] references the parent Proof rather than a Workflow
+pub mod v3 {
+  use super::*;
+  use crate::types::{MaxProofParamsGet, ProofData, ProofExtra, ProofRecord};
+  use anagolay_support::{constants::MaxGroupsGet, Characters, CreatorId, ForWhat};
+  use verification::types::VerificationContext;
+  use workflows::types::WorkflowId;
+
+  /// Structure representing the data of a Proof as stored before version 3
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+  pub struct OldProofData {
+    pub workflow_id: WorkflowId,
+    pub prev_id: WorkflowId,
+    pub creator: CreatorId,
+    pub groups: BoundedVec<ForWhat, MaxGroupsGet>,
+    pub params: BoundedVec<Characters, MaxProofParamsGet>,
+    pub context: VerificationContext,
+  }
+
+  /// Structure representing a Proof as stored before version 3
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+  pub struct OldProof {
+    pub id: ProofId,
+    pub data: OldProofData,
+    pub extra: Option<ProofExtra>,
+  }
+
+  /// Structure representing the record of a Proof as stored before version 3
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+  pub struct OldProofRecord<AccountId, BlockNumber> {
+    pub record: OldProof,
+    pub account_id: AccountId,
+    pub block_number: BlockNumber,
+  }
+
+  impl From<OldProofData> for ProofData {
+    fn from(old_data: OldProofData) -> Self {
+      ProofData {
+        workflow_id: old_data.workflow_id,
+        // The Workflow formerly referenced cannot be a parent Proof
+        prev_id: None,
+        creator: old_data.creator,
+        groups: old_data.groups,
+        params: old_data.params,
+        context: old_data.context,
+      }
+    }
+  }
+
+  /// Translate every stored Proof into the new format, leaving it without a parent. The ids of
+  /// the Proofs are preserved
+  ///
+  /// # Return
+  /// The weight consumed by the migration
+  pub fn migrate<T: Config>() -> Weight {
+    let mut translated = 0u64;
+
+    ProofByProofIdAndAccountId::<T>::translate::<OldProofRecord<T::AccountId, T::BlockNumber>, _>(
+      |_, _, old_record| {
+        translated += 1;
+        Some(ProofRecord::<T> {
+          record: Proof {
+            id: old_record.record.id,
+            data: old_record.record.data.into(),
+            extra: old_record.record.extra,
+          },
+          account_id: old_record.account_id,
+          block_number: old_record.block_number,
+        })
+      },
+    );
+
+    StorageVersion::new(3).put::<Pallet<T>>();
+
+    T::DbWeight::get().reads_writes(translated, translated.saturating_add(1))
+  }
+}
+
+/// Version 4: Proofs and perceptual hashes are indexed by their owner in `ProofIdsByAccountId` and
+/// `PhashDigestsByAccountId`
+pub mod v4 {
+  use super::*;
+
+  /// Index every stored Proof and perceptual hash by its owner
+  ///
+  /// # Return
+  /// The weight consumed by the migration
+  pub fn migrate<T: Config>() -> Weight {
+    let mut indexed = 0u64;
+
+    ProofByProofIdAndAccountId::<T>::iter_keys().for_each(|(proof_id, account_id)| {
+      indexed += 1;
+      ProofIdsByAccountId::<T>::insert(account_id, proof_id, ());
+    });
+    PhashByHashAndAccountId::<T>::iter_keys().for_each(|(hash, account_id)| {
+      indexed += 1;
+      PhashDigestsByAccountId::<T>::insert(account_id, hash, ());
+    });
+
+    StorageVersion::new(4).put::<Pallet<T>>();
+
+    T::DbWeight::get().reads_writes(indexed, indexed.saturating_add(1))
+  }
+}
+
+/// Version 5: a [`PhashInfo`] records the algorithm that computed the perceptual hash, which is
+/// part of the bands indexed in `PhashesByIndexBand`
+pub mod v5 {
+  use super::*;
+  use crate::types::{MaxPHashLenGet, PhashAlgorithm};
+
+  /// Structure representing a perceptual hash as stored before version 5
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, Default, RuntimeDebug, TypeInfo)]
+  pub struct OldPhashInfo {
+    pub p_hash: BoundedVec<u8, MaxPHashLenGet>,
+    pub proof_id: ProofId,
+  }
+
+  /// `PhashByHashAndAccountId` as stored before version 5
+  #[frame_support::storage_alias]
+  pub type PhashByHashAndAccountId<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::Hash,
+    Twox64Concat,
+    <T as frame_system::Config>::AccountId,
+    OldPhashInfo,
+    ValueQuery,
+  >;
+
+  /// Translate every stored perceptual hash into the new format with the `Legacy` algorithm, then
+  /// index the bands again since they now include the algorithm. The digests used as keys are
+  /// preserved
+  ///
+  /// # Return
+  /// The weight consumed by the migration
+  pub fn migrate<T: Config>() -> Weight {
+    let mut translated = 0u64;
+
+    crate::PhashByHashAndAccountId::<T>::translate::<OldPhashInfo, _>(|_, _, old_phash_info| {
+      translated += 1;
+      Some(PhashInfo {
+        algorithm: PhashAlgorithm::Legacy,
+        p_hash: old_phash_info.p_hash,
+        proof_id: old_phash_info.proof_id,
+      })
+    });
+
+    // The empty bands are removed on revocation, so every indexed band holds some of the
+    // perceptual hashes just translated
+    let bands = translated.saturating_mul(PHASH_INDEX_BANDS as u64);
+    let cleared = PhashesByIndexBand::<T>::clear(u32::try_from(bands).unwrap_or(u32::MAX), None).unique as u64;
+    crate::PhashByHashAndAccountId::<T>::iter().for_each(|(hash, account_id, phash_info)| {
+      Pallet::<T>::do_index_phash(&phash_info, &hash, &account_id);
+    });
+
+    StorageVersion::new(5).put::<Pallet<T>>();
+
+    T::DbWeight::get().reads_writes(
+      translated.saturating_mul(2).saturating_add(bands),
+      translated
+        .saturating_add(cleared)
+        .saturating_add(bands)
+        .saturating_add(1),
+    )
+  }
+}
+
+/// Version 6: the collection of the Proof NFTs is created by the upgrade, owned by the pallet
+/// account, instead of at the first mint
+pub mod v6 {
+  use super::*;
+
+  /// Create the collection of the Proof NFTs, unless it was already created by a mint
+  ///
+  /// # Return
+  /// The weight consumed by the migration
+  pub fn migrate<T: Config>() -> Weight {
+    let mut weight = T::DbWeight::get().reads_writes(1, 1);
+
+    if ProofNftCollectionId::<T>::get().is_none() {
+      // If the collection cannot be created, the mints will try again and fail with
+      // `ProofNftMintError`
+      let _ = Pallet::<T>::do_create_proof_nft_collection();
+      weight = weight.saturating_add(T::DbWeight::get().reads_writes(2, 3));
+    }
+
+    StorageVersion::new(6).put::<Pallet<T>>();
+
+    weight
+  }
+}
@@ -21,8 +21,12 @@
 #![cfg(test)]
 
 use crate as poe;
-use crate::{types::PoeVerificationKeyGenerator, Config};
-use frame_support::{parameter_types, traits::UnixTime};
+use crate::{
+  types::{NaiveProofRevocationHandler, PoeVerificationInvalidator, PoeVerificationKeyGenerator, ProofNftMinter},
+  Config,
+};
+use codec::Encode;
+use frame_support::{dispatch::DispatchResult, parameter_types, storage, traits::UnixTime, PalletId};
 use sp_core::{sr25519, sr25519::Signature, H256};
 use sp_runtime::{
   testing::{Header, TestXt, UintAuthorityId},
@@ -32,7 +36,6 @@ use std::{
   convert::{TryFrom, TryInto},
   time::Duration,
 };
-use verification::types::NaiveVerificationInvalidator;
 
 type Extrinsic = TestXt<RuntimeCall, ()>;
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -113,7 +116,7 @@ impl verification::Config for Test {
   type AuthorityId = verification::crypto::VerificationAuthId;
   type RuntimeEvent = RuntimeEvent;
   type VerificationKeyGenerator = PoeVerificationKeyGenerator<Self>;
-  type VerificationInvalidator = NaiveVerificationInvalidator<Self>;
+  type VerificationInvalidator = PoeVerificationInvalidator<Self>;
   type WeightInfo = ();
   type Currency = ();
 
@@ -149,11 +152,62 @@ impl UnixTime for MockTime {
   }
 }
 
+/// Proof NFT minter keeping the owner of each NFT in the unhashed storage
+#[derive(Clone)]
+pub struct MockProofNftMinter;
+
+impl MockProofNftMinter {
+  fn key(collection: &u32, item: &u32) -> Vec<u8> {
+    (b"proof_nft", collection, item).encode()
+  }
+
+  /// Retrieves the owner of a collection
+  pub fn collection_owner(collection: &u32) -> Option<sr25519::Public> {
+    storage::unhashed::get(&(b"proof_nft_collection", collection).encode())
+  }
+
+  /// Transfers a Proof NFT, as the non-fungible tokens pallet would do
+  pub fn transfer(collection: &u32, item: &u32, destination: &sr25519::Public) {
+    storage::unhashed::put(&Self::key(collection, item), destination);
+  }
+}
+
+impl ProofNftMinter<Test> for MockProofNftMinter {
+  type CollectionId = u32;
+  type ItemId = u32;
+
+  fn create_collection(collection: &u32, owner: &sr25519::Public) -> DispatchResult {
+    storage::unhashed::put(&(b"proof_nft_collection", collection).encode(), owner);
+    Ok(())
+  }
+
+  fn mint(collection: &u32, item: &u32, owner: &sr25519::Public, _metadata: Vec<u8>) -> DispatchResult {
+    Self::transfer(collection, item, owner);
+    Ok(())
+  }
+
+  fn owner(collection: &u32, item: &u32) -> Option<sr25519::Public> {
+    storage::unhashed::get(&Self::key(collection, item))
+  }
+}
+
+parameter_types! {
+  pub const ProofNftCollectionId: u32 = u32::MAX;
+}
+
 impl Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type WeightInfo = ();
+  type ProofRevocationHandler = NaiveProofRevocationHandler<Self>;
+  type ProofNftMinter = MockProofNftMinter;
+  type ProofNftCollectionId = ProofNftCollectionId;
+
+  const PALLET_ID: PalletId = PalletId(*b"anag/poe");
 
-  const MAX_PROOFS_PER_WORKFLOW: u32 = 1;
+  const MAX_PROOFS_PER_VERIFICATION_CONTEXT: u32 = 2;
+  const MAX_PHASHES_PER_INDEX_BAND: u32 = 2;
+  const MAX_PHASHES_PER_PROOF: u32 = 4;
+  const MAX_DERIVED_PROOFS_PER_PROOF: u32 = 2;
 }
 
 impl workflows::Config for Test {
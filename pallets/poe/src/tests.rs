@@ -20,19 +20,41 @@
 
 #![cfg(test)]
 use super::{mock::*, *};
-use crate::types::{PoeVerificationKeyGenerator, ProofData, ProofId};
-use anagolay_support::{AnagolayArtifactStructure, AnagolayStructureData, ArtifactId};
+use crate::types::{
+  PhashAlgorithm, PoeVerificationInvalidator, PoeVerificationKeyGenerator, ProofBatchInfo, ProofData, ProofId,
+  ProofNft, SimilarPhash,
+};
+use anagolay_support::{
+  generic_id::GenericId, AnagolayArtifactStructure, AnagolayStructureData, ArtifactId, Characters, ForWhat,
+};
+use codec::Encode;
 use core::convert::TryInto;
-use frame_support::{assert_noop, assert_ok, traits::UnixTime};
+use frame_support::{
+  assert_noop, assert_ok,
+  sp_runtime::traits::Hash,
+  storage,
+  traits::{GetStorageVersion, StorageVersion, UnixTime},
+  BoundedVec,
+};
+use operations::types::OperationVersionId;
 use sp_core::{sr25519, Pair};
-use verification::types::{VerificationContext, VerificationKeyGenerator};
+use verification::types::{
+  VerificationAction, VerificationContext, VerificationInvalidator, VerificationKeyGenerator, VerificationRequest,
+  VerificationStatus,
+};
 use workflows::types::{
-  Workflow, WorkflowArtifactType, WorkflowData, WorkflowId, WorkflowVersion, WorkflowVersionData, WorkflowVersionExtra,
-  WorkflowVersionId,
+  Workflow, WorkflowArtifactType, WorkflowData, WorkflowId, WorkflowSegment, WorkflowVersion, WorkflowVersionData,
+  WorkflowVersionExtra, WorkflowVersionId,
 };
 
 const PERCEPTUAL_HASH: &[u8] = b"0x30303030303030303031313030303030303030303030303030303031313130303031313131313030313131313131313031313131313131313131313131313130303031313130303030303030303030303131313131313130303030303030303031313131313131313130303030303030313131313131313131313131313030303131313131313131313131313031313131313131313131313130313030313130313131303030303030303130303030303030303030303031303030303030303031313131313131313131313131313131313131313131313130303030303030303131313130303030303030303030303031313131303030303030303030303030";
 
+fn perceptual_hash_algorithm() -> PhashAlgorithm {
+  PhashAlgorithm::Operation(OperationVersionId::from(
+    "bafkr4ih2xmsije6aa6yfwjdfmztnnkbb6ip56g3ojfcyfgjx6jsh6bogoe",
+  ))
+}
+
 fn mock_account(ss58: &str) -> sr25519::Public {
   let (pair, _) = sr25519::Pair::from_string_with_seed(ss58, None).unwrap();
   pair.public()
@@ -72,6 +94,7 @@ fn mock_request() -> (Workflow, WorkflowVersion) {
 pub fn build_default_proof_data(workflow_id: WorkflowId) -> ProofData {
   let mut proof_data = ProofData::default();
   proof_data.workflow_id = workflow_id;
+  proof_data.creator = "tester".into();
   proof_data
 }
 
@@ -90,6 +113,21 @@ fn proof_create_default() {
   });
 }
 
+/// A successful verification request of the holder for the context
+fn mock_successful_request(
+  holder: sr25519::Public,
+  context: &VerificationContext,
+) -> VerificationRequest<sr25519::Public> {
+  VerificationRequest {
+    context: context.clone(),
+    action: VerificationAction::DnsTxtRecord,
+    holder,
+    status: VerificationStatus::Success,
+    key: "anagolay-domain-verification=test".into(),
+    id: None,
+  }
+}
+
 #[test]
 fn proof_create_for_verification_context() {
   new_test_ext().execute_with(|| {
@@ -114,12 +152,98 @@ fn proof_create_for_verification_context() {
       "Proof key does not correspond to the generated one"
     );
 
-    let proofs_by_context = ProofIdsByVerificationContext::<Test>::get(context).unwrap();
+    // The Proof is associated to the VerificationContext only once the verification succeeds
+    assert!(ProofIdsByVerificationContext::<Test>::get(&context).is_none());
+    assert_eq!(
+      Some(proof_id.clone()),
+      PendingProofIdByAccountIdAndVerificationContext::<Test>::get(holder, &context)
+    );
+
+    PoeVerificationKeyGenerator::<Test>::confirm(&mock_successful_request(holder, &context)).unwrap();
+
+    let proofs_by_context = ProofIdsByVerificationContext::<Test>::get(&context).unwrap();
     assert_eq!(
       proofs_by_context.into_iter().next().unwrap(),
       proof_id,
       "Proof id was not associated to the VerificationContext"
     );
+    assert!(PendingProofIdByAccountIdAndVerificationContext::<Test>::get(holder, &context).is_none());
+  });
+}
+
+#[test]
+fn proof_create_for_verification_context_accumulates() {
+  new_test_ext().execute_with(|| {
+    let alice = mock_account("//Alice");
+    let bob = mock_account("//Bob");
+    let charlie = mock_account("//Charlie");
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    // The pending verification requests do not fill the VerificationContext
+    PoeVerificationKeyGenerator::<Test>::generate(&charlie, &context, "//Charlieanagolay.network".as_bytes().to_vec())
+      .unwrap();
+    for (holder, identifier) in [(alice, "//Aliceanagolay.network"), (bob, "//Bobanagolay.network")] {
+      PoeVerificationKeyGenerator::<Test>::generate(&holder, &context, identifier.as_bytes().to_vec()).unwrap();
+      PoeVerificationKeyGenerator::<Test>::confirm(&mock_successful_request(holder, &context)).unwrap();
+    }
+    // Verifying the same context again does not duplicate the Proof
+    PoeVerificationKeyGenerator::<Test>::generate(&alice, &context, "//Aliceanagolay.network".as_bytes().to_vec())
+      .unwrap();
+    PoeVerificationKeyGenerator::<Test>::confirm(&mock_successful_request(alice, &context)).unwrap();
+
+    let proofs_by_context = ProofIdsByVerificationContext::<Test>::get(&context).unwrap();
+    assert_eq!(proofs_by_context.len(), 2, "Proofs of both holders must be kept");
+    assert_eq!(TestPoe::proof_total(), 3);
+    proofs_by_context.iter().for_each(|proof_id| {
+      assert_eq!(ProofByProofIdAndAccountId::<Test>::iter_prefix(proof_id).count(), 1);
+    });
+
+    assert_noop!(
+      PoeVerificationKeyGenerator::<Test>::confirm(&mock_successful_request(charlie, &context)),
+      verification::Error::<Test>::VerificationConfirmationError
+    );
+  });
+}
+
+#[test]
+fn proof_remove_from_verification_context_on_invalidation() {
+  new_test_ext().execute_with(|| {
+    let alice = mock_account("//Alice");
+    let bob = mock_account("//Bob");
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    for (holder, identifier) in [(alice, "//Aliceanagolay.network"), (bob, "//Bobanagolay.network")] {
+      PoeVerificationKeyGenerator::<Test>::generate(&holder, &context, identifier.as_bytes().to_vec()).unwrap();
+      PoeVerificationKeyGenerator::<Test>::confirm(&mock_successful_request(holder, &context)).unwrap();
+    }
+    let proofs_by_context = ProofIdsByVerificationContext::<Test>::get(&context).unwrap();
+    assert_eq!(proofs_by_context.len(), 2);
+
+    let request = |holder: sr25519::Public, status: VerificationStatus| VerificationRequest {
+      context: context.clone(),
+      action: VerificationAction::DnsTxtRecord,
+      holder,
+      status,
+      key: "anagolay-domain-verification=test".into(),
+      id: None,
+    };
+
+    // The failed verification of a holder only removes the Proof of that holder
+    PoeVerificationInvalidator::<Test>::invalidate(&request(
+      alice,
+      VerificationStatus::Failure("an error description".into()),
+    ))
+    .unwrap();
+    let proofs_by_context = ProofIdsByVerificationContext::<Test>::get(&context).unwrap();
+    assert_eq!(proofs_by_context.len(), 1);
+    let bob_proof_id = proofs_by_context[0].clone();
+    assert!(ProofByProofIdAndAccountId::<Test>::contains_key(&bob_proof_id, bob));
+    assert_eq!(TestPoe::proof_total(), 2, "Proofs must not be revoked");
+
+    // Once the last holder is invalidated, the context has no Proofs
+    PoeVerificationInvalidator::<Test>::invalidate(&request(bob, VerificationStatus::Success)).unwrap();
+    assert!(ProofIdsByVerificationContext::<Test>::get(&context).is_none());
+    assert_eq!(TestPoe::proof_total(), 2, "Proofs must not be revoked");
   });
 }
 
@@ -145,6 +269,77 @@ fn proof_error_on_duplicate() {
   });
 }
 
+#[test]
+fn proof_error_on_bad_request() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    let mut pd = build_default_proof_data(wf_id.clone());
+    pd.creator = "".into();
+    assert_noop!(TestPoe::create_proof(origin.clone(), pd), Error::<Test>::BadRequest);
+
+    let mut pd = build_default_proof_data(wf_id.clone());
+    pd.creator = "not well formed".into();
+    assert_noop!(TestPoe::create_proof(origin.clone(), pd), Error::<Test>::BadRequest);
+
+    let mut pd = build_default_proof_data(wf_id);
+    pd.params = vec!["".into()].try_into().unwrap();
+    assert_noop!(TestPoe::create_proof(origin, pd), Error::<Test>::BadRequest);
+  });
+}
+
+#[test]
+fn proof_error_on_workflow_mismatch() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (mut wf, wf_ver) = mock_request();
+    wf.data.groups = vec![ForWhat::GENERIC].try_into().unwrap();
+    wf.data.segments = vec![WorkflowSegment {
+      inputs: vec![-1].try_into().unwrap(),
+      sequence: vec![].try_into().unwrap(),
+    }]
+    .try_into()
+    .unwrap();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    // Missing the external input
+    let mut pd = build_default_proof_data(wf_id.clone());
+    pd.groups = vec![ForWhat::GENERIC].try_into().unwrap();
+    assert_noop!(
+      TestPoe::create_proof(origin.clone(), pd),
+      Error::<Test>::ProofWorkflowTypeMismatch
+    );
+
+    // Missing the group
+    let mut pd = build_default_proof_data(wf_id.clone());
+    pd.params = vec!["input".into()].try_into().unwrap();
+    assert_noop!(
+      TestPoe::create_proof(origin.clone(), pd),
+      Error::<Test>::ProofWorkflowTypeMismatch
+    );
+
+    // Group not belonging to the Workflow
+    let mut pd = build_default_proof_data(wf_id.clone());
+    pd.params = vec!["input".into()].try_into().unwrap();
+    pd.groups = vec![ForWhat::PHOTO].try_into().unwrap();
+    assert_noop!(
+      TestPoe::create_proof(origin.clone(), pd),
+      Error::<Test>::ProofWorkflowTypeMismatch
+    );
+
+    let mut pd = build_default_proof_data(wf_id);
+    pd.params = vec!["input".into()].try_into().unwrap();
+    pd.groups = vec![ForWhat::GENERIC].try_into().unwrap();
+    assert_ok!(TestPoe::create_proof(origin, pd));
+  });
+}
+
 #[test]
 fn phash_save_phash() {
   new_test_ext().execute_with(|| {
@@ -160,6 +355,7 @@ fn phash_save_phash() {
 
     let phash = PERCEPTUAL_HASH.to_vec();
     let p_hash_payload = PhashInfo {
+      algorithm: perceptual_hash_algorithm(),
       p_hash: phash.clone().try_into().unwrap(),
       proof_id: pd.to_cid(),
     };
@@ -184,6 +380,7 @@ fn phash_save_phash_error_on_duplicate() {
     let phash = PERCEPTUAL_HASH.to_vec();
 
     let p_hash_payload = PhashInfo {
+      algorithm: perceptual_hash_algorithm(),
       p_hash: phash.clone().try_into().unwrap(),
       proof_id: pd.to_cid(),
     };
@@ -204,6 +401,7 @@ fn phash_save_phash_error_no_proof() {
         let proof_id = ProofId::from("0x6261666b32627a616365616d6c6e766678726c717175743274686f6b6c6a76726b68726f7a787562696a78746f3476743566646f776c6162747733686177");
 
         let p_hash_payload = PhashInfo {
+        algorithm: perceptual_hash_algorithm(),
         p_hash: phash.clone().try_into().unwrap(),
         proof_id: proof_id.clone(),
         };
@@ -213,6 +411,656 @@ fn phash_save_phash_error_no_proof() {
     });
 }
 
+#[test]
+fn revoke_proof() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let account = mock_account("//Alice");
+    let other = mock_account("//Bob");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    let pd = build_default_proof_data(wf_id);
+    let proof_id = pd.to_cid();
+    assert_ok!(TestPoe::create_proof(origin.clone(), pd));
+    let phash_info = PhashInfo {
+      algorithm: perceptual_hash_algorithm(),
+      p_hash: PERCEPTUAL_HASH.to_vec().try_into().unwrap(),
+      proof_id: proof_id.clone(),
+    };
+    assert_ok!(TestPoe::save_phash(origin.clone(), phash_info.clone()));
+    assert_eq!(1, TestPoe::proof_total());
+    assert_eq!(1, TestPoe::phash_total());
+
+    assert_noop!(
+      TestPoe::revoke_proof(mock::RuntimeOrigin::signed(other), proof_id.clone(), false),
+      Error::<Test>::NoSuchProof
+    );
+
+    assert_ok!(TestPoe::revoke_proof(origin.clone(), proof_id.clone(), false));
+    System::assert_last_event(mock::RuntimeEvent::TestPoe(Event::ProofRevoked(
+      account,
+      proof_id.clone(),
+    )));
+
+    assert!(ProofByProofIdAndAccountId::<Test>::get(&proof_id, account).is_none());
+    assert!(PhashDigestsByProofIdAndAccountId::<Test>::get(&proof_id, account).is_empty());
+    assert!(!ProofIdsByAccountId::<Test>::contains_key(account, &proof_id));
+    assert_eq!(0, PhashDigestsByAccountId::<Test>::iter_key_prefix(account).count());
+    assert!(TestPoe::get_phashes(None, None, 0, 10).is_empty());
+    assert!(TestPoe::find_similar_phashes(perceptual_hash_algorithm(), PERCEPTUAL_HASH.to_vec(), 0).is_empty());
+    assert_eq!(0, TestPoe::proof_total());
+    assert_eq!(0, TestPoe::phash_total());
+
+    assert_noop!(
+      TestPoe::revoke_proof(origin, proof_id, false),
+      Error::<Test>::NoSuchProof
+    );
+  });
+}
+
+#[test]
+fn revoke_proof_for_verification_context() {
+  new_test_ext().execute_with(|| {
+    let holder = mock_account("//Alice");
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    PoeVerificationKeyGenerator::<Test>::generate(&holder, &context, b"//Aliceanagolay.network".to_vec()).unwrap();
+    PoeVerificationKeyGenerator::<Test>::confirm(&mock_successful_request(holder, &context)).unwrap();
+    let (proof_id, _, _) = ProofByProofIdAndAccountId::<Test>::iter().next().unwrap();
+
+    assert_ok!(TestPoe::revoke_proof(
+      mock::RuntimeOrigin::signed(holder),
+      proof_id,
+      false
+    ));
+    assert!(ProofIdsByVerificationContext::<Test>::get(context).is_none());
+  });
+}
+
+#[test]
+fn phash_save_phash_error_max_phashes_per_proof() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    let pd = build_default_proof_data(wf_id);
+    assert_ok!(TestPoe::create_proof(origin.clone(), pd.clone()));
+
+    let phash_info = |i: u8| PhashInfo {
+      algorithm: PhashAlgorithm::AHash,
+      p_hash: vec![i; 8].try_into().unwrap(),
+      proof_id: pd.to_cid(),
+    };
+    for i in 0..<Test as Config>::MAX_PHASHES_PER_PROOF {
+      assert_ok!(TestPoe::save_phash(origin.clone(), phash_info(i as u8)));
+    }
+    assert_noop!(
+      TestPoe::save_phash(origin, phash_info(u8::MAX)),
+      Error::<Test>::MaxPhashesPerProofLimitReached
+    );
+  });
+}
+
+#[test]
+fn phash_save_phash_error_on_bad_request() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    let pd = build_default_proof_data(wf_id);
+    assert_ok!(TestPoe::create_proof(origin.clone(), pd.clone()));
+
+    let phash_info = |algorithm: PhashAlgorithm, len: usize| PhashInfo {
+      algorithm,
+      p_hash: vec![0u8; len].try_into().unwrap(),
+      proof_id: pd.to_cid(),
+    };
+    assert_noop!(
+      TestPoe::save_phash(origin.clone(), phash_info(PhashAlgorithm::Legacy, 8)),
+      Error::<Test>::BadRequest
+    );
+    assert_noop!(
+      TestPoe::save_phash(origin.clone(), phash_info(PhashAlgorithm::AHash, 16)),
+      Error::<Test>::BadRequest
+    );
+    assert_noop!(
+      TestPoe::save_phash(origin.clone(), phash_info(PhashAlgorithm::Blockhash, 16)),
+      Error::<Test>::BadRequest
+    );
+    assert_noop!(
+      TestPoe::save_phash(origin.clone(), phash_info(perceptual_hash_algorithm(), 0)),
+      Error::<Test>::BadRequest
+    );
+
+    assert_ok!(TestPoe::save_phash(
+      origin.clone(),
+      phash_info(PhashAlgorithm::PHashDct, 8)
+    ));
+    assert_ok!(TestPoe::save_phash(
+      origin.clone(),
+      phash_info(PhashAlgorithm::Blockhash, 18)
+    ));
+    assert_ok!(TestPoe::save_phash(origin, phash_info(perceptual_hash_algorithm(), 3)));
+  });
+}
+
+#[test]
+fn proof_lineage() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    let root = build_default_proof_data(wf_id.clone());
+    let root_id = root.to_cid();
+
+    let mut orphan = build_default_proof_data(wf_id.clone());
+    orphan.prev_id = Some(root_id.clone());
+    assert_noop!(
+      TestPoe::create_proof(origin.clone(), orphan),
+      Error::<Test>::NoSuchParentProof
+    );
+
+    assert_ok!(TestPoe::create_proof(origin.clone(), root));
+    let mut derived = build_default_proof_data(wf_id.clone());
+    derived.prev_id = Some(root_id.clone());
+    let derived_id = derived.to_cid();
+    assert_ok!(TestPoe::create_proof(origin.clone(), derived));
+    let mut leaf = build_default_proof_data(wf_id);
+    leaf.prev_id = Some(derived_id.clone());
+    let leaf_id = leaf.to_cid();
+    assert_ok!(TestPoe::create_proof(origin.clone(), leaf));
+
+    assert_eq!(
+      vec![derived_id.clone()],
+      DerivedProofIdsByProofId::<Test>::get(&root_id).into_inner()
+    );
+    let tree: Vec<ProofId> = TestPoe::get_proof_tree(leaf_id.clone())
+      .into_iter()
+      .map(|proof_info| proof_info.proof.id)
+      .collect();
+    assert_eq!(vec![root_id.clone(), derived_id.clone(), leaf_id.clone()], tree);
+    assert_eq!(3, TestPoe::get_proof_tree(root_id.clone()).len());
+
+    assert_noop!(
+      TestPoe::revoke_proof(origin.clone(), derived_id.clone(), false),
+      Error::<Test>::ProofHasDerivedProofs
+    );
+    assert_ok!(TestPoe::revoke_proof(origin.clone(), leaf_id, false));
+    assert!(DerivedProofIdsByProofId::<Test>::get(&derived_id).is_empty());
+    assert_eq!(2, TestPoe::get_proof_tree(root_id).len());
+  });
+}
+
+#[test]
+fn migrate_proof_prev_id() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let proof_id = ProofId::from("bafkr4ih2xmsije6aa6yfwjdfmztnnkbb6ip56g3ojfcyfgjx6jsh6bogoe");
+
+    StorageVersion::new(2).put::<TestPoe>();
+    let old_record = migration::v3::OldProofRecord {
+      record: migration::v3::OldProof {
+        id: proof_id.clone(),
+        data: migration::v3::OldProofData {
+          workflow_id: WorkflowId::from("bafkr4ih2xmsije6aa6yfwjdfmztnnkbb6ip56g3ojfcyfgjx6jsh6bogoe"),
+          prev_id: WorkflowId::from("bafkr4ih2xmsije6aa6yfwjdfmztnnkbb6ip56g3ojfcyfgjx6jsh6bogoe"),
+          creator: "tester".into(),
+          groups: vec![].try_into().unwrap(),
+          params: vec![].try_into().unwrap(),
+          context: VerificationContext::Unbounded,
+        },
+        extra: None,
+      },
+      account_id: account,
+      block_number: 10u64,
+    };
+    storage::unhashed::put(
+      &ProofByProofIdAndAccountId::<Test>::hashed_key_for(proof_id.clone(), account),
+      &old_record,
+    );
+
+    migration::migrate::<Test>();
+
+    let record = ProofByProofIdAndAccountId::<Test>::get(&proof_id, account).unwrap();
+    assert_eq!(proof_id, record.record.id);
+    assert_eq!(None, record.record.data.prev_id);
+    assert_eq!(Characters::from("tester"), record.record.data.creator);
+    assert_eq!(10u64, record.block_number);
+    assert!(ProofIdsByAccountId::<Test>::contains_key(account, &proof_id));
+    assert_eq!(StorageVersion::new(6), TestPoe::on_chain_storage_version());
+  });
+}
+
+#[test]
+fn proof_batch_anchoring() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let account = mock_account("//Alice");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    let proof_cids: Vec<ProofId> = (0..5)
+      .map(|i| {
+        let mut pd = build_default_proof_data(wf_id.clone());
+        pd.creator = format!("tester{}", i).as_str().into();
+        pd.to_cid()
+      })
+      .collect();
+    let tree = poe_merkle::MerkleTree::new(&proof_cids.iter().map(|cid| cid.as_bytes()).collect::<Vec<_>>());
+    let merkle_root = tree.root().unwrap();
+
+    assert_noop!(
+      TestPoe::create_proof_batch(origin.clone(), wf_id.clone(), merkle_root, 0),
+      Error::<Test>::BadRequest
+    );
+    assert_noop!(
+      TestPoe::create_proof_batch(origin.clone(), WorkflowId::from("no-such-workflow"), merkle_root, 5),
+      Error::<Test>::NoSuchWorkflow
+    );
+    assert_ok!(TestPoe::create_proof_batch(
+      origin.clone(),
+      wf_id.clone(),
+      merkle_root,
+      5
+    ));
+    System::assert_last_event(mock::RuntimeEvent::TestPoe(Event::ProofBatchCreated(
+      account,
+      merkle_root,
+    )));
+    assert_noop!(
+      TestPoe::create_proof_batch(origin, wf_id.clone(), merkle_root, 5),
+      Error::<Test>::ProofBatchAlreadyAnchored
+    );
+
+    let batch = ProofBatchInfo {
+      merkle_root,
+      workflow_id: wf_id.clone(),
+      leaves: 5,
+      account_id: account,
+      block_number: 1,
+    };
+    proof_cids.iter().enumerate().for_each(|(index, proof_cid)| {
+      let merkle_path = tree.path(index).unwrap();
+      assert_eq!(
+        vec![batch.clone()],
+        TestPoe::verify_inclusion(proof_cid.clone(), merkle_path)
+      );
+    });
+
+    let mut not_included = build_default_proof_data(wf_id);
+    not_included.creator = "tester5".into();
+    assert!(TestPoe::verify_inclusion(not_included.to_cid(), tree.path(0).unwrap()).is_empty());
+    assert!(TestPoe::verify_inclusion(proof_cids[0].clone(), tree.path(1).unwrap()).is_empty());
+  });
+}
+
+#[test]
+fn proof_nft_minting() {
+  new_test_ext().execute_with(|| {
+    System::set_block_number(1);
+    let account = mock_account("//Alice");
+    let other = mock_account("//Bob");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    let pd = build_default_proof_data(wf_id);
+    let proof_id = pd.to_cid();
+    assert_ok!(TestPoe::create_proof(origin.clone(), pd));
+
+    assert_noop!(
+      TestPoe::mint_proof_nft(mock::RuntimeOrigin::signed(other), proof_id.clone()),
+      Error::<Test>::NoSuchProof
+    );
+    assert_ok!(TestPoe::mint_proof_nft(origin.clone(), proof_id.clone()));
+    System::assert_last_event(mock::RuntimeEvent::TestPoe(Event::ProofNftMinted(
+      account,
+      proof_id.clone(),
+      u32::MAX,
+      0,
+    )));
+    assert_noop!(
+      TestPoe::mint_proof_nft(origin.clone(), proof_id.clone()),
+      Error::<Test>::ProofNftAlreadyMinted
+    );
+
+    let nft = ProofNft {
+      collection_id: u32::MAX,
+      item_id: 0,
+    };
+    assert_eq!(Some(u32::MAX), TestPoe::proof_nft_collection_id());
+    assert_eq!(1, TestPoe::next_proof_nft_item_id());
+    assert_eq!(
+      Some((proof_id.clone(), account)),
+      ProofClaimByProofNftItemId::<Test>::get(0)
+    );
+    let info = TestPoe::get_proof_nft(proof_id.clone(), account).unwrap();
+    assert_eq!(nft, info.nft);
+    assert_eq!(proof_id, info.proof.proof.id);
+    assert_eq!(Some(account), info.nft_owner);
+
+    // The transfer of the NFT is reflected by the queries, while the claim stays unchanged
+    MockProofNftMinter::transfer(&u32::MAX, &0, &other);
+    let info = TestPoe::get_proof_by_nft(0).unwrap();
+    assert_eq!(account, info.proof.account_id);
+    assert_eq!(Some(other), info.nft_owner);
+    assert!(TestPoe::get_proof_by_nft(1).is_none());
+
+    assert_noop!(
+      TestPoe::revoke_proof(origin, proof_id, false),
+      Error::<Test>::ProofHasNft
+    );
+  });
+}
+
+#[test]
+fn migrate_proof_nft_collection() {
+  new_test_ext().execute_with(|| {
+    StorageVersion::new(5).put::<TestPoe>();
+    assert_eq!(None, TestPoe::proof_nft_collection_id());
+
+    migration::migrate::<Test>();
+
+    assert_eq!(StorageVersion::new(6), TestPoe::on_chain_storage_version());
+    assert_eq!(Some(u32::MAX), TestPoe::proof_nft_collection_id());
+    assert_eq!(
+      Some(TestPoe::account_id()),
+      MockProofNftMinter::collection_owner(&u32::MAX)
+    );
+  });
+}
+
+#[test]
+fn get_proofs_and_phashes() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let other = mock_account("//Bob");
+    let origin = mock::RuntimeOrigin::signed(account);
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(origin.clone(), wf.data, wf_ver.data).unwrap();
+
+    let pd = build_default_proof_data(wf_id);
+    assert_ok!(TestPoe::create_proof(origin.clone(), pd.clone()));
+    let proof_id = pd.to_cid();
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    PoeVerificationKeyGenerator::<Test>::generate(&other, &context, b"//Bobanagolay.network".to_vec()).unwrap();
+
+    let phash_info = PhashInfo {
+      algorithm: perceptual_hash_algorithm(),
+      p_hash: PERCEPTUAL_HASH.to_vec().try_into().unwrap(),
+      proof_id: proof_id.clone(),
+    };
+    assert_ok!(TestPoe::save_phash(origin, phash_info.clone()));
+
+    assert_eq!(2, TestPoe::get_proofs_by_ids(vec![], 0, 10).len());
+    assert_eq!(1, TestPoe::get_proofs_by_ids(vec![], 1, 10).len());
+    let proofs = TestPoe::get_proofs_by_ids(vec![proof_id.clone()], 0, 10);
+    assert_eq!(1, proofs.len());
+    assert_eq!(proof_id, proofs[0].proof.id);
+    assert_eq!(account, proofs[0].account_id);
+
+    let proofs = TestPoe::get_proofs_by_account(account, 0, 10);
+    assert_eq!(1, proofs.len());
+    assert_eq!(proof_id, proofs[0].proof.id);
+
+    let proofs = TestPoe::get_proofs_by_context(context.clone(), 0, 10);
+    assert_eq!(1, proofs.len());
+    assert_eq!(other, proofs[0].account_id);
+    assert_eq!(context, proofs[0].proof.data.context);
+    assert!(TestPoe::get_proofs_by_context(VerificationContext::Unbounded, 0, 10).is_empty());
+
+    assert_eq!(vec![phash_info.clone()], TestPoe::get_phashes(None, None, 0, 10));
+    assert_eq!(
+      vec![phash_info],
+      TestPoe::get_phashes(Some(account), Some(proof_id), 0, 10)
+    );
+    assert!(TestPoe::get_phashes(Some(other), None, 0, 10).is_empty());
+  });
+}
+
+#[test]
+fn get_proofs_by_account_pages() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let other = mock_account("//Bob");
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(mock::RuntimeOrigin::signed(account), wf.data, wf_ver.data).unwrap();
+
+    let mut proof_ids: Vec<ProofId> = (0..5)
+      .map(|index| {
+        let mut pd = build_default_proof_data(wf_id.clone());
+        pd.creator = format!("tester {}", index).as_str().into();
+        assert_ok!(TestPoe::create_proof(mock::RuntimeOrigin::signed(account), pd.clone()));
+        pd.to_cid()
+      })
+      .collect();
+    proof_ids.sort();
+    let mut pd = build_default_proof_data(wf_id);
+    pd.creator = "other tester".into();
+    assert_ok!(TestPoe::create_proof(mock::RuntimeOrigin::signed(other), pd));
+
+    // Consecutive pages cover every Proof of the account exactly once
+    let pages: Vec<Vec<ProofId>> = vec![0u64, 2u64, 4u64]
+      .into_iter()
+      .map(|offset| {
+        TestPoe::get_proofs_by_account(account, offset, 2)
+          .into_iter()
+          .map(|proof_info| {
+            assert_eq!(account, proof_info.account_id);
+            proof_info.proof.id
+          })
+          .collect()
+      })
+      .collect();
+    assert_eq!(vec![2, 2, 1], pages.iter().map(Vec::len).collect::<Vec<usize>>());
+    let mut paged_ids: Vec<ProofId> = pages.into_iter().flatten().collect();
+    paged_ids.sort();
+    assert_eq!(proof_ids, paged_ids);
+
+    // Past the last Proof of the account, the page is empty
+    assert!(TestPoe::get_proofs_by_account(account, 5, 2).is_empty());
+    assert!(TestPoe::get_proofs_by_account(account, u64::MAX, 2).is_empty());
+    assert!(TestPoe::get_proofs_by_account(account, 0, 0).is_empty());
+    assert!(TestPoe::get_proofs_by_account(mock_account("//Charlie"), 0, 10).is_empty());
+
+    // A limit beyond the Proofs of the account is clamped to the remaining ones
+    assert_eq!(5, TestPoe::get_proofs_by_account(account, 0, u16::MAX).len());
+    assert_eq!(2, TestPoe::get_proofs_by_account(account, 3, u16::MAX).len());
+    assert_eq!(1, TestPoe::get_proofs_by_account(other, 0, u16::MAX).len());
+  });
+}
+
+#[test]
+fn find_similar_phashes() {
+  new_test_ext().execute_with(|| {
+    let alice = mock_account("//Alice");
+    let bob = mock_account("//Bob");
+    let (wf, wf_ver) = mock_request();
+    let wf_id = wf.data.clone().to_cid();
+    Workflows::create(mock::RuntimeOrigin::signed(alice), wf.data, wf_ver.data).unwrap();
+
+    let pd = build_default_proof_data(wf_id);
+    assert_ok!(TestPoe::create_proof(mock::RuntimeOrigin::signed(alice), pd.clone()));
+    assert_ok!(TestPoe::create_proof(mock::RuntimeOrigin::signed(bob), pd.clone()));
+
+    let phash_info = |algorithm: PhashAlgorithm, p_hash: Vec<u8>| PhashInfo {
+      algorithm,
+      p_hash: p_hash.try_into().unwrap(),
+      proof_id: pd.to_cid(),
+    };
+    let mut near = vec![0u8; 32];
+    near[0] = 0b0000_0001;
+    let base = phash_info(PhashAlgorithm::DHash, vec![0u8; 32]);
+    let far = phash_info(PhashAlgorithm::DHash, vec![0xFFu8; 32]);
+    let short = phash_info(PhashAlgorithm::DHash, vec![0u8; 8]);
+    let other_algorithm = phash_info(PhashAlgorithm::AHash, vec![0u8; 32]);
+
+    assert_ok!(TestPoe::save_phash(mock::RuntimeOrigin::signed(alice), base.clone()));
+    assert_ok!(TestPoe::save_phash(mock::RuntimeOrigin::signed(alice), far.clone()));
+    assert_ok!(TestPoe::save_phash(mock::RuntimeOrigin::signed(alice), short.clone()));
+    assert_ok!(TestPoe::save_phash(
+      mock::RuntimeOrigin::signed(alice),
+      other_algorithm.clone()
+    ));
+
+    // Retrieved through the multi-index hashing
+    let band_digest =
+      <Test as frame_system::Config>::Hashing::hash(&PhashInfo::index_bands(&PhashAlgorithm::DHash, &near)[1]);
+    assert_eq!(1, PhashesByIndexBand::<Test>::get(band_digest).len());
+    assert_eq!(
+      vec![SimilarPhash {
+        phash_info: base.clone(),
+        account_id: alice,
+        distance: 1,
+      }],
+      TestPoe::find_similar_phashes(PhashAlgorithm::DHash, near.clone(), 1)
+    );
+    assert!(TestPoe::find_similar_phashes(PhashAlgorithm::DHash, near.clone(), 0).is_empty());
+
+    // The bands shared by the base and the near perceptual hashes are now full
+    assert_ok!(TestPoe::save_phash(
+      mock::RuntimeOrigin::signed(bob),
+      phash_info(PhashAlgorithm::DHash, near.clone())
+    ));
+    assert_eq!(2, PhashesByIndexBand::<Test>::get(band_digest).len());
+    assert_eq!(
+      vec![
+        SimilarPhash {
+          phash_info: base.clone(),
+          account_id: alice,
+          distance: 0,
+        },
+        SimilarPhash {
+          phash_info: phash_info(PhashAlgorithm::DHash, near),
+          account_id: bob,
+          distance: 1,
+        }
+      ],
+      TestPoe::find_similar_phashes(PhashAlgorithm::DHash, base.p_hash.to_vec(), 1)
+    );
+
+    // Distances exceeding the bands are answered by a full scan
+    let similar = TestPoe::find_similar_phashes(PhashAlgorithm::DHash, base.p_hash.to_vec(), 256);
+    assert_eq!(3, similar.len());
+    assert_eq!(far, similar[2].phash_info);
+    assert_eq!(256, similar[2].distance);
+
+    // Perceptual hashes of different algorithms are never compared
+    assert_eq!(
+      vec![SimilarPhash {
+        phash_info: other_algorithm,
+        account_id: alice,
+        distance: 0,
+      }],
+      TestPoe::find_similar_phashes(PhashAlgorithm::AHash, base.p_hash.to_vec(), 256)
+    );
+    assert!(TestPoe::find_similar_phashes(PhashAlgorithm::PHashDct, base.p_hash.to_vec(), 256).is_empty());
+
+    assert_eq!(
+      short,
+      TestPoe::find_similar_phashes(PhashAlgorithm::DHash, short.p_hash.to_vec(), 0)[0].phash_info
+    );
+    assert!(TestPoe::find_similar_phashes(PhashAlgorithm::DHash, vec![], 8).is_empty());
+  });
+}
+
+#[test]
+fn migrate_phash_index() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let old_phash_info = migration::v5::OldPhashInfo {
+      p_hash: PERCEPTUAL_HASH.to_vec().try_into().unwrap(),
+      proof_id: ProofId::from("bafkr4ih2xmsije6aa6yfwjdfmztnnkbb6ip56g3ojfcyfgjx6jsh6bogoe"),
+    };
+    let phash_info_digest = old_phash_info.using_encoded(<Test as frame_system::Config>::Hashing::hash);
+    let phash_info = PhashInfo {
+      algorithm: PhashAlgorithm::Legacy,
+      p_hash: old_phash_info.p_hash.clone(),
+      proof_id: old_phash_info.proof_id.clone(),
+    };
+
+    StorageVersion::new(0).put::<TestPoe>();
+    migration::v5::PhashByHashAndAccountId::<Test>::insert(phash_info_digest, account, old_phash_info);
+    assert!(TestPoe::find_similar_phashes(PhashAlgorithm::Legacy, PERCEPTUAL_HASH.to_vec(), 0).is_empty());
+
+    migration::migrate::<Test>();
+
+    assert_eq!(StorageVersion::new(6), TestPoe::on_chain_storage_version());
+    assert_eq!(
+      phash_info,
+      PhashByHashAndAccountId::<Test>::get(phash_info_digest, account)
+    );
+    assert!(PhashDigestsByAccountId::<Test>::contains_key(
+      account,
+      phash_info_digest
+    ));
+    assert_eq!(
+      vec![phash_info.clone()],
+      TestPoe::get_phashes(Some(account), None, 0, 10)
+    );
+    assert_eq!(
+      vec![phash_info_digest],
+      PhashDigestsByProofIdAndAccountId::<Test>::get(&phash_info.proof_id, account).into_inner()
+    );
+    assert_eq!(
+      vec![SimilarPhash {
+        phash_info,
+        account_id: account,
+        distance: 0,
+      }],
+      TestPoe::find_similar_phashes(PhashAlgorithm::Legacy, PERCEPTUAL_HASH.to_vec(), 0)
+    );
+  });
+}
+
+#[test]
+fn migrate_phash_index_clears_stale_bands() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let old_phash_info = migration::v5::OldPhashInfo {
+      p_hash: PERCEPTUAL_HASH.to_vec().try_into().unwrap(),
+      proof_id: ProofId::from("bafkr4ih2xmsije6aa6yfwjdfmztnnkbb6ip56g3ojfcyfgjx6jsh6bogoe"),
+    };
+    let phash_info_digest = old_phash_info.using_encoded(<Test as frame_system::Config>::Hashing::hash);
+    let stale_band = <Test as frame_system::Config>::Hashing::hash(b"stale band");
+
+    StorageVersion::new(4).put::<TestPoe>();
+    migration::v5::PhashByHashAndAccountId::<Test>::insert(phash_info_digest, account, old_phash_info);
+    PhashesByIndexBand::<Test>::insert(
+      stale_band,
+      BoundedVec::truncate_from(vec![(phash_info_digest, account)]),
+    );
+    let estimated_weight = migration::estimate_weight::<Test>(0, 1, 1);
+
+    let weight = migration::migrate::<Test>();
+
+    assert!(weight.ref_time() <= estimated_weight.ref_time());
+    assert_eq!(StorageVersion::new(6), TestPoe::on_chain_storage_version());
+    assert!(!PhashesByIndexBand::<Test>::contains_key(stale_band));
+    assert_eq!(
+      1,
+      TestPoe::find_similar_phashes(PhashAlgorithm::Legacy, PERCEPTUAL_HASH.to_vec(), 0).len()
+    );
+  });
+}
+
 #[test]
 fn test_template() {
   new_test_ext().execute_with(|| {});
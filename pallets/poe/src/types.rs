@@ -21,22 +21,77 @@ use codec::{Decode, Encode};
 use core::convert::TryInto;
 use frame_support::{
   pallet_prelude::*,
-  sp_runtime::RuntimeDebug,
+  sp_runtime::{traits::AtLeast32BitUnsigned, RuntimeDebug},
   sp_std::{clone::Clone, default::Default, vec, vec::Vec},
 };
-use verification::types::{VerificationContext, VerificationKeyGenerator};
-use workflows::types::WorkflowId;
+use operations::types::OperationVersionId;
+use verification::types::{VerificationContext, VerificationInvalidator, VerificationKeyGenerator, VerificationRequest};
+use workflows::types::{WorkflowData, WorkflowId};
 
 getter_for_hardcoded_constant!(MaxPHashLen, u32, 1024);
 getter_for_hardcoded_constant!(MaxProofParams, u32, 16);
 
+/// Number of bands a perceptual hash is split into for the multi-index hashing. Two perceptual
+/// hashes whose Hamming distance is lower than this value share at least one identical band
+pub const PHASH_INDEX_BANDS: usize = 8;
+
 // Proof id
 anagolay_generic_id!(Proof);
 
+/// Algorithm that computed a perceptual hash. Only perceptual hashes computed by the same
+/// algorithm are comparable
+#[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum PhashAlgorithm {
+  /// Perceptual hash saved before the algorithm was recorded, it can't be saved anymore
+  #[default]
+  Legacy,
+  /// Average hash, one bit per pixel of the downscaled image
+  AHash,
+  /// Difference hash, one bit per gradient between adjacent pixels of the downscaled image
+  DHash,
+  /// Perceptual hash based on the discrete cosine transform of the downscaled image
+  PHashDct,
+  /// Block mean value hash
+  Blockhash,
+  /// Perceptual hash computed by the referenced Operation version
+  Operation(OperationVersionId),
+}
+
+impl PhashAlgorithm {
+  /// Check that a perceptual hash of the given length can be computed by this algorithm.
+  /// Average, difference and DCT hashes are square bit matrices with a side of 8, 16 or 32 bits,
+  /// blockhashes are square bit matrices with a side multiple of 4 bits. The length of the
+  /// perceptual hashes computed by an Operation is not known upfront, so any non empty one is
+  /// accepted
+  ///
+  /// # Arguments
+  ///  * len - The length in bytes of the perceptual hash
+  ///
+  /// # Return
+  /// True if the length is valid for this algorithm, false otherwise
+  pub fn is_valid_len(&self, len: usize) -> bool {
+    let bits = len.saturating_mul(8);
+    match self {
+      PhashAlgorithm::Legacy => false,
+      PhashAlgorithm::AHash | PhashAlgorithm::DHash | PhashAlgorithm::PHashDct => {
+        [8, 16, 32].iter().any(|side| side * side == bits)
+      }
+      PhashAlgorithm::Blockhash => (1..)
+        .map(|side| side * 4)
+        .take_while(|side| side * side <= bits)
+        .any(|side| side * side == bits),
+      PhashAlgorithm::Operation(_) => len > 0,
+    }
+  }
+}
+
 /// Perceptive hash information, what gets stored
 #[derive(Encode, Decode, Clone, Eq, PartialEq, Default, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct PhashInfo {
+  /// The algorithm that computed the perceptive hash
+  pub algorithm: PhashAlgorithm,
   /// The perceptive hash bytes
   pub p_hash: BoundedVec<u8, MaxPHashLenGet>,
   /// The id of the proof associated to this perceptive hash
@@ -49,8 +104,8 @@ pub struct PhashInfo {
 pub struct ProofData {
   /// The id of the Workflow that generated this Proof
   pub workflow_id: WorkflowId,
-  /// which workflow is executed
-  pub prev_id: WorkflowId,
+  /// The id of the Proof this one is derived from (e.g. the original of a cropped image)
+  pub prev_id: Option<ProofId>,
   /// Identifier of the creator user or system as a reference to his account id on the blockchain,
   /// pgp key or email
   pub creator: CreatorId,
@@ -66,7 +121,44 @@ impl AnagolayStructureData for ProofData {
   type Id = ProofId;
 
   fn validate(&self) -> Result<(), Characters> {
-    Ok(())
+    let creator_is_well_formed = frame_support::sp_std::str::from_utf8(self.creator.as_bytes())
+      .map(|creator| !creator.is_empty() && !creator.chars().any(|c| c.is_whitespace() || c.is_control()))
+      .unwrap_or(false);
+    if !creator_is_well_formed {
+      Err("ProofData.creator: must be a non empty string without whitespaces or control characters".into())
+    } else if self.params.iter().any(|param| param.is_empty()) {
+      Err("ProofData.params: every parameter must be non empty".into())
+    } else {
+      Ok(())
+    }
+  }
+}
+
+impl ProofData {
+  /// Check that this Proof is compatible with the definition of the Workflow that generated it
+  ///
+  /// # Arguments
+  ///  * workflow - The data of the Workflow referenced by `workflow_id`
+  ///
+  /// # Return
+  /// Unit type if the Proof matches the Workflow, a description of the mismatch otherwise
+  pub fn validate_for_workflow(&self, workflow: &WorkflowData) -> Result<(), Characters> {
+    // Every input acquired from the outside world is expected to be provided as a parameter
+    let expected_params = workflow
+      .segments
+      .iter()
+      .flat_map(|segment| segment.inputs.iter())
+      .filter(|input| **input < 0)
+      .count();
+    if self.params.len() != expected_params {
+      Err("ProofData.params: count must match the external inputs of the Workflow segments".into())
+    } else if self.groups.iter().any(|group| !workflow.groups.contains(group)) {
+      Err("ProofData.groups: every group must be one of the Workflow groups".into())
+    } else if self.groups.is_empty() && !workflow.groups.is_empty() {
+      Err("ProofData.groups: at least one of the Workflow groups is required".into())
+    } else {
+      Ok(())
+    }
   }
 }
 
@@ -74,7 +166,7 @@ impl Default for ProofData {
   fn default() -> Self {
     ProofData {
       workflow_id: WorkflowId::default(),
-      prev_id: WorkflowId::default(),
+      prev_id: None,
       groups: BoundedVec::with_bounded_capacity(0),
       creator: CreatorId::default(),
       params: BoundedVec::with_bounded_capacity(0),
@@ -94,6 +186,133 @@ anagolay_structure!(Proof, ProofId, ProofData, ProofExtra);
 // This produces `ProofRecord<T>`,  the Storage record of the Proof.
 anagolay_record!(Proof);
 
+/// A Proof along with its owner and the block where it was created, as returned by RPCs
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofInfo<AccountId, BlockNumber> {
+  /// The Proof
+  pub proof: Proof,
+  /// The owner of the Proof
+  pub account_id: AccountId,
+  /// Block where the Proof was created
+  pub block_number: BlockNumber,
+}
+
+/// The non-fungible token representing the claim of a Proof by an account
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofNft<CollectionId, ItemId> {
+  /// The identifier of the collection of the Proof NFTs
+  pub collection_id: CollectionId,
+  /// The identifier of the NFT inside the collection
+  pub item_id: ItemId,
+}
+
+/// A Proof along with the NFT representing it and the current owner of the NFT, as returned by
+/// RPCs
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofNftInfo<AccountId, BlockNumber, CollectionId, ItemId> {
+  /// The Proof, along with the account that claimed it and minted the NFT
+  pub proof: ProofInfo<AccountId, BlockNumber>,
+  /// The NFT representing the Proof
+  pub nft: ProofNft<CollectionId, ItemId>,
+  /// The current owner of the NFT, which may differ from the claimer once the NFT is transferred
+  pub nft_owner: Option<AccountId>,
+}
+
+/// The root of the Merkle tree built over the CIDs of the `ProofData` of a batch
+pub type MerkleRoot = poe_merkle::H256;
+
+/// A batch of Proofs anchored on chain through the root of the Merkle tree built over their CIDs
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct ProofBatchInfo<AccountId, BlockNumber> {
+  /// The root of the Merkle tree
+  pub merkle_root: MerkleRoot,
+  /// The id of the Workflow that generated the Proofs of the batch
+  pub workflow_id: WorkflowId,
+  /// The count of Proofs in the batch
+  pub leaves: u32,
+  /// The owner of the batch
+  pub account_id: AccountId,
+  /// Block where the batch was anchored
+  pub block_number: BlockNumber,
+}
+
+/// A perceptual hash similar to a searched one, along with its owner and its distance from the
+/// searched one, as returned by RPCs
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct SimilarPhash<AccountId> {
+  /// The perceptual hash and the Proof it is associated to
+  pub phash_info: PhashInfo,
+  /// The owner of the perceptual hash
+  pub account_id: AccountId,
+  /// Hamming distance, in bits, from the searched perceptual hash
+  pub distance: u32,
+}
+
+impl PhashInfo {
+  /// Check that the perceptual hash can be computed by its algorithm
+  ///
+  /// # Return
+  /// Unit type if the perceptual hash is valid, a description of the problem otherwise
+  pub fn validate(&self) -> Result<(), Characters> {
+    if self.algorithm == PhashAlgorithm::Legacy {
+      Err("PhashInfo.algorithm: must not be Legacy".into())
+    } else if !self.algorithm.is_valid_len(self.p_hash.len()) {
+      Err("PhashInfo.p_hash: length is not valid for the algorithm".into())
+    } else {
+      Ok(())
+    }
+  }
+
+  /// Compute the Hamming distance between this perceptual hash and another one
+  ///
+  /// # Arguments
+  ///  * algorithm - The algorithm that computed the other perceptual hash
+  ///  * p_hash - The other perceptual hash
+  ///
+  /// # Return
+  /// The count of differing bits, or None if the perceptual hashes have different algorithms or
+  /// lengths
+  pub fn hamming_distance(&self, algorithm: &PhashAlgorithm, p_hash: &[u8]) -> Option<u32> {
+    if self.algorithm != *algorithm || self.p_hash.len() != p_hash.len() {
+      return None;
+    }
+    Some(
+      self
+        .p_hash
+        .iter()
+        .zip(p_hash.iter())
+        .map(|(a, b)| (a ^ b).count_ones())
+        .sum(),
+    )
+  }
+
+  /// Split a perceptual hash in contiguous bands, used as keys of the multi-index hashing. The
+  /// count of bands is [`PHASH_INDEX_BANDS`], or the length of the perceptual hash if shorter
+  ///
+  /// # Arguments
+  ///  * algorithm - The algorithm that computed the perceptual hash
+  ///  * p_hash - The perceptual hash to split
+  ///
+  /// # Return
+  /// Collection of bands, each one encoded along with its position, the algorithm and the length
+  /// of the perceptual hash so that bands of different hashes only collide if they are comparable
+  pub fn index_bands(algorithm: &PhashAlgorithm, p_hash: &[u8]) -> Vec<Vec<u8>> {
+    let len = p_hash.len();
+    let bands = PHASH_INDEX_BANDS.min(len);
+    (0..bands)
+      .map(|band| {
+        let chunk = &p_hash[band * len / bands..(band + 1) * len / bands];
+        (algorithm, len as u32, band as u8, chunk).encode()
+      })
+      .collect()
+  }
+}
+
 #[derive(Clone)]
 pub struct PoeVerificationKeyGenerator<T: crate::Config> {
   _marker: PhantomData<T>,
@@ -118,8 +337,7 @@ impl<T: crate::Config> VerificationKeyGenerator<T> for PoeVerificationKeyGenerat
     let proof_data = ProofData {
       // @FIXME expose get_id() as Workflow trait method in next iteration
       workflow_id: "bafkr4icflbi5pbomtcyejivr4l7dcdvcmvcsviwmnn7qp52flfnkvy2ebe".into(),
-      // @FIXME this is unused
-      prev_id: "".into(),
+      prev_id: None,
       // @FIXME this is scale encoded, not ss58 encoded (no ss58 codec in nostd)
       creator: holder
         .encode()
@@ -140,7 +358,191 @@ impl<T: crate::Config> VerificationKeyGenerator<T> for PoeVerificationKeyGenerat
 
     let proof = Proof::new(proof_data);
 
-    <crate::Pallet<T>>::do_create_proofs_of_verification(holder, context, vec![proof]);
+    <crate::Pallet<T>>::do_create_proof_of_verification(holder, context, proof);
     Ok(cid)
   }
+
+  /// Associates the Proof created for the verification request to its context, once the
+  /// verification is successful
+  ///
+  /// # Arguments
+  /// * request - The verification request
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn confirm(request: &VerificationRequest<T::AccountId>) -> Result<(), verification::Error<T>> {
+    <crate::Pallet<T>>::do_confirm_proof_of_verification(&request.holder, &request.context)
+      .map_err(|_| verification::Error::<T>::VerificationConfirmationError)
+  }
+}
+
+/// Implementation for the verification invalidator that removes the Proofs of the holder from the
+/// Proofs of the invalidated context, so that the context only lists the Proofs of its valid
+/// holders. The Proofs stay owned by the holder
+#[derive(Clone)]
+pub struct PoeVerificationInvalidator<T: crate::Config> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: crate::Config> VerificationInvalidator<T> for PoeVerificationInvalidator<T> {
+  /// Called when a verification request fails or turns out to be no longer valid
+  ///
+  /// # Arguments
+  /// * request - The verification request
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn invalidate(request: &VerificationRequest<T::AccountId>) -> Result<(), verification::Error<T>> {
+    <crate::Pallet<T>>::do_remove_proofs_of_verification(&request.holder, &request.context);
+    Ok(())
+  }
+}
+
+/// A trait that allows other pallets to take part in the revocation of a Proof, either by
+/// preventing it while they reference the Proof or by removing their own entities referencing it.
+/// The default implementation [`NaiveProofRevocationHandler`] considers Proofs never referenced.
+///
+/// # Type arguments
+/// - T: the runtime `Config`
+pub trait ProofRevocationHandler<T: crate::Config>: Clone {
+  /// Tells whether some entity references the Proof
+  ///
+  /// # Arguments
+  /// * proof_id - The id of the Proof
+  ///
+  /// # Return
+  /// True if the Proof is referenced, false otherwise
+  fn is_referenced(proof_id: &ProofId) -> bool;
+
+  /// Called when a Proof is revoked along with the entities referencing it
+  ///
+  /// # Arguments
+  /// * proof_id - The id of the Proof
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn revoke(proof_id: &ProofId) -> Result<(), crate::Error<T>>;
+
+  /// The upper bound of the weight consumed by `revoke()`
+  ///
+  /// # Return
+  /// The weight of the revocation of the entities referencing a Proof
+  fn revoke_weight() -> Weight;
+}
+
+/// Default implementation for the proof revocation handler
+#[derive(Clone)]
+pub struct NaiveProofRevocationHandler<T: crate::Config> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: crate::Config> ProofRevocationHandler<T> for NaiveProofRevocationHandler<T> {
+  /// Tells whether some entity references the Proof
+  ///
+  /// # Arguments
+  /// * proof_id - The id of the Proof
+  ///
+  /// # Return
+  /// Always false
+  fn is_referenced(_proof_id: &ProofId) -> bool {
+    false
+  }
+
+  /// Called when a Proof is revoked along with the entities referencing it
+  ///
+  /// # Arguments
+  /// * proof_id - The id of the Proof
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn revoke(_proof_id: &ProofId) -> Result<(), crate::Error<T>> {
+    Ok(())
+  }
+
+  /// The upper bound of the weight consumed by `revoke()`
+  ///
+  /// # Return
+  /// The weight of the revocation of the entities referencing a Proof
+  fn revoke_weight() -> Weight {
+    Weight::zero()
+  }
+}
+
+/// A trait that allows to define how the NFTs representing the Proofs are minted. The default
+/// implementation [`NaiveProofNftMinter`] mints nothing, so that runtimes without a non-fungible
+/// tokens pallet can still use the poe pallet. However, the pallet configuration allow to define
+/// another implementation of this trait, for instance backed by `pallet_uniques`.
+///
+/// # Type arguments
+/// - T: the runtime `Config`
+pub trait ProofNftMinter<T: frame_system::Config>: Clone {
+  /// The identifier of the collection of the Proof NFTs
+  type CollectionId: Member + Parameter + MaxEncodedLen + Copy + Default + AtLeast32BitUnsigned;
+  /// The identifier of a Proof NFT inside its collection
+  type ItemId: Member + Parameter + MaxEncodedLen + Copy + Default + AtLeast32BitUnsigned;
+
+  /// Creates the collection of the Proof NFTs
+  ///
+  /// # Arguments
+  /// * collection - The identifier of the collection
+  /// * owner - The owner of the collection
+  ///
+  /// # Return
+  /// `DispatchResult` containing Unit type
+  fn create_collection(collection: &Self::CollectionId, owner: &T::AccountId) -> DispatchResult;
+
+  /// Mints a Proof NFT into the account of the owner of the Proof
+  ///
+  /// # Arguments
+  /// * collection - The identifier of the collection
+  /// * item - The identifier of the NFT
+  /// * owner - The account receiving the NFT
+  /// * metadata - The [`ProofId`] bytes
+  ///
+  /// # Return
+  /// `DispatchResult` containing Unit type
+  fn mint(
+    collection: &Self::CollectionId,
+    item: &Self::ItemId,
+    owner: &T::AccountId,
+    metadata: Vec<u8>,
+  ) -> DispatchResult;
+
+  /// Retrieves the current owner of a Proof NFT
+  ///
+  /// # Arguments
+  /// * collection - The identifier of the collection
+  /// * item - The identifier of the NFT
+  ///
+  /// # Return
+  /// The owner of the NFT, if it exists
+  fn owner(collection: &Self::CollectionId, item: &Self::ItemId) -> Option<T::AccountId>;
+}
+
+/// Default implementation for the Proof NFT minter, which does not support NFTs
+#[derive(Clone)]
+pub struct NaiveProofNftMinter<T: frame_system::Config> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: frame_system::Config> ProofNftMinter<T> for NaiveProofNftMinter<T> {
+  type CollectionId = u32;
+  type ItemId = u32;
+
+  fn create_collection(_collection: &Self::CollectionId, _owner: &T::AccountId) -> DispatchResult {
+    Err(DispatchError::Other("Proof NFTs are not supported"))
+  }
+
+  fn mint(
+    _collection: &Self::CollectionId,
+    _item: &Self::ItemId,
+    _owner: &T::AccountId,
+    _metadata: Vec<u8>,
+  ) -> DispatchResult {
+    Err(DispatchError::Other("Proof NFTs are not supported"))
+  }
+
+  fn owner(_collection: &Self::CollectionId, _item: &Self::ItemId) -> Option<T::AccountId> {
+    None
+  }
 }
@@ -47,49 +47,158 @@ use frame_support::{
 pub trait WeightInfo {
   fn create_proof() -> Weight;
   fn save_phash() -> Weight;
+  fn revoke_proof(p: u32) -> Weight;
+  fn create_proof_batch() -> Weight;
+  fn mint_proof_nft() -> Weight;
 }
 
 /// Weights for poe using the Substrate node and recommended hardware.
 pub struct AnagolayWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for AnagolayWeight<T> {
   // Storage: Workflows WorkflowByWorkflowIdAndAccountId (r:2 w:0)
-  // Storage: Poe ProofByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe ProofByProofIdAndAccountId (r:2 w:1)
+  // Storage: Poe DerivedProofIdsByProofId (r:1 w:1)
   // Storage: Poe ProofTotal (r:1 w:1)
+  // Storage: Poe ProofIdsByAccountId (r:0 w:1)
   fn create_proof() -> Weight {
-    // Minimum execution time: 62_580 nanoseconds.
-    Weight::from_ref_time(66_220_000)
-      .saturating_add(T::DbWeight::get().reads(4))
-      .saturating_add(T::DbWeight::get().writes(2))
+    // Minimum execution time: 70_940 nanoseconds.
+    Weight::from_ref_time(74_510_000)
+      .saturating_add(T::DbWeight::get().reads(6))
+      .saturating_add(T::DbWeight::get().writes(4))
   }
   // Storage: Poe ProofByProofIdAndAccountId (r:1 w:0)
   // Storage: Poe PhashByHashAndAccountId (r:1 w:1)
+  // Storage: Poe PhashDigestsByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe PhashesByIndexBand (r:8 w:8)
   // Storage: Poe PhashTotal (r:1 w:1)
+  // Storage: Poe PhashDigestsByAccountId (r:0 w:1)
   fn save_phash() -> Weight {
-    // Minimum execution time: 47_800 nanoseconds.
-    Weight::from_ref_time(49_330_000)
+    // Minimum execution time: 75_310 nanoseconds.
+    Weight::from_ref_time(78_040_000)
+      .saturating_add(T::DbWeight::get().reads(12))
+      .saturating_add(T::DbWeight::get().writes(12))
+  }
+  // Storage: Poe ProofByProofIdAndAccountId (r:3 w:1)
+  // Storage: Poe ProofNftByProofIdAndAccountId (r:1 w:0)
+  // Storage: Poe DerivedProofIdsByProofId (r:2 w:1)
+  // Storage: Poe PhashDigestsByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe PhashByHashAndAccountId (r:1 w:1)
+  // Storage: Poe PhashesByIndexBand (r:8 w:8)
+  // Storage: Poe PhashTotal (r:1 w:1)
+  // Storage: Poe PendingProofIdByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Poe ProofIdsByVerificationContext (r:1 w:1)
+  // Storage: Poe ProofTotal (r:1 w:1)
+  // Storage: Poe ProofIdsByAccountId (r:0 w:1)
+  // Storage: Poe PhashDigestsByAccountId (r:0 w:1)
+  /// The range of component `p` is `[0, 16]`.
+  fn revoke_proof(p: u32) -> Weight {
+    // Minimum execution time: 55_630 nanoseconds.
+    Weight::from_ref_time(58_210_000)
+      // Standard Error: 21_640
+      .saturating_add(Weight::from_ref_time(34_180_000).saturating_mul(p as u64))
+      .saturating_add(T::DbWeight::get().reads(11))
+      .saturating_add(T::DbWeight::get().reads((9 as u64).saturating_mul(p as u64)))
+      .saturating_add(T::DbWeight::get().writes(8))
+      .saturating_add(T::DbWeight::get().writes((10 as u64).saturating_mul(p as u64)))
+  }
+  // Storage: Workflows WorkflowByWorkflowIdAndAccountId (r:2 w:0)
+  // Storage: Poe ProofBatchByMerkleRootAndAccountId (r:1 w:1)
+  fn create_proof_batch() -> Weight {
+    // Minimum execution time: 41_270 nanoseconds.
+    Weight::from_ref_time(43_860_000)
       .saturating_add(T::DbWeight::get().reads(3))
-      .saturating_add(T::DbWeight::get().writes(2))
+      .saturating_add(T::DbWeight::get().writes(1))
+  }
+  // Storage: Poe ProofByProofIdAndAccountId (r:1 w:0)
+  // Storage: Poe ProofNftByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe ProofNftCollectionId (r:1 w:1)
+  // Storage: Poe NextProofNftItemId (r:1 w:1)
+  // Storage: Uniques Class (r:1 w:1)
+  // Storage: Uniques Asset (r:1 w:1)
+  // Storage: Uniques CollectionMaxSupply (r:1 w:0)
+  // Storage: Uniques InstanceMetadataOf (r:1 w:1)
+  // Storage: Uniques Account (r:0 w:1)
+  // Storage: Uniques ClassAccount (r:0 w:1)
+  // Storage: Poe ProofClaimByProofNftItemId (r:0 w:1)
+  fn mint_proof_nft() -> Weight {
+    // Minimum execution time: 98_720 nanoseconds.
+    Weight::from_ref_time(101_350_000)
+      .saturating_add(T::DbWeight::get().reads(8))
+      .saturating_add(T::DbWeight::get().writes(9))
   }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
   // Storage: Workflows WorkflowByWorkflowIdAndAccountId (r:2 w:0)
-  // Storage: Poe ProofByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe ProofByProofIdAndAccountId (r:2 w:1)
+  // Storage: Poe DerivedProofIdsByProofId (r:1 w:1)
   // Storage: Poe ProofTotal (r:1 w:1)
+  // Storage: Poe ProofIdsByAccountId (r:0 w:1)
   fn create_proof() -> Weight {
-    // Minimum execution time: 62_580 nanoseconds.
-    Weight::from_ref_time(66_220_000)
-      .saturating_add(RocksDbWeight::get().reads(4))
-      .saturating_add(RocksDbWeight::get().writes(2))
+    // Minimum execution time: 70_940 nanoseconds.
+    Weight::from_ref_time(74_510_000)
+      .saturating_add(RocksDbWeight::get().reads(6))
+      .saturating_add(RocksDbWeight::get().writes(4))
   }
   // Storage: Poe ProofByProofIdAndAccountId (r:1 w:0)
   // Storage: Poe PhashByHashAndAccountId (r:1 w:1)
+  // Storage: Poe PhashDigestsByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe PhashesByIndexBand (r:8 w:8)
   // Storage: Poe PhashTotal (r:1 w:1)
+  // Storage: Poe PhashDigestsByAccountId (r:0 w:1)
   fn save_phash() -> Weight {
-    // Minimum execution time: 47_800 nanoseconds.
-    Weight::from_ref_time(49_330_000)
+    // Minimum execution time: 75_310 nanoseconds.
+    Weight::from_ref_time(78_040_000)
+      .saturating_add(RocksDbWeight::get().reads(12))
+      .saturating_add(RocksDbWeight::get().writes(12))
+  }
+  // Storage: Poe ProofByProofIdAndAccountId (r:3 w:1)
+  // Storage: Poe ProofNftByProofIdAndAccountId (r:1 w:0)
+  // Storage: Poe DerivedProofIdsByProofId (r:2 w:1)
+  // Storage: Poe PhashDigestsByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe PhashByHashAndAccountId (r:1 w:1)
+  // Storage: Poe PhashesByIndexBand (r:8 w:8)
+  // Storage: Poe PhashTotal (r:1 w:1)
+  // Storage: Poe PendingProofIdByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Poe ProofIdsByVerificationContext (r:1 w:1)
+  // Storage: Poe ProofTotal (r:1 w:1)
+  // Storage: Poe ProofIdsByAccountId (r:0 w:1)
+  // Storage: Poe PhashDigestsByAccountId (r:0 w:1)
+  /// The range of component `p` is `[0, 16]`.
+  fn revoke_proof(p: u32) -> Weight {
+    // Minimum execution time: 55_630 nanoseconds.
+    Weight::from_ref_time(58_210_000)
+      // Standard Error: 21_640
+      .saturating_add(Weight::from_ref_time(34_180_000).saturating_mul(p as u64))
+      .saturating_add(RocksDbWeight::get().reads(11))
+      .saturating_add(RocksDbWeight::get().reads((9 as u64).saturating_mul(p as u64)))
+      .saturating_add(RocksDbWeight::get().writes(8))
+      .saturating_add(RocksDbWeight::get().writes((10 as u64).saturating_mul(p as u64)))
+  }
+  // Storage: Workflows WorkflowByWorkflowIdAndAccountId (r:2 w:0)
+  // Storage: Poe ProofBatchByMerkleRootAndAccountId (r:1 w:1)
+  fn create_proof_batch() -> Weight {
+    // Minimum execution time: 41_270 nanoseconds.
+    Weight::from_ref_time(43_860_000)
       .saturating_add(RocksDbWeight::get().reads(3))
-      .saturating_add(RocksDbWeight::get().writes(2))
+      .saturating_add(RocksDbWeight::get().writes(1))
+  }
+  // Storage: Poe ProofByProofIdAndAccountId (r:1 w:0)
+  // Storage: Poe ProofNftByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe ProofNftCollectionId (r:1 w:1)
+  // Storage: Poe NextProofNftItemId (r:1 w:1)
+  // Storage: Uniques Class (r:1 w:1)
+  // Storage: Uniques Asset (r:1 w:1)
+  // Storage: Uniques CollectionMaxSupply (r:1 w:0)
+  // Storage: Uniques InstanceMetadataOf (r:1 w:1)
+  // Storage: Uniques Account (r:0 w:1)
+  // Storage: Uniques ClassAccount (r:0 w:1)
+  // Storage: Poe ProofClaimByProofNftItemId (r:0 w:1)
+  fn mint_proof_nft() -> Weight {
+    // Minimum execution time: 98_720 nanoseconds.
+    Weight::from_ref_time(101_350_000)
+      .saturating_add(RocksDbWeight::get().reads(8))
+      .saturating_add(RocksDbWeight::get().writes(9))
   }
 }
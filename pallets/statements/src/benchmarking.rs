@@ -21,9 +21,14 @@
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 use anagolay_support::{AnagolayStructureData, Characters};
+use codec::Encode;
 use core::convert::TryInto;
 use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::{sp_std::vec, BoundedVec};
+use frame_support::{
+  sp_io::crypto::{sr25519_generate, sr25519_sign},
+  sp_std::{vec, vec::Vec},
+  BoundedVec,
+};
 use frame_system::RawOrigin;
 use poe::{
   constants::*,
@@ -62,7 +67,8 @@ where
     block_number: T::BlockNumber::default(),
   };
   poe::pallet::ProofByProofIdAndAccountId::insert(proof_id.clone(), account.clone(), proof_record);
-  let proof_ids: BoundedVec<ProofId, MaxProofsPerWorkflowGet<T>> = vec![proof_id.clone()].try_into().unwrap();
+  let proof_ids: BoundedVec<ProofId, MaxProofsPerVerificationContextGet<T>> =
+    vec![proof_id.clone()].try_into().unwrap();
   poe::pallet::ProofIdsByVerificationContext::insert(context.clone(), proof_ids);
   verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(
     account.clone(),
@@ -72,33 +78,90 @@ where
   context
 }
 
+/// Key type of the keys generated to sign the Statements
+const KEY_TYPE: sp_core::crypto::KeyTypeId = sp_core::crypto::KeyTypeId(*b"stmt");
+
+/// Sign a Statement as holder and issuer with a sr25519 key generated in the keystore, which becomes
+/// the issuer of the Claim
+fn sign_statement(statement_data: &mut StatementData) {
+  let public = sr25519_generate(KEY_TYPE, None);
+
+  // did:substrate:<ss58 address with the generic substrate prefix>
+  let body = [&[42u8][..], &public.0[..]].concat();
+  let checksum = sp_core::hashing::blake2_512(&["SS58PRE".as_bytes(), &body].concat());
+  let address = bs58::encode([&body[..], &checksum[..2]].concat()).into_string();
+  statement_data.claim.issuer = Characters::from("did:substrate:").concat(&address);
+
+  let message = [
+    "<Bytes>".as_bytes(),
+    &statement_data.claim.encode(),
+    "</Bytes>".as_bytes(),
+  ]
+  .concat();
+  let sig = sr25519_sign(KEY_TYPE, &public, &message).unwrap();
+  statement_data.signatures.holder.sig = sig.0.to_vec().try_into().unwrap();
+  statement_data.signatures.holder.sig_key = Characters::from("urn:substrate:0x").concat(&hex::encode(public.0));
+  statement_data.signatures.issuer = statement_data.signatures.holder.clone();
+}
+
+/// Create a Statement of the caller to be used as parent, already having
+/// `MAX_CHILD_STATEMENTS_PER_STATEMENT - 1` child Statements so that the benchmarked child Statement
+/// hits the worst case
+fn mock_parent_statement<T: Config>(caller: &T::AccountId, claim_type: ClaimType) -> StatementId {
+  let mut statement = StatementData::default();
+  statement.claim.claim_type = claim_type.clone();
+  sign_statement(&mut statement);
+  let statement_id = statement.to_cid();
+
+  let origin = RawOrigin::Signed(caller.clone()).into();
+  if claim_type == ClaimType::Ownership {
+    crate::Pallet::<T>::create_ownership(origin, statement).unwrap();
+  } else {
+    crate::Pallet::<T>::create_copyright(origin, statement).unwrap();
+  }
+
+  let child_statement_ids = (1..T::MAX_CHILD_STATEMENTS_PER_STATEMENT)
+    .map(|index| StatementId::from(&index.to_le_bytes()[..]))
+    .collect::<Vec<_>>();
+  ChildStatementIdsByStatementId::<T>::insert(&statement_id, BoundedVec::truncate_from(child_statement_ids));
+  statement_id
+}
+
 benchmarks! {
   create_copyright{
-    //Initializing benchmark for Copyright Extrinsic
+    //Initializing benchmark for Copyright Extrinsic, as a child Statement of a parent with the
+    //maximum number of children
     let caller: T::AccountId = whitelisted_caller();
+    let parent_id = mock_parent_statement::<T>(&caller, ClaimType::Copyright);
+
     let mut copyright_statement = StatementData::default();
-    copyright_statement.signatures.holder.sig = frame_support::sp_std::vec![188, 174, 18, 5, 230, 169, 26, 59, 5, 221, 70, 121, 33, 198, 98, 19, 241, 149, 42, 114, 34, 137, 156, 255, 69, 104, 157, 166, 232, 238, 129, 100, 163, 13, 118, 59, 5, 136, 88, 103, 153, 36, 161, 203, 48, 71, 17, 80, 179, 11, 107, 167, 225, 81, 210, 31, 15, 112, 203, 122, 60, 78, 153, 138].try_into().unwrap();
-    copyright_statement.signatures.holder.sig_key = Characters::from("urn:substrate:0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+    copyright_statement.claim.prev_id = Some(parent_id);
+    sign_statement(&mut copyright_statement);
   }: _(RawOrigin::Signed(caller), copyright_statement)
 
   create_ownership{
-    //Initializing benchmark for Ownership Extrinsic
+    //Initializing benchmark for Ownership Extrinsic, as a child Statement of a parent with the
+    //maximum number of children
     let caller: T::AccountId = whitelisted_caller();
     let _context = mock_verification_context::<T>(&caller, ProofId::default());
+    let parent_id = mock_parent_statement::<T>(&caller, ClaimType::Ownership);
 
     let mut ownership_statement = StatementData::default();
     ownership_statement.claim.claim_type = ClaimType::Ownership;
-    ownership_statement.signatures.holder.sig = frame_support::sp_std::vec![4, 162, 137, 242, 15, 129, 216, 106, 125, 59, 141, 17, 134, 176, 229, 224, 108, 11, 244, 151, 218, 201, 30, 104, 192, 84, 61, 109, 206, 151, 222, 63, 140, 244, 153, 184, 240, 163, 40, 0, 169, 52, 44, 42, 52, 254, 75, 210, 159, 237, 237, 98, 64, 129, 170, 176, 32, 36, 140, 231, 32, 128, 72, 140].try_into().unwrap();
-    ownership_statement.signatures.holder.sig_key = Characters::from("urn:substrate:0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+    ownership_statement.claim.prev_id = Some(parent_id);
+    sign_statement(&mut ownership_statement);
 
   }: _(RawOrigin::Signed(caller), ownership_statement)
 
   revoke{
-    //Initializing benchmark for Revoke Extrinsic
+    //Initializing benchmark for Revoke Extrinsic, as a child Statement of a parent with the
+    //maximum number of children
     let caller: T::AccountId = whitelisted_caller();
+    let parent_id = mock_parent_statement::<T>(&caller, ClaimType::Copyright);
+
     let mut statement = StatementData::default();
-    statement.signatures.holder.sig = frame_support::sp_std::vec![188, 174, 18, 5, 230, 169, 26, 59, 5, 221, 70, 121, 33, 198, 98, 19, 241, 149, 42, 114, 34, 137, 156, 255, 69, 104, 157, 166, 232, 238, 129, 100, 163, 13, 118, 59, 5, 136, 88, 103, 153, 36, 161, 203, 48, 71, 17, 80, 179, 11, 107, 167, 225, 81, 210, 31, 15, 112, 203, 122, 60, 78, 153, 138].try_into().unwrap();
-    statement.signatures.holder.sig_key = Characters::from("urn:substrate:0xd43593c715fdd31c61141abd04a99fd6822c8558854ccde39a5684e7a56da27d");
+    statement.claim.prev_id = Some(parent_id);
+    sign_statement(&mut statement);
     let statement_id = statement.to_cid();
     crate::Pallet::<T>::create_copyright(RawOrigin::Signed(caller.clone()).into(), statement)?;
   }: _(RawOrigin::Signed(caller), statement_id)
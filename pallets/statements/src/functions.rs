@@ -18,14 +18,19 @@
 
 use super::{constants::*, *};
 use crate::{
-  types::{Claim, Signature, Statement, StatementId, StatementRecord},
+  types::{Claim, ClaimType, Signature, Statement, StatementId, StatementRecord},
   Error::NoSuchStatement,
 };
 use anagolay_support::Characters;
 use codec::{Decode, Encode};
-use frame_support::{sp_std::vec::Vec, BoundedVec};
+use core::convert::TryInto;
+use frame_support::{
+  sp_io::{crypto::secp256k1_ecdsa_recover, hashing::keccak_256},
+  sp_std::{vec, vec::Vec},
+  BoundedVec,
+};
 use poe::types::ProofId;
-use sp_runtime::traits::Verify;
+use sp_runtime::traits::{IdentifyAccount, Verify};
 
 impl<T: Config> Pallet<T> {
   /// Decrease the statements count
@@ -38,26 +43,147 @@ impl<T: Config> Pallet<T> {
     Total::<T>::mutate(|v| *v += 1);
   }
 
-  /// Verify the Signature of a Claim
+  /// Verify the sr25519 Signature of a Claim
   pub fn verify_substrate_signature(claim: &Claim, signature: &Signature, public_key: &str) -> bool {
-    let public_key = hex::decode(public_key.strip_prefix("0x").unwrap_or(public_key)).unwrap_or_default();
-    let public_key = sp_core::sr25519::Public::decode(&mut public_key.as_slice());
-    let signature = sp_core::sr25519::Signature::decode(&mut signature.sig.as_slice());
+    Self::verify_wrapped_signature::<sp_core::sr25519::Signature>(claim, signature, public_key)
+  }
+
+  /// Verify the ed25519 Signature of a Claim
+  pub fn verify_ed25519_signature(claim: &Claim, signature: &Signature, public_key: &str) -> bool {
+    Self::verify_wrapped_signature::<sp_core::ed25519::Signature>(claim, signature, public_key)
+  }
+
+  /// Verify the Signature of a Claim produced by an Ethereum wallet through `personal_sign`, as
+  /// defined by EIP-191. The signer is recovered from the Signature and compared to the address
+  pub fn verify_ethereum_signature(claim: &Claim, signature: &Signature, address: &str) -> bool {
+    Self::verify_personal_sign(claim.encode().as_slice(), signature.sig.as_slice(), address)
+  }
+
+  /// Verify a message signed by an Ethereum wallet through `personal_sign`, as defined by EIP-191
+  ///
+  /// # Arguments
+  ///  * message - The bare message, before the wallet prefixes it
+  ///  * signature - The 65 bytes signature, with the recovery id as last byte
+  ///  * address - The hex encoded address of the signer
+  /// # Return
+  /// `true` if the signer recovered from the signature has the given address, `false` otherwise
+  pub fn verify_personal_sign(message: &[u8], signature: &[u8], address: &str) -> bool {
+    let address = hex::decode(address.strip_prefix("0x").unwrap_or(address)).unwrap_or_default();
+    let signature: Result<[u8; 65], _> = signature.try_into();
+
+    // Prefix the message with its length in decimal digits, as `personal_sign` does
+    let mut len = message.len();
+    let mut digits = Vec::new();
+    loop {
+      digits.push(b'0' + (len % 10) as u8);
+      len /= 10;
+      if len == 0 {
+        break;
+      }
+    }
+    digits.reverse();
+    let prefixed = ["\x19Ethereum Signed Message:\n".as_bytes(), digits.as_slice(), message].concat();
+    let message_hash = keccak_256(prefixed.as_slice());
+
+    match signature {
+      Ok(signature) => secp256k1_ecdsa_recover(&signature, &message_hash)
+        .map(|public_key| keccak_256(&public_key)[12..] == *address.as_slice())
+        .unwrap_or(false),
+      _ => false,
+    }
+  }
+
+  /// Verify a Signature of a Claim, produced either on the bare Claim or on the Claim wrapped as
+  /// polkadot-js extension does
+  fn verify_wrapped_signature<S>(claim: &Claim, signature: &Signature, public_key: &str) -> bool
+  where
+    S: Verify + Decode,
+    <S::Signer as IdentifyAccount>::AccountId: Decode,
+  {
     let message = claim.encode();
     // Wrap message as polkadot-js extension does https://substrate.stackexchange.com/questions/4209/verify-a-signature-in-pallet
     let wrapped = ["<Bytes>".as_bytes(), message.as_slice(), "</Bytes>".as_bytes()].concat();
 
-    let verified = match (public_key, signature) {
-      // Try with the wrapping or without
-      (Ok(public_key), Ok(signature)) => {
-        signature.verify(wrapped.as_slice(), &public_key) || signature.verify(message.as_slice(), &public_key)
-      }
+    // Try with the wrapping or without
+    Self::verify_message_signature::<S>(wrapped.as_slice(), signature.sig.as_slice(), public_key) ||
+      Self::verify_message_signature::<S>(message.as_slice(), signature.sig.as_slice(), public_key)
+  }
+
+  /// Verify a message signed with a substrate signature scheme, such as sr25519 or ed25519
+  ///
+  /// # Arguments
+  ///  * message - The signed message
+  ///  * signature - The encoded signature
+  ///  * public_key - The hex encoded public key of the signer
+  /// # Return
+  /// `true` if the signature signs the message with the given public key, `false` otherwise
+  pub fn verify_message_signature<S>(message: &[u8], signature: &[u8], public_key: &str) -> bool
+  where
+    S: Verify + Decode,
+    <S::Signer as IdentifyAccount>::AccountId: Decode,
+  {
+    let public_key = hex::decode(public_key.strip_prefix("0x").unwrap_or(public_key)).unwrap_or_default();
+    let public_key = <S::Signer as IdentifyAccount>::AccountId::decode(&mut public_key.as_slice());
+    let signature = S::decode(&mut &signature[..]);
+
+    match (public_key, signature) {
+      (Ok(public_key), Ok(signature)) => signature.verify(message, &public_key),
       _ => false,
+    }
+  }
+
+  /// Decode the public key of a `did:substrate` identifier, which is an ss58 address optionally
+  /// followed by the network name, as in `did:substrate:<ss58 address>/anagolay-network`
+  fn decode_substrate_did(address: &str) -> Option<Vec<u8>> {
+    let address = address.split('/').next()?;
+    let data = bs58::decode(address).into_vec().ok()?;
+    // Simple account formats use one byte prefix, full ones use two
+    let prefix_len = match data.first()? {
+      0..=63 => 1,
+      64..=127 => 2,
+      _ => return None,
+    };
+    if data.len() != prefix_len + 32 + 2 {
+      return None;
+    }
+    let (body, checksum) = data.split_at(prefix_len + 32);
+    let hash = sp_core::hashing::blake2_512(&["SS58PRE".as_bytes(), body].concat());
+    if hash[..2] != *checksum {
+      return None;
+    }
+    Some(body[prefix_len..].to_vec())
+  }
+
+  /// Check that a Signature is made with the key identified by a DID. `did:substrate` identifiers
+  /// match the `substrate` and `ed25519` schemes, `did:ethr` identifiers match the `ethereum`
+  /// scheme, with or without the network, e.g. `did:ethr:<address>` or
+  /// `did:ethr:<network>:<address>`
+  ///
+  /// # Arguments
+  ///  * did - The DID of the expected signer
+  ///  * signature - The Signature to check
+  /// # Return
+  /// `true` if the `sig_key` of the Signature is the key identified by the DID, `false` otherwise
+  pub fn is_signed_by_did(did: &Characters, signature: &Signature) -> bool {
+    let split: Vec<Characters> = signature.sig_key.split(":");
+    if split.len() != 3 {
+      return false;
+    }
+    let key = split.get(2).unwrap().as_str();
+    let key = hex::decode(key.strip_prefix("0x").unwrap_or(key)).unwrap_or_default();
+    let did = did.as_str();
+    let expected_key = match split.get(1).unwrap().as_str() {
+      "substrate" | "ed25519" => did.strip_prefix("did:substrate:").and_then(Self::decode_substrate_did),
+      "ethereum" => did
+        .strip_prefix("did:ethr:")
+        .and_then(|identifier| identifier.rsplit(':').next())
+        .and_then(|address| hex::decode(address.strip_prefix("0x").unwrap_or(address)).ok()),
+      _ => None,
     };
-    verified
+    expected_key.map(|expected_key| expected_key == key).unwrap_or(false)
   }
 
-  /// Remove the Statement from the storage
+  /// Remove the Statement from the storage, along with its association to the parent Statement
   ///
   /// Does no checks.
   ///
@@ -75,12 +201,55 @@ impl<T: Config> Pallet<T> {
         )?;
         StatementByStatementIdAndAccountId::<T>::remove(&statement_id, account_id);
         Self::decrease_statements_count();
+
+        // The indexes are shared by all the accounts holding the same Statement
+        if StatementByStatementIdAndAccountId::<T>::iter_key_prefix(&statement_id)
+          .next()
+          .is_none()
+        {
+          Self::remove_child_statement(&statement_id);
+        }
         Ok(())
       }
       _ => Err(NoSuchStatement),
     }
   }
 
+  /// Remove a Statement along with its descendants, every child before its parent, so that no
+  /// Statement is removed while it has child Statements. A Statement already removed is skipped
+  ///
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * statement_id - The id of the Statement to remove
+  ///  * account_id - The issuer of the Statement, who is the issuer of its descendants as well
+  /// # Return
+  /// The ids of the removed Statements, in order of removal, `Error` if one of them cannot be
+  /// removed
+  pub fn remove_statement_tree(
+    statement_id: &StatementId,
+    account_id: &T::AccountId,
+  ) -> Result<Vec<StatementId>, Error<T>> {
+    let mut removed = Vec::new();
+    if !StatementByStatementIdAndAccountId::<T>::contains_key(statement_id, account_id) {
+      return Ok(removed);
+    }
+
+    // The removal of a child Statement detaches it from its parent
+    let mut pending = vec![statement_id.clone()];
+    while let Some(current_id) = pending.last().cloned() {
+      match ChildStatementIdsByStatementId::<T>::get(&current_id).first() {
+        Some(child_id) => pending.push(child_id.clone()),
+        None => {
+          Self::remove_statement(current_id.clone(), account_id)?;
+          pending.pop();
+          removed.push(current_id);
+        }
+      }
+    }
+    Ok(removed)
+  }
+
   /// Insert the statement to the storage
   ///
   /// Does no checks.
@@ -164,45 +333,159 @@ impl<T: Config> Pallet<T> {
     }
   }
 
-  /// Validate the Statement signature and save it
-  /// The signature must be in the form `urn:substrate:<hex encoded public key>` and must
-  /// sign the associated Claim, encoded by parity scale encoder.
+  /// Verify a Signature of a Claim according to the scheme of its `sig_key`, which must be in the
+  /// form `urn:<scheme>:<hex encoded public key>`. Supported schemes are `substrate` (sr25519),
+  /// `ed25519` and `ethereum`, whose public key is replaced by the hex encoded address
+  ///
+  /// # Arguments
+  ///  * claim - The signed Claim
+  ///  * signature - The Signature to verify
+  /// # Return
+  /// A unit-type `Result` if the Signature signs the Claim, `InvalidSignature` if it does not,
+  /// `UnrecognizedSignature` if the `sig_key` cannot be parsed or its scheme is not supported
+  pub fn verify_signature(claim: &Claim, signature: &Signature) -> Result<(), Error<T>> {
+    let split: Vec<Characters> = signature.sig_key.split(":");
+    if split.len() != 3 {
+      return Err(Error::<T>::UnrecognizedSignature);
+    }
+    let algorithm = split.get(1).unwrap().clone();
+    let public_key = split.get(2).unwrap().clone();
+    let verified = match algorithm.as_str() {
+      "substrate" => Self::verify_substrate_signature(claim, signature, public_key.as_str()),
+      "ed25519" => Self::verify_ed25519_signature(claim, signature, public_key.as_str()),
+      "ethereum" => Self::verify_ethereum_signature(claim, signature, public_key.as_str()),
+      _ => return Err(Error::<T>::UnrecognizedSignature),
+    };
+    if verified {
+      Ok(())
+    } else {
+      Err(Error::<T>::InvalidSignature)
+    }
+  }
+
+  /// Check that a Statement can be created as child of the given parent Statement
+  ///
+  /// # Arguments
+  ///  * prev_id - The id of the parent Statement
+  ///  * poe_id - The id of the Proof referenced by the child Statement
+  ///  * account_id - The issuer of the child Statement
+  /// # Return
+  /// A unit-type `Result` if the parent Statement is owned by the issuer, references the same
+  /// Proof and is not superseded, `Error` otherwise
+  pub fn ensure_valid_parent_statement(
+    prev_id: &StatementId,
+    poe_id: &ProofId,
+    account_id: &T::AccountId,
+  ) -> Result<(), Error<T>> {
+    let parent =
+      StatementByStatementIdAndAccountId::<T>::get(prev_id, account_id).ok_or(Error::<T>::NoSuchParentStatement)?;
+    if parent.record.data.claim.poe_id != *poe_id {
+      return Err(Error::<T>::ChildStatementProofMismatch);
+    }
+    if Self::is_statement_superseded(&parent.record) {
+      return Err(Error::<T>::StatementAlreadySuperseded);
+    }
+    Ok(())
+  }
+
+  /// Tell whether a Statement was superseded. An Ownership Statement is transferred to a new
+  /// holder through a child Statement, which supersedes it: hence an Ownership Statement has at
+  /// most one child and, along the chain of transfers, only the last Statement is in force. A
+  /// Copyright Statement is never superseded, its children license it
+  ///
+  /// # Arguments
+  ///  * statement - The Statement
+  /// # Return
+  /// `true` if the Statement is an Ownership with a child Statement, `false` otherwise
+  pub fn is_statement_superseded(statement: &Statement) -> bool {
+    statement.data.claim.claim_type == ClaimType::Ownership &&
+      !ChildStatementIdsByStatementId::<T>::get(&statement.id).is_empty()
+  }
+
+  /// Associate a child Statement to its parent Statement in the `ParentStatementIdByStatementId`
+  /// and `ChildStatementIdsByStatementId` storages
+  ///
+  /// # Arguments
+  ///  * prev_id - The id of the parent Statement
+  ///  * statement_id - The id of the child Statement
+  /// # Return
+  /// A unit-type `Result` if the association was created, `Error` otherwise
+  pub fn add_child_statement(prev_id: &StatementId, statement_id: &StatementId) -> Result<(), Error<T>> {
+    ChildStatementIdsByStatementId::<T>::try_mutate(prev_id, |child_statement_ids| {
+      if !child_statement_ids.contains(statement_id) {
+        child_statement_ids
+          .try_push(statement_id.clone())
+          .map_err(|_| Error::<T>::MaxChildStatementsPerStatementLimitReached)?;
+      }
+      Ok::<(), Error<T>>(())
+    })?;
+    ParentStatementIdByStatementId::<T>::insert(statement_id, prev_id);
+    Ok(())
+  }
+
+  /// Remove the association between a child Statement and its parent Statement, if any
+  ///
+  /// Does no checks.
+  ///
+  /// # Arguments
+  ///  * statement_id - The id of the child Statement
+  pub fn remove_child_statement(statement_id: &StatementId) {
+    if let Some(prev_id) = ParentStatementIdByStatementId::<T>::take(statement_id) {
+      ChildStatementIdsByStatementId::<T>::mutate_exists(&prev_id, |child_statement_ids| {
+        if let Some(ids) = child_statement_ids {
+          ids.retain(|child_statement_id| child_statement_id != statement_id);
+          if ids.is_empty() {
+            *child_statement_ids = None;
+          }
+        }
+      });
+    }
+  }
+
+  /// Validate the Statement signatures and save it
+  /// Both the holder and the issuer signatures must be in the form
+  /// `urn:<scheme>:<hex encoded public key>` and must sign the associated Claim, encoded by
+  /// parity scale encoder. The issuer signature must be made with the key identified by the
+  /// `issuer` DID of the Claim.
   ///
   /// # Arguments
   ///  * statement - The Statement to validate and save
   ///  * account_id - The issuer of the Statement
   ///  * block_number - Current block
   /// # Return
-  /// A boolean determining whether the Statement validation passed or not. If not, the statement
-  /// is not saved.
+  /// A unit-type `Result` if the Statement validation passed, `Error` otherwise. If not, the
+  /// statement is not saved.
   pub fn validate_and_save_statement(
     statement: Statement,
     account_id: &T::AccountId,
     block_number: &T::BlockNumber,
   ) -> Result<(), Error<T>> {
-    let holder_signature = &statement.data.signatures.holder;
-    let split: Vec<Characters> = holder_signature.sig_key.split(":");
-    if split.len() == 3 {
-      let algorithm = split.get(1).unwrap().clone();
-      let public_key = split.get(2).unwrap().clone();
-      return match algorithm.as_str() {
-        "substrate" => {
-          if Self::verify_substrate_signature(&statement.data.claim, holder_signature, public_key.as_str()) {
-            let statement_info = StatementRecord::<T> {
-              record: statement.clone(),
-              account_id: account_id.clone(),
-              block_number: *block_number,
-            };
-            Self::add_statement_to_proof(statement.data.claim.poe_id.clone(), statement.id.clone())?;
-            Self::insert_statement(&statement_info, account_id);
-            Ok(())
-          } else {
-            Err(Error::<T>::InvalidSignature)
-          }
-        }
-        _ => Err(Error::<T>::UnrecognizedSignature),
-      };
+    let signatures = &statement.data.signatures;
+    Self::verify_signature(&statement.data.claim, &signatures.holder)?;
+
+    // The issuer must consent to the Claim as well
+    if signatures.issuer.sig_key.is_empty() || signatures.issuer.sig.is_empty() {
+      return Err(Error::<T>::MissingIssuerSignature);
+    }
+    Self::verify_signature(&statement.data.claim, &signatures.issuer).map_err(|err| match err {
+      Error::<T>::InvalidSignature => Error::<T>::InvalidIssuerSignature,
+      err => err,
+    })?;
+    // And the issuer signature must be made with the key of the issuer of the Claim
+    if !Self::is_signed_by_did(&statement.data.claim.issuer, &signatures.issuer) {
+      return Err(Error::<T>::IssuerSignatureKeyMismatch);
+    }
+
+    let statement_info = StatementRecord::<T> {
+      record: statement.clone(),
+      account_id: account_id.clone(),
+      block_number: *block_number,
+    };
+    Self::add_statement_to_proof(statement.data.claim.poe_id.clone(), statement.id.clone())?;
+    if let Some(prev_id) = &statement.data.claim.prev_id {
+      Self::add_child_statement(prev_id, &statement.id)?;
     }
-    Err(Error::<T>::UnrecognizedSignature)
+    Self::insert_statement(&statement_info, account_id);
+    Ok(())
   }
 }
@@ -39,6 +39,7 @@ pub use weights::WeightInfo;
 mod constants {
   use anagolay_support::getter_for_constant;
   getter_for_constant!(MaxStatementsPerProof, u32);
+  getter_for_constant!(MaxChildStatementsPerStatement, u32);
 }
 
 #[frame_support::pallet]
@@ -67,6 +68,9 @@ pub mod pallet {
     /// Maximum number of Statements registered for a single Proof on Anagolay network at a given
     /// time.
     const MAX_STATEMENTS_PER_PROOF: u32;
+
+    /// Maximum number of child Statements referencing the same parent Statement in `prev_id`
+    const MAX_CHILD_STATEMENTS_PER_STATEMENT: u32;
   }
 
   #[pallet::extra_constants]
@@ -75,6 +79,11 @@ pub mod pallet {
     fn max_statements_per_proof() -> u32 {
       T::MAX_STATEMENTS_PER_PROOF
     }
+
+    #[pallet::constant_name(MaxChildStatementsPerStatement)]
+    fn max_child_statements_per_statement() -> u32 {
+      T::MAX_CHILD_STATEMENTS_PER_STATEMENT
+    }
   }
 
   /// Retrieve a Statement with the Statement Id and the Account Id
@@ -100,12 +109,24 @@ pub mod pallet {
   /// }```
   ///
   /// So this will be a map of StatementId to StatementId (parent)
-  /// It's used to quickly check upon revoke: the revoke of `aStatement` it will fail,
-  /// because it is the parent of the `bStatement`
   #[pallet::storage]
   #[pallet::getter(fn parent_statement_id_by_statement_id)]
   pub type ParentStatementIdByStatementId<T: Config> =
-    StorageMap<_, Blake2_128Concat, StatementId, StatementId, ValueQuery>;
+    StorageMap<_, Blake2_128Concat, StatementId, StatementId, OptionQuery>;
+
+  /// Retrieve the child Statement Ids given a Statement Id, the inverse of
+  /// `ParentStatementIdByStatementId`
+  /// It's used to quickly check upon revoke: the revoke of `aStatement` it will fail,
+  /// because it is the parent of the `bStatement`
+  #[pallet::storage]
+  #[pallet::getter(fn child_statement_ids_by_statement_id)]
+  pub type ChildStatementIdsByStatementId<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    StatementId,
+    BoundedVec<StatementId, MaxChildStatementsPerStatementGet<T>>,
+    ValueQuery,
+  >;
 
   /// Amount of saved Statements
   #[pallet::storage]
@@ -148,8 +169,12 @@ pub mod pallet {
     InvalidVerificationContext,
     /// Statement has child statement and it cannot be revoked
     StatementHasChildStatement,
-    /// Create child statement is not yet supported
-    CreatingChildStatementNotSupported,
+    /// The parent Statement referenced in `prev_id` does not exist or is not owned by the caller
+    NoSuchParentStatement,
+    /// The child Statement does not reference the same Proof as its parent Statement
+    ChildStatementProofMismatch,
+    /// Insertion of Statement failed since MaxChildStatementsPerStatement limit is reached
+    MaxChildStatementsPerStatementLimitReached,
     /// A parameter of the request is invalid or does not respect a given constraint
     BadRequest,
     /// Insertion of Statement failed since MaxStatementsPerProof limit is reached
@@ -158,10 +183,29 @@ pub mod pallet {
     InvalidSignature,
     /// Statement signature could not be parsed correctly
     UnrecognizedSignature,
+    /// Statement lacks the signature of the issuer
+    MissingIssuerSignature,
+    /// Statement issuer signature did not validate
+    InvalidIssuerSignature,
+    /// Statement issuer signature is not made with the key of the Claim issuer
+    IssuerSignatureKeyMismatch,
+    /// The parent Ownership Statement was already transferred, so its child supersedes it
+    StatementAlreadySuperseded,
   }
 
   #[pallet::hooks]
-  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+  impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn integrity_test() {
+      assert!(
+        T::MAX_STATEMENTS_PER_PROOF > 0u32,
+        "`MaxStatementsPerProof` must be greater than 0"
+      );
+      assert!(
+        T::MAX_CHILD_STATEMENTS_PER_STATEMENT > 0u32,
+        "`MaxChildStatementsPerStatement` must be greater than 0"
+      );
+    }
+  }
 
   #[pallet::call]
   impl<T: Config> Pallet<T> {
@@ -176,11 +220,23 @@ pub mod pallet {
     ///
     /// # Errors
     /// * `WrongClaimType` - if the Statement type is not[`ClaimType::Copyright`]
-    /// * `CreatingChildStatementNotSupported` - creating child Statements is not supported at the
-    ///   moment
+    /// * `NoSuchParentStatement` - if the parent Statement does not exist or is not owned by the
+    ///   caller
+    /// * `ChildStatementProofMismatch` - if the parent Statement references a different Proof
+    /// * `MaxChildStatementsPerStatementLimitReached` - if the parent Statement already has
+    ///   `MAX_CHILD_STATEMENTS_PER_STATEMENT` child Statements
+    /// * `StatementAlreadySuperseded` - if the parent Statement is an Ownership already transferred
     /// * `StatementAlreadyExists` - the Statement already exists
-    /// * `ProofHasStatements` - the Proof is already associated to existing Statements
+    /// * `ProofHasStatements` - the Proof is already associated to existing Statements, unless the
+    ///   Statement is a child Statement
     /// * `BadRequest` - if the request is invalid or does not respect a given constraint
+    /// * `UnrecognizedSignature` - if a signature key cannot be parsed or its scheme is not
+    ///   supported
+    /// * `InvalidSignature` - if the holder signature does not sign the Claim
+    /// * `MissingIssuerSignature` - if the Statement lacks the issuer signature
+    /// * `InvalidIssuerSignature` - if the issuer signature does not sign the Claim
+    /// * `IssuerSignatureKeyMismatch` - if the issuer signature is not made with the key identified
+    ///   by the issuer DID of the Claim
     ///
     /// # Return
     /// `DispatchResultWithPostInfo` containing Unit type
@@ -202,11 +258,10 @@ pub mod pallet {
         Error::<T>::WrongClaimType
       );
 
-      // Ensure that previous statement is empty. we do not allow updating the statements at this point
-      ensure!(
-        statement_data.claim.prev_id.is_none(),
-        Error::<T>::CreatingChildStatementNotSupported
-      );
+      // A child Statement derives from a parent Statement of the caller on the same Proof
+      if let Some(prev_id) = &statement_data.claim.prev_id {
+        Self::ensure_valid_parent_statement(prev_id, &statement_data.claim.poe_id, &sender)?;
+      }
 
       let statement = Statement::new(statement_data);
 
@@ -216,8 +271,11 @@ pub mod pallet {
         Error::<T>::StatementAlreadyExists
       );
 
-      // Ensure that Proof has or no associated statements
-      Self::is_proof_statement_list_empty(statement.clone())?;
+      // Ensure that Proof has or no associated statements, child statements join the ones of their
+      // parent
+      if statement.data.claim.prev_id.is_none() {
+        Self::is_proof_statement_list_empty(statement.clone())?;
+      }
 
       Self::validate_and_save_statement(statement.clone(), &sender, &current_block)?;
 
@@ -238,13 +296,25 @@ pub mod pallet {
     ///
     /// # Errors
     /// * `WrongClaimType` - if the Statement type is not [`ClaimType::Ownership`]
-    /// * `CreatingChildStatementNotSupported` - creating child Statements is not supported at the
-    ///   moment
+    /// * `NoSuchParentStatement` - if the parent Statement does not exist or is not owned by the
+    ///   caller
+    /// * `ChildStatementProofMismatch` - if the parent Statement references a different Proof
+    /// * `MaxChildStatementsPerStatementLimitReached` - if the parent Statement already has
+    ///   `MAX_CHILD_STATEMENTS_PER_STATEMENT` child Statements
+    /// * `StatementAlreadySuperseded` - if the parent Statement is an Ownership already transferred
     /// * `StatementAlreadyExists` - the Statement already exists
     /// * `InvalidVerificationContext` - if the proof does not exist, the verification is not
     ///   associated to the caller or the verification is not successful
-    /// * `ProofHasStatements` - the Proof is already associated to existing Statements
+    /// * `ProofHasStatements` - the Proof is already associated to existing Statements, unless the
+    ///   Statement is a child Statement
     /// * `BadRequest` - if the request is invalid or does not respect a given constraint
+    /// * `UnrecognizedSignature` - if a signature key cannot be parsed or its scheme is not
+    ///   supported
+    /// * `InvalidSignature` - if the holder signature does not sign the Claim
+    /// * `MissingIssuerSignature` - if the Statement lacks the issuer signature
+    /// * `InvalidIssuerSignature` - if the issuer signature does not sign the Claim
+    /// * `IssuerSignatureKeyMismatch` - if the issuer signature is not made with the key identified
+    ///   by the issuer DID of the Claim
     ///
     /// # Return
     /// `DispatchResultWithPostInfo` containing Unit type
@@ -271,11 +341,11 @@ pub mod pallet {
         Error::<T>::WrongClaimType
       );
 
-      // Ensure that previous statement is empty. we do not allow updating the statements at this point
-      ensure!(
-        statement_data.claim.prev_id.is_none(),
-        Error::<T>::CreatingChildStatementNotSupported
-      );
+      // A child Statement transfers a parent Statement of the caller on the same Proof, which it
+      // supersedes
+      if let Some(prev_id) = &statement_data.claim.prev_id {
+        Self::ensure_valid_parent_statement(prev_id, &statement_data.claim.poe_id, &sender)?;
+      }
 
       let statement = Statement::new(statement_data);
 
@@ -285,8 +355,11 @@ pub mod pallet {
         Error::<T>::StatementAlreadyExists
       );
 
-      // Ensure that Proof has or no associated statements
-      Self::is_proof_statement_list_empty(statement.clone())?;
+      // Ensure that Proof has or no associated statements, child statements join the ones of their
+      // parent
+      if statement.data.claim.prev_id.is_none() {
+        Self::is_proof_statement_list_empty(statement.clone())?;
+      }
 
       Self::validate_and_save_statement(statement.clone(), &sender, &current_block)?;
 
@@ -320,9 +393,9 @@ pub mod pallet {
         Error::<T>::NoSuchStatement
       );
 
-      // Ensure Statement to previous statement index is not present | no child statements support atm
+      // Ensure no child Statement references this one as parent
       ensure!(
-        !ParentStatementIdByStatementId::<T>::contains_key(&statement_id),
+        ChildStatementIdsByStatementId::<T>::get(&statement_id).is_empty(),
         Error::<T>::StatementHasChildStatement
       );
 
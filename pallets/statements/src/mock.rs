@@ -21,14 +21,21 @@
 #![cfg(test)]
 
 use crate as statements;
-use crate::{types::StatementsVerificationInvalidator, Config};
-use frame_support::parameter_types;
+use crate::{
+  types::{StatementsProofRevocationHandler, StatementsVerificationInvalidator},
+  Config,
+};
+use frame_support::{parameter_types, traits::ConstU32, PalletId};
 use sp_core::{sr25519, sr25519::Signature, H256};
+use sp_keystore::{testing::KeyStore, KeystoreExt};
 use sp_runtime::{
   testing::{Header, TestXt, UintAuthorityId},
   traits::{BlakeTwo256, IdentityLookup},
 };
-use std::convert::{TryFrom, TryInto};
+use std::{
+  convert::{TryFrom, TryInto},
+  sync::Arc,
+};
 use verification::types::NaiveVerificationKeyGenerator;
 
 type Extrinsic = TestXt<RuntimeCall, ()>;
@@ -106,7 +113,10 @@ impl verification::Config for Test {
   type AuthorityId = verification::crypto::VerificationAuthId;
   type RuntimeEvent = RuntimeEvent;
   type VerificationKeyGenerator = NaiveVerificationKeyGenerator<Self>;
-  type VerificationInvalidator = StatementsVerificationInvalidator<Self>;
+  type VerificationInvalidator = (
+    StatementsVerificationInvalidator<Self>,
+    poe::types::PoeVerificationInvalidator<Self>,
+  );
   type WeightInfo = ();
   type Currency = ();
 
@@ -130,8 +140,15 @@ impl frame_system::offchain::SigningTypes for Test {
 impl poe::Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type WeightInfo = ();
-
-  const MAX_PROOFS_PER_WORKFLOW: u32 = 1;
+  type ProofRevocationHandler = StatementsProofRevocationHandler<Self>;
+  type ProofNftMinter = poe::types::NaiveProofNftMinter<Self>;
+  type ProofNftCollectionId = ConstU32<{ u32::MAX }>;
+
+  const PALLET_ID: PalletId = PalletId(*b"anag/poe");
+  const MAX_PROOFS_PER_VERIFICATION_CONTEXT: u32 = 2;
+  const MAX_PHASHES_PER_INDEX_BAND: u32 = 2;
+  const MAX_PHASHES_PER_PROOF: u32 = 4;
+  const MAX_DERIVED_PROOFS_PER_PROOF: u32 = 2;
 }
 
 pub struct MockTime {}
@@ -164,12 +181,16 @@ impl Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type WeightInfo = crate::weights::AnagolayWeight<Test>;
   const MAX_STATEMENTS_PER_PROOF: u32 = 16;
+  const MAX_CHILD_STATEMENTS_PER_STATEMENT: u32 = 2;
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-  frame_system::GenesisConfig::default()
+  let mut ext: sp_io::TestExternalities = frame_system::GenesisConfig::default()
     .build_storage::<Test>()
     .unwrap()
-    .into()
+    .into();
+  // Benchmarks sign the Statements with keys generated in the keystore
+  ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+  ext
 }
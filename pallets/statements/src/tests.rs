@@ -21,7 +21,7 @@
 #![cfg(test)]
 
 use super::{mock::*, *};
-use crate::types::{Claim, ClaimType, StatementData, StatementId, StatementsVerificationInvalidator};
+use crate::types::{Claim, ClaimType, Signature, StatementData, StatementId, StatementsVerificationInvalidator};
 use anagolay_support::{AnagolayStructureData, Characters};
 use codec::Encode;
 use core::convert::TryInto;
@@ -31,7 +31,7 @@ use poe::{
   types::{ProofId, *},
   ProofIdsByVerificationContext,
 };
-use sp_core::{sr25519, Pair};
+use sp_core::{crypto::Ss58Codec, ecdsa, ed25519, sr25519, Pair};
 use verification::{consts::MaxVerificationRequestsPerContextGet, types::*};
 
 fn mock_account(ss58: &str) -> sr25519::Public {
@@ -69,7 +69,8 @@ where
     block_number: 0u64,
   };
   poe::pallet::ProofByProofIdAndAccountId::insert(proof_id.clone(), account, proof_record);
-  let proof_ids: BoundedVec<ProofId, MaxProofsPerWorkflowGet<T>> = vec![proof_id.clone()].try_into().unwrap();
+  let proof_ids: BoundedVec<ProofId, MaxProofsPerVerificationContextGet<T>> =
+    vec![proof_id.clone()].try_into().unwrap();
   poe::pallet::ProofIdsByVerificationContext::insert(context.clone(), proof_ids);
   verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(
     account,
@@ -82,18 +83,59 @@ where
   request
 }
 
+/// Sign a Claim with the given key pair, producing a Signature in the `urn:<scheme>:<public key>`
+/// form, wrapped as polkadot-js extension does
+fn sign_claim<P: Pair>(claim: &Claim, pair: &P, scheme: &str) -> Signature {
+  let message = ["<Bytes>".as_bytes(), &claim.encode(), "</Bytes>".as_bytes()].concat();
+  Signature {
+    sig_key: Characters::from(format!("urn:{}:0x{}", scheme, hex::encode(pair.public())).as_str()),
+    sig: pair.sign(message.as_slice()).as_ref().to_vec().try_into().unwrap(),
+    ..Signature::default()
+  }
+}
+
+/// The `did:substrate` identifier of a public key
+fn substrate_did<P: Ss58Codec>(public: &P) -> Characters {
+  Characters::from(format!("did:substrate:{}/anagolay-network", public.to_ss58check()).as_str())
+}
+
+/// Sign a Statement as holder with the sr25519 key of //Alice and as issuer with the sr25519 key of
+/// //Bob, which becomes the issuer of the Claim
 fn sign_statement(statement_data: &mut StatementData) {
-  let (pair, _) = sr25519::Pair::from_string_with_seed("//Alice", None).unwrap();
-  let message = [
-    "<Bytes>".as_bytes(),
-    &statement_data.claim.encode(),
-    "</Bytes>".as_bytes(),
+  let (holder, _) = sr25519::Pair::from_string_with_seed("//Alice", None).unwrap();
+  let (issuer, _) = sr25519::Pair::from_string_with_seed("//Bob", None).unwrap();
+  statement_data.claim.issuer = substrate_did(&issuer.public());
+  statement_data.signatures.holder = sign_claim(&statement_data.claim, &holder, "substrate");
+  statement_data.signatures.issuer = sign_claim(&statement_data.claim, &issuer, "substrate");
+}
+
+/// Private key and address of a well-known Ethereum development account
+const ETHEREUM_PRIVATE_KEY: &str = "ac0974bec39a17e36ba4a6b4d238ff944bacb478cbed5efcae784d7bf4f2ff80";
+const ETHEREUM_ADDRESS: &str = "0xf39Fd6e51aad88F6F4ce6aB8827279cffFb92266";
+
+fn sign_statement_ed25519(statement_data: &mut StatementData) {
+  let (pair, _) = ed25519::Pair::from_string_with_seed("//Alice", None).unwrap();
+  statement_data.claim.issuer = substrate_did(&pair.public());
+  statement_data.signatures.holder = sign_claim(&statement_data.claim, &pair, "ed25519");
+  statement_data.signatures.issuer = statement_data.signatures.holder.clone();
+}
+
+fn sign_statement_ethereum(statement_data: &mut StatementData) {
+  let pair = ecdsa::Pair::from_seed_slice(&hex::decode(ETHEREUM_PRIVATE_KEY).unwrap()).unwrap();
+  statement_data.claim.issuer = Characters::from(format!("did:ethr:{}", ETHEREUM_ADDRESS).as_str());
+  let message = statement_data.claim.encode();
+  // EIP-191 personal_sign
+  let prefixed = [
+    format!("\x19Ethereum Signed Message:\n{}", message.len()).as_bytes(),
+    &message,
   ]
   .concat();
-  let signature = pair.sign(message.as_slice()).encode();
+  let mut signature = pair.sign_prehashed(&sp_io::hashing::keccak_256(&prefixed)).encode();
+  // Wallets produce recovery ids in the form 27 + v
+  signature[64] += 27;
   statement_data.signatures.holder.sig = signature.try_into().unwrap();
-  statement_data.signatures.holder.sig_key =
-    Characters::from(format!("urn:substrate:0x{}", hex::encode(pair.public())).as_str());
+  statement_data.signatures.holder.sig_key = Characters::from(format!("urn:ethereum:{}", ETHEREUM_ADDRESS).as_str());
+  statement_data.signatures.issuer = statement_data.signatures.holder.clone();
 }
 
 #[test]
@@ -159,7 +201,7 @@ fn copyright_create_child() {
     sign_statement(&mut r);
 
     let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
-    assert_noop!(res, Error::<Test>::CreatingChildStatementNotSupported);
+    assert_noop!(res, Error::<Test>::NoSuchParentStatement);
   });
 }
 #[test]
@@ -173,7 +215,7 @@ fn ownership_create_child() {
     sign_statement(&mut r);
 
     let res = TestStatements::create_ownership(mock::RuntimeOrigin::signed(account), r.clone());
-    assert_noop!(res, Error::<Test>::CreatingChildStatementNotSupported);
+    assert_noop!(res, Error::<Test>::NoSuchParentStatement);
   });
 }
 #[test]
@@ -248,6 +290,130 @@ fn statements_create_copyright_invalid_signature() {
   });
 }
 
+#[test]
+fn statements_create_copyright_ed25519_signature() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let mut r = StatementData::default();
+    sign_statement_ed25519(&mut r);
+
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_ok!(res);
+
+    // A sr25519 signature is not valid for an ed25519 key
+    let mut s = StatementData::default();
+    s.claim.poe_id = ProofId::from("my-fake-proof-id");
+    sign_statement(&mut s);
+    let (pair, _) = ed25519::Pair::from_string_with_seed("//Alice", None).unwrap();
+    s.signatures.holder.sig_key = Characters::from(format!("urn:ed25519:0x{}", hex::encode(pair.public())).as_str());
+
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), s.clone());
+    assert_noop!(res, Error::<Test>::InvalidSignature);
+  });
+}
+
+#[test]
+fn statements_create_copyright_ethereum_signature() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let mut r = StatementData::default();
+    sign_statement_ethereum(&mut r);
+
+    let mut wrong_address = r.clone();
+    wrong_address.signatures.holder.sig_key = "urn:ethereum:0x70997970C51812dc3A010C7d01b50e0d17dc79C8".into();
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), wrong_address);
+    assert_noop!(res, Error::<Test>::InvalidSignature);
+
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_ok!(res);
+  });
+}
+
+#[test]
+fn statements_issuer_did_ethr_with_network() {
+  new_test_ext().execute_with(|| {
+    let mut r = StatementData::default();
+    sign_statement_ethereum(&mut r);
+    let signature = r.signatures.issuer;
+
+    for did in [
+      format!("did:ethr:{}", ETHEREUM_ADDRESS),
+      format!("did:ethr:mainnet:{}", ETHEREUM_ADDRESS),
+      format!("did:ethr:0x5:{}", ETHEREUM_ADDRESS),
+    ] {
+      assert!(TestStatements::is_signed_by_did(&did.as_str().into(), &signature));
+    }
+    for did in [
+      "did:ethr:mainnet:0x70997970C51812dc3A010C7d01b50e0d17dc79C8".to_string(),
+      format!("did:ethr:{}:mainnet", ETHEREUM_ADDRESS),
+      format!("did:substrate:{}", ETHEREUM_ADDRESS),
+    ] {
+      assert!(!TestStatements::is_signed_by_did(&did.as_str().into(), &signature));
+    }
+  });
+}
+
+#[test]
+fn statements_create_copyright_missing_issuer_signature() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let mut r = StatementData::default();
+    sign_statement(&mut r);
+    r.signatures.issuer = Signature::default();
+
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_noop!(res, Error::<Test>::MissingIssuerSignature);
+  });
+}
+
+#[test]
+fn statements_create_copyright_invalid_issuer_signature() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let mut r = StatementData::default();
+    sign_statement(&mut r);
+    let (pair, _) = sr25519::Pair::from_string_with_seed("//Charlie", None).unwrap();
+    r.signatures.issuer.sig_key = Characters::from(format!("urn:substrate:0x{}", hex::encode(pair.public())).as_str());
+
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_noop!(res, Error::<Test>::InvalidIssuerSignature);
+
+    r.signatures.issuer.sig_key = "urn:unknown:0xCAFEBABE".into();
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_noop!(res, Error::<Test>::UnrecognizedSignature);
+  });
+}
+
+#[test]
+fn statements_create_copyright_issuer_signature_key_mismatch() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let mut r = StatementData::default();
+    sign_statement(&mut r);
+
+    // The issuer signature is valid, but made with a key unrelated to the Claim issuer
+    let (unrelated, _) = sr25519::Pair::from_string_with_seed("//Charlie", None).unwrap();
+    r.signatures.issuer = sign_claim(&r.claim, &unrelated, "substrate");
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_noop!(res, Error::<Test>::IssuerSignatureKeyMismatch);
+
+    // The same holds for a key of another scheme
+    let (unrelated, _) = ed25519::Pair::from_string_with_seed("//Bob", None).unwrap();
+    r.signatures.issuer = sign_claim(&r.claim, &unrelated, "ed25519");
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_noop!(res, Error::<Test>::IssuerSignatureKeyMismatch);
+
+    // And for a Claim issuer which is not a supported DID
+    let (holder, _) = sr25519::Pair::from_string_with_seed("//Alice", None).unwrap();
+    let (issuer, _) = sr25519::Pair::from_string_with_seed("//Bob", None).unwrap();
+    r.claim.issuer = Characters::from(format!("did:substrate:0x{}", hex::encode(issuer.public())).as_str());
+    r.signatures.holder = sign_claim(&r.claim, &holder, "substrate");
+    r.signatures.issuer = sign_claim(&r.claim, &issuer, "substrate");
+    let res = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_noop!(res, Error::<Test>::IssuerSignatureKeyMismatch);
+  });
+}
+
 #[test]
 fn statements_revoke() {
   new_test_ext().execute_with(|| {
@@ -282,17 +448,139 @@ fn statements_revoke_statement_has_child_statements() {
     sign_statement(&mut r);
     let s_id = r.to_cid();
 
-    let _res1 = TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), r.clone());
+    assert_ok!(TestStatements::create_copyright(
+      mock::RuntimeOrigin::signed(account),
+      r.clone()
+    ));
 
-    // do this after the create, since it will fail because we don't accept this ATM
-    r.claim.prev_id = Some(StatementId::from("child-statement-id"));
+    r.claim.prev_id = Some(s_id.clone());
     sign_statement(&mut r);
+    let child_id = r.to_cid();
+    assert_ok!(TestStatements::create_copyright(
+      mock::RuntimeOrigin::signed(account),
+      r.clone()
+    ));
 
-    ParentStatementIdByStatementId::<Test>::insert(&s_id, &r.claim.prev_id.unwrap());
+    let res = TestStatements::revoke(mock::RuntimeOrigin::signed(account), s_id.clone());
+    assert_noop!(res, Error::<Test>::StatementHasChildStatement);
 
-    let res = TestStatements::revoke(mock::RuntimeOrigin::signed(account), s_id);
+    assert_ok!(TestStatements::revoke(
+      mock::RuntimeOrigin::signed(account),
+      child_id.clone()
+    ));
+    assert!(ParentStatementIdByStatementId::<Test>::get(&child_id).is_none());
+    assert!(ChildStatementIdsByStatementId::<Test>::get(&s_id).is_empty());
+    assert_ok!(TestStatements::revoke(mock::RuntimeOrigin::signed(account), s_id));
+  });
+}
 
-    assert_noop!(res, Error::<Test>::StatementHasChildStatement);
+#[test]
+fn statements_create_child_statements() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let other = mock_account("//Bob");
+    let mut parent = StatementData::default();
+    sign_statement(&mut parent);
+    let parent_id = parent.to_cid();
+    assert_ok!(TestStatements::create_copyright(
+      mock::RuntimeOrigin::signed(account),
+      parent.clone()
+    ));
+
+    // Licensing the copyright to a new holder
+    let child = |holder: &str| {
+      let mut child = parent.clone();
+      child.claim.prev_id = Some(parent_id.clone());
+      child.claim.holder = holder.into();
+      sign_statement(&mut child);
+      child
+    };
+
+    assert_noop!(
+      TestStatements::create_copyright(mock::RuntimeOrigin::signed(other), child("bob")),
+      Error::<Test>::NoSuchParentStatement
+    );
+    let mut mismatch = child("bob");
+    mismatch.claim.poe_id = ProofId::from("my-fake-proof-id");
+    sign_statement(&mut mismatch);
+    assert_noop!(
+      TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), mismatch),
+      Error::<Test>::ChildStatementProofMismatch
+    );
+
+    for holder in ["bob", "charlie"] {
+      assert_ok!(TestStatements::create_copyright(
+        mock::RuntimeOrigin::signed(account),
+        child(holder)
+      ));
+      let child_id = child(holder).to_cid();
+      assert_eq!(
+        Some(parent_id.clone()),
+        ParentStatementIdByStatementId::<Test>::get(&child_id)
+      );
+      assert!(StatementIdsByProofId::<Test>::get(&parent.claim.poe_id).contains(&child_id));
+    }
+    assert_eq!(2, ChildStatementIdsByStatementId::<Test>::get(&parent_id).len());
+    assert_eq!(3, TestStatements::total());
+
+    assert_noop!(
+      TestStatements::create_copyright(mock::RuntimeOrigin::signed(account), child("dave")),
+      Error::<Test>::MaxChildStatementsPerStatementLimitReached
+    );
+  });
+}
+
+#[test]
+fn statements_transfer_ownership() {
+  new_test_ext().execute_with(|| {
+    let account = mock_account("//Alice");
+    let _request = mock_verification_request::<Test>(ProofId::default());
+    let mut parent = StatementData::default();
+    parent.claim.claim_type = ClaimType::Ownership;
+    sign_statement(&mut parent);
+    let parent_id = parent.to_cid();
+    assert_ok!(TestStatements::create_ownership(
+      mock::RuntimeOrigin::signed(account),
+      parent.clone()
+    ));
+
+    // Transferring the ownership to a new holder
+    let transfer = |prev: &StatementData, holder: &str| {
+      let mut child = prev.clone();
+      child.claim.prev_id = Some(prev.to_cid());
+      child.claim.holder = holder.into();
+      sign_statement(&mut child);
+      child
+    };
+
+    let to_bob = transfer(&parent, "bob");
+    assert_ok!(TestStatements::create_ownership(
+      mock::RuntimeOrigin::signed(account),
+      to_bob.clone()
+    ));
+    let parent_record = StatementByStatementIdAndAccountId::<Test>::get(&parent_id, account).unwrap();
+    let to_bob_record = StatementByStatementIdAndAccountId::<Test>::get(to_bob.to_cid(), account).unwrap();
+    assert!(TestStatements::is_statement_superseded(&parent_record.record));
+    assert!(!TestStatements::is_statement_superseded(&to_bob_record.record));
+
+    // The superseded Statement can not be transferred again
+    assert_noop!(
+      TestStatements::create_ownership(mock::RuntimeOrigin::signed(account), transfer(&parent, "charlie")),
+      Error::<Test>::StatementAlreadySuperseded
+    );
+    assert_noop!(
+      TestStatements::revoke(mock::RuntimeOrigin::signed(account), parent_id.clone()),
+      Error::<Test>::StatementHasChildStatement
+    );
+
+    // The Statement in force is transferred further
+    assert_ok!(TestStatements::create_ownership(
+      mock::RuntimeOrigin::signed(account),
+      transfer(&to_bob, "charlie")
+    ));
+    let to_bob_record = StatementByStatementIdAndAccountId::<Test>::get(to_bob.to_cid(), account).unwrap();
+    assert!(TestStatements::is_statement_superseded(&to_bob_record.record));
+    assert_eq!(3, StatementIdsByProofId::<Test>::get(&parent.claim.poe_id).len());
   });
 }
 
@@ -329,6 +617,83 @@ fn statements_revoke_for_verification_context_invalid() {
   });
 }
 
+#[test]
+fn statements_revoke_for_proof_revoked() {
+  new_test_ext().execute_with(|| {
+    let holder = mock_account("//Alice");
+    let proof_id = ProofId::default();
+    let request = mock_verification_request::<Test>(proof_id.clone());
+    let mut r = StatementData::default();
+    r.claim.claim_type = ClaimType::Ownership;
+    sign_statement(&mut r);
+
+    assert_ok!(TestStatements::create_ownership(
+      mock::RuntimeOrigin::signed(holder),
+      r.clone()
+    ));
+
+    assert_noop!(
+      Poe::revoke_proof(mock::RuntimeOrigin::signed(holder), proof_id.clone(), false),
+      poe::Error::<Test>::ProofIsReferenced
+    );
+
+    assert_ok!(Poe::revoke_proof(
+      mock::RuntimeOrigin::signed(holder),
+      proof_id.clone(),
+      true
+    ));
+
+    let statement_ids = StatementIdsByProofId::<Test>::get(&proof_id);
+    assert_eq!(statement_ids.len(), 0, "Statement must have been revoked");
+    assert!(StatementByStatementIdAndAccountId::<Test>::get(r.to_cid(), holder).is_none());
+    assert!(ProofIdsByVerificationContext::<Test>::get(request.context).is_none());
+  });
+}
+
+#[test]
+fn statements_revoke_child_statements_first() {
+  let create_chain = || {
+    let holder = mock_account("//Alice");
+    let request = mock_verification_request::<Test>(ProofId::default());
+    let mut parent = StatementData::default();
+    parent.claim.claim_type = ClaimType::Ownership;
+    sign_statement(&mut parent);
+    let mut child = parent.clone();
+    child.claim.prev_id = Some(parent.to_cid());
+    child.claim.holder = "bob".into();
+    sign_statement(&mut child);
+    for statement in [&parent, &child] {
+      assert_ok!(TestStatements::create_ownership(
+        mock::RuntimeOrigin::signed(holder),
+        statement.clone()
+      ));
+    }
+    (holder, request, parent.to_cid(), child.to_cid())
+  };
+  let assert_chain_removed = |parent_id: &StatementId, child_id: &StatementId| {
+    assert!(StatementIdsByProofId::<Test>::get(ProofId::default()).is_empty());
+    assert!(ChildStatementIdsByStatementId::<Test>::get(parent_id).is_empty());
+    assert!(ParentStatementIdByStatementId::<Test>::get(child_id).is_none());
+    assert_eq!(0, TestStatements::total());
+  };
+
+  new_test_ext().execute_with(|| {
+    let (holder, _, parent_id, child_id) = create_chain();
+    assert_ok!(Poe::revoke_proof(
+      mock::RuntimeOrigin::signed(holder),
+      ProofId::default(),
+      true
+    ));
+    assert_chain_removed(&parent_id, &child_id);
+  });
+
+  new_test_ext().execute_with(|| {
+    let (_, request, parent_id, child_id) = create_chain();
+    assert_ok!(StatementsVerificationInvalidator::<Test>::invalidate(&request));
+    assert_chain_removed(&parent_id, &child_id);
+  });
+}
+
 #[test]
 fn statements_signature_verification_substrate() {
   use crate::types::*;
@@ -406,6 +771,96 @@ fn statements_signature_verification_substrate() {
   });
 }
 
+#[test]
+fn statements_signature_verification_ethereum() {
+  new_test_ext().execute_with(|| {
+    let mut r = StatementData::default();
+    sign_statement_ethereum(&mut r);
+    let signature = r.signatures.holder.clone();
+
+    assert!(TestStatements::verify_ethereum_signature(
+      &r.claim,
+      &signature,
+      ETHEREUM_ADDRESS
+    ));
+    assert!(TestStatements::verify_ethereum_signature(
+      &r.claim,
+      &signature,
+      &ETHEREUM_ADDRESS.to_lowercase()
+    ));
+
+    // Recovery ids not offset by 27 are accepted as well
+    let mut raw_sig = signature.sig.to_vec();
+    raw_sig[64] -= 27;
+    let raw_signature = Signature {
+      sig: raw_sig.try_into().unwrap(),
+      ..signature.clone()
+    };
+    assert!(TestStatements::verify_ethereum_signature(
+      &r.claim,
+      &raw_signature,
+      ETHEREUM_ADDRESS
+    ));
+
+    r.claim.poe_id = ProofId::from("my-fake-proof-id");
+    assert!(!TestStatements::verify_ethereum_signature(
+      &r.claim,
+      &signature,
+      ETHEREUM_ADDRESS
+    ));
+  });
+}
+
+#[test]
+fn statements_signature_verification_external_vectors() {
+  new_test_ext().execute_with(|| {
+    // `personal_sign` of "Some data", as published by the web3.js documentation of
+    // `web3.eth.accounts.sign`
+    let address = "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23";
+    let signature = hex::decode(
+      "b91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd\
+       6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c",
+    )
+    .unwrap();
+    assert!(TestStatements::verify_personal_sign(b"Some data", &signature, address));
+    assert!(!TestStatements::verify_personal_sign(
+      b"Some other data",
+      &signature,
+      address
+    ));
+    // The signer does not match another address
+    assert!(!TestStatements::verify_personal_sign(
+      b"Some data",
+      &signature,
+      ETHEREUM_ADDRESS
+    ));
+
+    // ed25519 test 2 of RFC 8032
+    let public_key = "0x3d4017c3e843895a92b70aa74d1b7ebc9c982ccf2ec4968cc0cd55f12af4660c";
+    let signature = hex::decode(
+      "92a009a9f0d4cab8720e820b5f642540a2b27b5416503f8fb3762223ebdb69da\
+       085ac1e43e15996e458f3613d0f11d8c387b2eaeb4302aeeb00d291612bb0c00",
+    )
+    .unwrap();
+    assert!(TestStatements::verify_message_signature::<ed25519::Signature>(
+      &[0x72],
+      &signature,
+      public_key
+    ));
+    assert!(!TestStatements::verify_message_signature::<ed25519::Signature>(
+      &[0x73],
+      &signature,
+      public_key
+    ));
+    // Nor is it a valid sr25519 signature
+    assert!(!TestStatements::verify_message_signature::<sr25519::Signature>(
+      &[0x72],
+      &signature,
+      public_key
+    ));
+  });
+}
+
 #[test]
 fn test_template() {
   new_test_ext().execute_with(|| {});
@@ -16,14 +16,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::WeightInfo;
 use anagolay_support::{constants::*, generic_id::GenericId, *};
 use codec::{Decode, Encode};
 use frame_support::{
   pallet_prelude::*,
   sp_runtime::RuntimeDebug,
   sp_std::{clone::Clone, default::Default},
+  storage::with_storage_layer,
 };
-use poe::types::ProofId;
+use poe::types::{ProofId, ProofRevocationHandler};
 use verification::types::{VerificationInvalidator, VerificationRequest};
 
 getter_for_hardcoded_constant!(MaxSignatureLen, u32, 256);
@@ -38,7 +40,9 @@ anagolay_generic_id!(Signature);
 #[derive(Default, Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Signature {
-  /// signing key in urn/did format 'urn:pgp:9cdf8dd38531511968c8d8cb524036585b62f15b'
+  /// signing key in urn/did format 'urn:pgp:9cdf8dd38531511968c8d8cb524036585b62f15b'. Verified
+  /// schemes are 'urn:substrate:<hex public key>', 'urn:ed25519:<hex public key>' and
+  /// 'urn:ethereum:<hex address>'
   pub sig_key: Characters,
   /// Signature sign(prepared_statement, pvtKey(sigKey))
   pub sig: BoundedVec<u8, MaxSignatureLenGet>,
@@ -180,13 +184,78 @@ impl<T: crate::Config> VerificationInvalidator<T> for StatementsVerificationInva
   fn invalidate(request: &VerificationRequest<T::AccountId>) -> Result<(), verification::Error<T>> {
     let proof_ids = poe::Pallet::<T>::proof_ids_by_verification_context(request.context.clone())
       .ok_or(verification::Error::<T>::VerificationInvalidationError)?;
-    for proof_id in proof_ids {
-      let statement_ids = <crate::Pallet<T>>::statement_ids_by_proof_id(proof_id.clone());
+    // The context accumulates the Proofs of all its holders, only the ones of this holder are
+    // affected by the invalidation. The Statements are removed all or none
+    with_storage_layer(|| -> Result<(), DispatchError> {
+      for proof_id in proof_ids
+        .into_iter()
+        .filter(|proof_id| poe::ProofByProofIdAndAccountId::<T>::contains_key(proof_id, &request.holder))
+      {
+        let statement_ids = <crate::Pallet<T>>::statement_ids_by_proof_id(proof_id.clone());
+        for statement_id in statement_ids {
+          <crate::Pallet<T>>::remove_statement_tree(&statement_id, &request.holder)?;
+        }
+      }
+      Ok(())
+    })
+    .map_err(|_| verification::Error::<T>::VerificationInvalidationError)
+  }
+}
+
+/// Implementation for the proof revocation handler that prevents the revocation of a Proof while
+/// Statements are associated to it, or revokes those Statements when cascading
+#[derive(Clone)]
+pub struct StatementsProofRevocationHandler<T: crate::Config> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: crate::Config> ProofRevocationHandler<T> for StatementsProofRevocationHandler<T> {
+  /// Tells whether some Statement is associated to the Proof
+  ///
+  /// # Arguments
+  /// * proof_id - The id of the Proof
+  ///
+  /// # Return
+  /// True if the Proof has Statements, false otherwise
+  fn is_referenced(proof_id: &ProofId) -> bool {
+    !<crate::Pallet<T>>::statement_ids_by_proof_id(proof_id).is_empty()
+  }
+
+  /// Revokes all the Statements associated to the Proof
+  ///
+  /// # Arguments
+  /// * proof_id - The id of the Proof
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn revoke(proof_id: &ProofId) -> Result<(), poe::Error<T>> {
+    let statement_ids = <crate::Pallet<T>>::statement_ids_by_proof_id(proof_id);
+    // The Statements are removed all or none
+    with_storage_layer(|| -> Result<(), DispatchError> {
       for statement_id in statement_ids {
-        <crate::Pallet<T>>::remove_statement(statement_id.clone(), &request.holder)
-          .map_err(|_| verification::Error::<T>::VerificationInvalidationError)?;
+        // A Statement is associated to the Proof only once, so it has a single issuer. A Statement
+        // already removed along with its parent has none
+        let issuer = match crate::StatementByStatementIdAndAccountId::<T>::iter_key_prefix(&statement_id).next() {
+          Some(issuer) => issuer,
+          None => continue,
+        };
+        for removed_id in <crate::Pallet<T>>::remove_statement_tree(&statement_id, &issuer)? {
+          <crate::Pallet<T>>::deposit_event(crate::Event::StatementRevoked(issuer.clone(), removed_id));
+        }
       }
-    }
-    Ok(())
+      Ok(())
+    })
+    .map_err(|_| poe::Error::<T>::ProofRevocationError)
+  }
+
+  /// The upper bound of the weight consumed by `revoke()`
+  ///
+  /// # Return
+  /// The weight of the revocation of `MAX_STATEMENTS_PER_PROOF` Statements, each with a single
+  /// issuer, along with a further read of the child Statements of each parent
+  fn revoke_weight() -> Weight {
+    <T as crate::Config>::WeightInfo::revoke()
+      .saturating_mul(T::MAX_STATEMENTS_PER_PROOF as u64)
+      .saturating_add(T::DbWeight::get().reads(T::MAX_STATEMENTS_PER_PROOF as u64))
   }
 }
@@ -54,70 +54,80 @@ pub trait WeightInfo {
 /// Weights for statements using the Substrate node and recommended hardware.
 pub struct AnagolayWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for AnagolayWeight<T> {
-  // Storage: Statements StatementByStatementIdAndAccountId (r:1 w:1)
+  // Storage: Statements StatementByStatementIdAndAccountId (r:2 w:1)
   // Storage: Statements StatementIdsByProofId (r:1 w:1)
+  // Storage: Statements ChildStatementIdsByStatementId (r:1 w:1)
+  // Storage: Statements ParentStatementIdByStatementId (r:0 w:1)
   // Storage: Statements Total (r:1 w:1)
   fn create_copyright() -> Weight {
     // Minimum execution time: 129_030 nanoseconds.
     Weight::from_ref_time(134_680_000)
-      .saturating_add(T::DbWeight::get().reads(3))
-      .saturating_add(T::DbWeight::get().writes(3))
+      .saturating_add(T::DbWeight::get().reads(5))
+      .saturating_add(T::DbWeight::get().writes(5))
   }
   // Storage: Poe ProofByProofIdAndAccountId (r:1 w:0)
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
   // Storage: Poe ProofIdsByVerificationContext (r:1 w:0)
-  // Storage: Statements StatementByStatementIdAndAccountId (r:1 w:1)
+  // Storage: Statements StatementByStatementIdAndAccountId (r:2 w:1)
   // Storage: Statements StatementIdsByProofId (r:1 w:1)
+  // Storage: Statements ChildStatementIdsByStatementId (r:1 w:1)
+  // Storage: Statements ParentStatementIdByStatementId (r:0 w:1)
   // Storage: Statements Total (r:1 w:1)
   fn create_ownership() -> Weight {
     // Minimum execution time: 155_390 nanoseconds.
     Weight::from_ref_time(158_670_000)
-      .saturating_add(T::DbWeight::get().reads(6))
-      .saturating_add(T::DbWeight::get().writes(3))
+      .saturating_add(T::DbWeight::get().reads(8))
+      .saturating_add(T::DbWeight::get().writes(5))
   }
-  // Storage: Statements StatementByStatementIdAndAccountId (r:1 w:1)
-  // Storage: Statements ParentStatementIdByStatementId (r:1 w:0)
+  // Storage: Statements StatementByStatementIdAndAccountId (r:2 w:1)
+  // Storage: Statements ChildStatementIdsByStatementId (r:2 w:1)
   // Storage: Statements StatementIdsByProofId (r:1 w:1)
   // Storage: Statements Total (r:1 w:1)
+  // Storage: Statements ParentStatementIdByStatementId (r:1 w:1)
   fn revoke() -> Weight {
     // Minimum execution time: 54_149 nanoseconds.
     Weight::from_ref_time(58_030_000)
-      .saturating_add(T::DbWeight::get().reads(4))
-      .saturating_add(T::DbWeight::get().writes(3))
+      .saturating_add(T::DbWeight::get().reads(7))
+      .saturating_add(T::DbWeight::get().writes(5))
   }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
-  // Storage: Statements StatementByStatementIdAndAccountId (r:1 w:1)
+  // Storage: Statements StatementByStatementIdAndAccountId (r:2 w:1)
   // Storage: Statements StatementIdsByProofId (r:1 w:1)
+  // Storage: Statements ChildStatementIdsByStatementId (r:1 w:1)
+  // Storage: Statements ParentStatementIdByStatementId (r:0 w:1)
   // Storage: Statements Total (r:1 w:1)
   fn create_copyright() -> Weight {
     // Minimum execution time: 129_030 nanoseconds.
     Weight::from_ref_time(134_680_000)
-      .saturating_add(RocksDbWeight::get().reads(3))
-      .saturating_add(RocksDbWeight::get().writes(3))
+      .saturating_add(RocksDbWeight::get().reads(5))
+      .saturating_add(RocksDbWeight::get().writes(5))
   }
   // Storage: Poe ProofByProofIdAndAccountId (r:1 w:0)
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
   // Storage: Poe ProofIdsByVerificationContext (r:1 w:0)
-  // Storage: Statements StatementByStatementIdAndAccountId (r:1 w:1)
+  // Storage: Statements StatementByStatementIdAndAccountId (r:2 w:1)
   // Storage: Statements StatementIdsByProofId (r:1 w:1)
+  // Storage: Statements ChildStatementIdsByStatementId (r:1 w:1)
+  // Storage: Statements ParentStatementIdByStatementId (r:0 w:1)
   // Storage: Statements Total (r:1 w:1)
   fn create_ownership() -> Weight {
     // Minimum execution time: 155_390 nanoseconds.
     Weight::from_ref_time(158_670_000)
-      .saturating_add(RocksDbWeight::get().reads(6))
-      .saturating_add(RocksDbWeight::get().writes(3))
+      .saturating_add(RocksDbWeight::get().reads(8))
+      .saturating_add(RocksDbWeight::get().writes(5))
   }
-  // Storage: Statements StatementByStatementIdAndAccountId (r:1 w:1)
-  // Storage: Statements ParentStatementIdByStatementId (r:1 w:0)
+  // Storage: Statements StatementByStatementIdAndAccountId (r:2 w:1)
+  // Storage: Statements ChildStatementIdsByStatementId (r:2 w:1)
   // Storage: Statements StatementIdsByProofId (r:1 w:1)
   // Storage: Statements Total (r:1 w:1)
+  // Storage: Statements ParentStatementIdByStatementId (r:1 w:1)
   fn revoke() -> Weight {
     // Minimum execution time: 54_149 nanoseconds.
     Weight::from_ref_time(58_030_000)
-      .saturating_add(RocksDbWeight::get().reads(4))
-      .saturating_add(RocksDbWeight::get().writes(3))
+      .saturating_add(RocksDbWeight::get().reads(7))
+      .saturating_add(RocksDbWeight::get().writes(5))
   }
 }
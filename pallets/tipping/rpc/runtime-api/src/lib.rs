@@ -31,6 +31,11 @@ sp_api::decl_runtime_apis! {
     /// - Balance: the `Balance` from the runtime `Config` (in, out)
     /// - AccountId: the `AccountId` from the runtime `Config` (in, out)
     /// - BlockNumber: the `BlockNumber` from the runtime `Config` (out)
+    ///
+    /// # Versions
+    /// - 2: adds `get_tips_page`, `escrowed_balance`, `get_tips_aggregate`, `get_tips_by_sender`
+    ///   and `top_supporters`
+    #[api_version(2)]
     pub trait TippingApi<Balance: Debug + Encode + Decode, AccountId: Debug + Encode + Decode, BlockNumber: Debug + Decode> {
 
       /// Get the total balance of tips received for a [`VerificationContext`]
@@ -40,7 +45,7 @@ sp_api::decl_runtime_apis! {
       ///  * verification_context - The [`VerificationContext`] to query
       ///
       /// # Return
-      /// Total balance, sum of all [`Tip`]s for the specified verification context
+      /// Total balance, sum of all [`Tip`]s ever received for the specified verification context
       fn total_received(account_id: AccountId, verification_context: VerificationContext) -> Balance;
 
       /// Get the count of tips for a [`VerificationContext`]
@@ -50,10 +55,10 @@ sp_api::decl_runtime_apis! {
       ///  * verification_context - The [`VerificationContext`] to query
       ///
       /// # Return
-      /// Count of [`Tip`]s for the specified verification context
+      /// Count of [`Tip`]s ever received for the specified verification context
       fn total(account_id: AccountId, verification_context: VerificationContext) -> u64;
 
-      /// Get the tips for an Account and a [`VerificationContext`]
+      /// Get the tips for an Account and a [`VerificationContext`], superseded by `get_tips_page`
       ///
       /// # Arguments
       ///  * account_id - The account to query
@@ -67,7 +72,87 @@ sp_api::decl_runtime_apis! {
         account_id: AccountId,
         verification_context: VerificationContext,
         offset: u64,
-        limit: u16, // why this one doesn't have at param?
+        limit: u16,
       ) -> Vec<Tip<Balance, AccountId, BlockNumber>>;
+
+      /// Get a page of tips for an Account and a [`VerificationContext`]
+      ///
+      /// # Arguments
+      ///  * account_id - The account to query
+      ///  * verification_context - The [`VerificationContext`] to query
+      ///  * sort - The order of the tips on the page
+      ///  * filter - The [`TipsFilter`] the tips on the page must satisfy
+      ///  * cursor - The sequence number of the first Tip on the page, as returned by the previous
+      ///    page
+      ///  * limit - The maximum count of Tips on the page
+      ///
+      /// # Return
+      /// A [`TipsPage`] containing the tips and the cursor of the next page, if any
+      fn get_tips_page(
+        account_id: AccountId,
+        verification_context: VerificationContext,
+        sort: SortTips,
+        filter: TipsFilter<Balance, AccountId, BlockNumber>,
+        cursor: Option<u64>,
+        limit: u16,
+      ) -> TipsPage<Tip<Balance, AccountId, BlockNumber>>;
+
+      /// Get the total balance held in escrow for a [`VerificationContext`] that is not available
+      /// for tipping yet
+      ///
+      /// # Arguments
+      ///  * verification_context - The [`VerificationContext`] to query
+      ///
+      /// # Return
+      /// Total balance, sum of all [`EscrowedTip`]s for the specified verification context
+      fn escrowed_balance(verification_context: VerificationContext) -> Balance;
+
+      /// Get the running aggregates of the tips received for a [`VerificationContext`], optionally
+      /// restricted to the tips of a single sender
+      ///
+      /// # Arguments
+      ///  * account_id - The holder of a successful [`VerificationRequest`] for the verification context
+      ///  * verification_context - The [`VerificationContext`] to query
+      ///  * sender - Additional filter on the sender account
+      ///
+      /// # Return
+      /// The [`TipsAggregate`] for the specified verification context
+      fn get_tips_aggregate(
+        account_id: AccountId,
+        verification_context: VerificationContext,
+        sender: Option<AccountId>,
+      ) -> TipsAggregate<Balance, BlockNumber>;
+
+      /// Get a page of tips given by a sender to any [`VerificationContext`]
+      ///
+      /// # Arguments
+      ///  * sender - The sender to query
+      ///  * sort - The order of the tips on the page, by creation time
+      ///  * cursor - The identifier of the first Tip on the page, as returned by the previous page
+      ///  * limit - The maximum count of Tips on the page
+      ///
+      /// # Return
+      /// A [`TipsPage`] containing the [`SentTip`]s and the cursor of the next page, if any
+      fn get_tips_by_sender(
+        sender: AccountId,
+        sort: SortTips,
+        cursor: Option<u64>,
+        limit: u16,
+      ) -> TipsPage<SentTip<Balance, AccountId, BlockNumber>>;
+
+      /// Get the supporters of a [`VerificationContext`] that tipped the highest total amount
+      ///
+      /// # Arguments
+      ///  * account_id - The holder of a successful [`VerificationRequest`] for the verification context
+      ///  * verification_context - The [`VerificationContext`] to query
+      ///  * count - The maximum count of supporters to return
+      ///
+      /// # Return
+      /// Collection of [`Supporter`]s sorted by the total amount tipped DESC
+      fn top_supporters(
+        account_id: AccountId,
+        verification_context: VerificationContext,
+        count: u16,
+      ) -> Vec<Supporter<Balance, AccountId, BlockNumber>>;
     }
 }
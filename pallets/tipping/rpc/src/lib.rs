@@ -82,6 +82,49 @@ pub trait TippingApi<
     limit: u16,
     at: Option<BlockHash>,
   ) -> RpcResult<Vec<Tip<Balance, AccountId, BlockNumber>>>;
+
+  #[method(name = "tipping_getTipsPage")]
+  fn get_tips_page(
+    &self,
+    account_id: AccountId,
+    verification_context: VerificationContext,
+    sort: SortTips,
+    filter: TipsFilter<Balance, AccountId, BlockNumber>,
+    cursor: Option<u64>,
+    limit: u16,
+    at: Option<BlockHash>,
+  ) -> RpcResult<TipsPage<Tip<Balance, AccountId, BlockNumber>>>;
+
+  #[method(name = "tipping_escrowedBalance")]
+  fn escrowed_balance(&self, verification_context: VerificationContext, at: Option<BlockHash>) -> RpcResult<Balance>;
+
+  #[method(name = "tipping_getTipsAggregate")]
+  fn get_tips_aggregate(
+    &self,
+    account_id: AccountId,
+    verification_context: VerificationContext,
+    sender: Option<AccountId>,
+    at: Option<BlockHash>,
+  ) -> RpcResult<TipsAggregate<Balance, BlockNumber>>;
+
+  #[method(name = "tipping_getTipsBySender")]
+  fn get_tips_by_sender(
+    &self,
+    sender: AccountId,
+    sort: SortTips,
+    cursor: Option<u64>,
+    limit: u16,
+    at: Option<BlockHash>,
+  ) -> RpcResult<TipsPage<SentTip<Balance, AccountId, BlockNumber>>>;
+
+  #[method(name = "tipping_topSupporters")]
+  fn top_supporters(
+    &self,
+    account_id: AccountId,
+    verification_context: VerificationContext,
+    count: u16,
+    at: Option<BlockHash>,
+  ) -> RpcResult<Vec<Supporter<Balance, AccountId, BlockNumber>>>;
 }
 
 /// A struct that implements the `TippingApi`.
@@ -172,4 +215,75 @@ where
       .get_tips(&at, account_id, verification_context, offset, limit)
       .map_err(map_jsonrpc_err)
   }
+
+  fn get_tips_page(
+    &self,
+    account_id: AccountId,
+    verification_context: VerificationContext,
+    sort: SortTips,
+    filter: TipsFilter<Balance, AccountId, BlockNumber>,
+    cursor: Option<u64>,
+    limit: u16,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<TipsPage<Tip<Balance, AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .get_tips_page(&at, account_id, verification_context, sort, filter, cursor, limit)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn escrowed_balance(&self, verification_context: VerificationContext, at: Option<Block::Hash>) -> RpcResult<Balance> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api.escrowed_balance(&at, verification_context).map_err(map_jsonrpc_err)
+  }
+
+  fn get_tips_aggregate(
+    &self,
+    account_id: AccountId,
+    verification_context: VerificationContext,
+    sender: Option<AccountId>,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<TipsAggregate<Balance, BlockNumber>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .get_tips_aggregate(&at, account_id, verification_context, sender)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn get_tips_by_sender(
+    &self,
+    sender: AccountId,
+    sort: SortTips,
+    cursor: Option<u64>,
+    limit: u16,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<TipsPage<SentTip<Balance, AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .get_tips_by_sender(&at, sender, sort, cursor, limit)
+      .map_err(map_jsonrpc_err)
+  }
+
+  fn top_supporters(
+    &self,
+    account_id: AccountId,
+    verification_context: VerificationContext,
+    count: u16,
+    at: Option<Block::Hash>,
+  ) -> RpcResult<Vec<Supporter<Balance, AccountId, BlockNumber>>> {
+    let api = self.client.runtime_api();
+    let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+    api
+      .top_supporters(&at, account_id, verification_context, count)
+      .map_err(map_jsonrpc_err)
+  }
 }
@@ -7,8 +7,12 @@
 #![cfg(feature = "runtime-benchmarks")]
 use super::*;
 use core::convert::TryInto;
-use frame_benchmarking::{benchmarks, impl_benchmark_test_suite, whitelisted_caller};
-use frame_support::{sp_std::vec, BoundedVec};
+use frame_benchmarking::{account, benchmarks, impl_benchmark_test_suite, whitelisted_caller};
+use frame_support::{
+  sp_std::vec,
+  traits::{Currency, EnsureOrigin, Hooks, ReservableCurrency},
+  BoundedVec,
+};
 use frame_system::RawOrigin;
 use types::*;
 
@@ -22,14 +26,140 @@ use crate::Pallet as Tipping;
 
 benchmarks! {
     update_settings {
+        let s in 1 .. 100;
         let caller: T::AccountId = whitelisted_caller();
-        let tipping_settings1 = TippingSettings::default();
-        let tipping_settings2 = TippingSettings::default();
-    }: _(RawOrigin::Signed(caller), vec![tipping_settings1, tipping_settings2])
+
+        let mut tipping_settings = vec![];
+        for i in 0 .. s {
+            let mut url = b"https://anagolay.network/".to_vec();
+            url.extend(i.to_le_bytes());
+            let context = VerificationContext::UrlForDomain(url.into(), "anagolay.network".into());
+            let request = VerificationRequest::<T::AccountId> {
+                context: context.clone(),
+                action: VerificationAction::DnsTxtRecord,
+                holder: caller.clone(),
+                status: VerificationStatus::Success,
+                key: "anagolay-domain-verification=test".into(),
+                id: None,
+            };
+            let accounts: BoundedVec<T::AccountId, MaxVerificationRequestsPerContextGet<T>> = vec![caller.clone()].try_into().unwrap();
+            verification::pallet::AccountIdsByVerificationContext::<T>::insert(context.clone(), accounts);
+            verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), request);
+
+            tipping_settings.push(TippingSettings {
+                context,
+                enabled: true,
+                account: None
+            });
+        }
+    }: _(RawOrigin::Signed(caller), tipping_settings)
+
+    remove_settings {
+        let caller: T::AccountId = whitelisted_caller();
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+        let settings = TippingSettings {
+            context: context.clone(),
+            enabled: true,
+            account: Some(caller.clone())
+        };
+        TippingSettingsByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), settings);
+    }: _(RawOrigin::Signed(caller), context)
 
     tip {
+        let m in 0 .. MAX_MEMO_LEN;
         let caller: T::AccountId = whitelisted_caller();
         let amount = 1u32.into();
+        let memo: Memo = vec![0u8; m as usize].try_into().unwrap();
+        <T as Config>::Currency::make_free_balance_be(&caller, 1_000_000_000u32.into());
+
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+        let action = VerificationAction::DnsTxtRecord;
+
+        let request = VerificationRequest::<T::AccountId> {
+            context: context.clone(),
+            action,
+            holder: caller.clone(),
+            status: VerificationStatus::Success,
+            key: "anagolay-domain-verification=test".into(),
+            id: None,
+        };
+        let accounts: BoundedVec<T::AccountId, MaxVerificationRequestsPerContextGet<T>> = vec![caller.clone()].try_into().unwrap();
+        verification::pallet::AccountIdsByVerificationContext::<T>::insert(context.clone(), accounts);
+        verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), request.clone());
+
+        let settings = TippingSettings {
+            context: context.clone(),
+            enabled: true,
+            account: Some(caller.clone())
+        };
+        TippingSettingsByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), settings);
+        SupporterBadgeThresholdByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), amount);
+    }: _(RawOrigin::Signed(caller), amount, context, Some(memo))
+
+    tip_remote {
+        let m in 0 .. MAX_MEMO_LEN;
+        let holder: T::AccountId = whitelisted_caller();
+        let origin = T::RemoteTipOrigin::successful_origin();
+        let (_, tipper) = T::RemoteTipOrigin::ensure_origin(origin.clone()).unwrap();
+        let amount = 1_000u32.into();
+        let memo: Memo = vec![0u8; m as usize].try_into().unwrap();
+        <T as Config>::Currency::make_free_balance_be(&holder, 1_000_000_000u32.into());
+        <T as Config>::Currency::make_free_balance_be(&tipper, 1_000_000_000u32.into());
+
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+        let request = VerificationRequest::<T::AccountId> {
+            context: context.clone(),
+            action: VerificationAction::DnsTxtRecord,
+            holder: holder.clone(),
+            status: VerificationStatus::Success,
+            key: "anagolay-domain-verification=test".into(),
+            id: None,
+        };
+        let accounts: BoundedVec<T::AccountId, MaxVerificationRequestsPerContextGet<T>> = vec![holder.clone()].try_into().unwrap();
+        verification::pallet::AccountIdsByVerificationContext::<T>::insert(context.clone(), accounts);
+        verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(holder.clone(), context.clone(), request);
+
+        let settings = TippingSettings {
+            context: context.clone(),
+            enabled: true,
+            account: Some(holder.clone())
+        };
+        TippingSettingsByAccountIdAndVerificationContext::<T>::insert(holder.clone(), context.clone(), settings);
+        SupporterBadgeThresholdByAccountIdAndVerificationContext::<T>::insert(holder, context.clone(), amount);
+
+        // Make the tipper eligible for the matching round in progress
+        let tipper_context = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
+        let tipper_request = VerificationRequest::<T::AccountId> {
+            context: tipper_context.clone(),
+            action: VerificationAction::DnsTxtRecord,
+            holder: tipper.clone(),
+            status: VerificationStatus::Success,
+            key: "anagolay-domain-verification=test".into(),
+            id: None,
+        };
+        verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(tipper, tipper_context, tipper_request);
+        MatchingRounds::<T>::insert(0, MatchingRound {
+            start: 0u32.into(),
+            end: 100u32.into(),
+            contexts: 0,
+            distributed: None,
+        });
+        CurrentMatchingRound::<T>::put(0);
+    }: _<T::RuntimeOrigin>(origin, amount, context, Some(memo))
+
+    tip_escrow {
+        let m in 0 .. MAX_MEMO_LEN;
+        let caller: T::AccountId = whitelisted_caller();
+        let amount = 1_000u32.into();
+        let memo: Memo = vec![0u8; m as usize].try_into().unwrap();
+        <T as Config>::Currency::make_free_balance_be(&caller, 1_000_000_000u32.into());
+
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    }: _(RawOrigin::Signed(caller), amount, context, Some(memo))
+
+    claim_escrowed_tips {
+        let c in 0 .. T::MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT;
+        let caller: T::AccountId = whitelisted_caller();
 
         let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
         let action = VerificationAction::DnsTxtRecord;
@@ -52,7 +182,215 @@ benchmarks! {
             account: Some(caller.clone())
         };
         TippingSettingsByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), settings);
-    }: _(RawOrigin::Signed(caller), amount, context)
+        SupporterBadgeThresholdByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), BalanceOf::<T>::from(1u32));
+
+        let escrowed_tips = vec![EscrowedTip {
+            amount: 1_000u32.into(),
+            sender: caller.clone(),
+            created_at: 0,
+            block_number: 0u32.into(),
+            expires_at: T::ESCROW_TIMEOUT,
+            memo: Some(vec![0u8; MAX_MEMO_LEN as usize].try_into().unwrap()),
+        }; c as usize];
+        EscrowedTipsByVerificationContext::<T>::insert(context.clone(), BoundedVec::truncate_from(escrowed_tips));
+        <T as Config>::Currency::make_free_balance_be(&Tipping::<T>::escrow_account_id(), 1_000_000_000u32.into());
+    }: _(RawOrigin::Signed(caller), context)
+
+    refund_escrowed_tips {
+        let c in 0 .. T::MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT;
+        let caller: T::AccountId = whitelisted_caller();
+
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+        let escrowed_tips = vec![EscrowedTip {
+            amount: 1_000u32.into(),
+            sender: caller.clone(),
+            created_at: 0,
+            block_number: 0u32.into(),
+            expires_at: 0u32.into(),
+            memo: Some(vec![0u8; MAX_MEMO_LEN as usize].try_into().unwrap()),
+        }; c as usize];
+        EscrowedTipsByVerificationContext::<T>::insert(context.clone(), BoundedVec::truncate_from(escrowed_tips));
+        <T as Config>::Currency::make_free_balance_be(&Tipping::<T>::escrow_account_id(), 1_000_000_000u32.into());
+        frame_system::Pallet::<T>::set_block_number(1u32.into());
+    }: _(RawOrigin::Signed(caller), context)
+
+    set_supporter_badge {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+        let action = VerificationAction::DnsTxtRecord;
+
+        let request = VerificationRequest::<T::AccountId> {
+            context: context.clone(),
+            action,
+            holder: caller.clone(),
+            status: VerificationStatus::Success,
+            key: "anagolay-domain-verification=test".into(),
+            id: None,
+        };
+        let accounts: BoundedVec<T::AccountId, MaxVerificationRequestsPerContextGet<T>> = vec![caller.clone()].try_into().unwrap();
+        verification::pallet::AccountIdsByVerificationContext::<T>::insert(context.clone(), accounts);
+        verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), request.clone());
+    }: _(RawOrigin::Signed(caller), context, Some(1_000u32.into()))
+
+    create_matching_round {
+        let origin = T::MatchingRoundOrigin::successful_origin();
+    }: _<T::RuntimeOrigin>(origin, 1u32.into(), 100u32.into())
+
+    fund_matching_round {
+        let caller: T::AccountId = whitelisted_caller();
+        <T as Config>::Currency::make_free_balance_be(&caller, 1_000_000_000u32.into());
+        MatchingRounds::<T>::insert(0, MatchingRound {
+            start: 1u32.into(),
+            end: 100u32.into(),
+            contexts: 0,
+            distributed: None,
+        });
+        CurrentMatchingRound::<T>::put(0);
+    }: _(RawOrigin::Signed(caller), 0, 1_000_000u32.into())
+
+    finalize_matching_round {
+        let c in 1 .. T::MAX_CONTEXTS_PER_MATCHING_ROUND;
+        let caller: T::AccountId = whitelisted_caller();
+
+        for i in 0 .. c {
+            let holder: T::AccountId = account("holder", i, 0);
+            let mut url = b"https://anagolay.network/".to_vec();
+            url.extend(i.to_le_bytes());
+            let context = VerificationContext::UrlForDomain(url.into(), "anagolay.network".into());
+            let request = VerificationRequest::<T::AccountId> {
+                context: context.clone(),
+                action: VerificationAction::DnsTxtRecord,
+                holder: holder.clone(),
+                status: VerificationStatus::Success,
+                key: "anagolay-domain-verification=test".into(),
+                id: None,
+            };
+            let accounts: BoundedVec<T::AccountId, MaxVerificationRequestsPerContextGet<T>> = vec![holder.clone()].try_into().unwrap();
+            verification::pallet::AccountIdsByVerificationContext::<T>::insert(context.clone(), accounts);
+            verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(holder.clone(), context.clone(), request);
+            let settings = TippingSettings {
+                context: context.clone(),
+                enabled: true,
+                account: Some(holder.clone())
+            };
+            TippingSettingsByAccountIdAndVerificationContext::<T>::insert(holder, context.clone(), settings);
+            MatchingRoundTallies::<T>::insert(0, context, MatchingRoundTally {
+                sqrt_sum: (i + 1).into(),
+                total: ((i + 1) * (i + 1)).into(),
+                contributors: 1,
+            });
+        }
+        MatchingRounds::<T>::insert(0, MatchingRound {
+            start: 0u32.into(),
+            end: 1u32.into(),
+            contexts: c,
+            distributed: None,
+        });
+        CurrentMatchingRound::<T>::put(0);
+        <T as Config>::Currency::make_free_balance_be(&Tipping::<T>::matching_round_account_id(0), 1_000_000_000u32.into());
+        frame_system::Pallet::<T>::set_block_number(2u32.into());
+    }: _(RawOrigin::Signed(caller), 0)
+
+    set_refund_window {
+        let caller: T::AccountId = whitelisted_caller();
+
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+        let request = VerificationRequest::<T::AccountId> {
+            context: context.clone(),
+            action: VerificationAction::DnsTxtRecord,
+            holder: caller.clone(),
+            status: VerificationStatus::Success,
+            key: "anagolay-domain-verification=test".into(),
+            id: None,
+        };
+        let accounts: BoundedVec<T::AccountId, MaxVerificationRequestsPerContextGet<T>> = vec![caller.clone()].try_into().unwrap();
+        verification::pallet::AccountIdsByVerificationContext::<T>::insert(context.clone(), accounts);
+        verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(caller.clone(), context.clone(), request);
+    }: _(RawOrigin::Signed(caller), context, Some(T::MAX_REFUND_WINDOW))
+
+    cancel_tip {
+        let caller: T::AccountId = whitelisted_caller();
+        let holder: T::AccountId = account("holder", 0, 0);
+        let amount: BalanceOf<T> = 1_000u32.into();
+        <T as Config>::Currency::make_free_balance_be(&caller, 1_000_000_000u32.into());
+        <T as Config>::Currency::reserve(&caller, amount).unwrap();
+
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+        let tip = Tip {
+            amount,
+            net_amount: amount,
+            sender: caller.clone(),
+            receiver: holder.clone(),
+            created_at: 0,
+            block_number: 0u32.into(),
+            memo: Some(vec![0u8; MAX_MEMO_LEN as usize].try_into().unwrap()),
+            state: TipSettlement::Pending(T::MAX_REFUND_WINDOW),
+        };
+        Tipping::<T>::do_store_tip(&holder, &context, tip.clone());
+        Tipping::<T>::do_hold_tip(&holder, &context, tip, T::MAX_REFUND_WINDOW).unwrap();
+    }: _(RawOrigin::Signed(caller), 0)
+
+    settle_pending_tips {
+        let c in 0 .. T::MAX_PENDING_TIPS_PER_BLOCK;
+        let holder: T::AccountId = whitelisted_caller();
+        let amount: BalanceOf<T> = 1_000u32.into();
+        let settles_at: T::BlockNumber = 1u32.into();
+
+        let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+        let request = VerificationRequest::<T::AccountId> {
+            context: context.clone(),
+            action: VerificationAction::DnsTxtRecord,
+            holder: holder.clone(),
+            status: VerificationStatus::Success,
+            key: "anagolay-domain-verification=test".into(),
+            id: None,
+        };
+        let accounts: BoundedVec<T::AccountId, MaxVerificationRequestsPerContextGet<T>> = vec![holder.clone()].try_into().unwrap();
+        verification::pallet::AccountIdsByVerificationContext::<T>::insert(context.clone(), accounts);
+        verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(holder.clone(), context.clone(), request);
+        SupporterBadgeThresholdByAccountIdAndVerificationContext::<T>::insert(holder.clone(), context.clone(), amount);
+        MatchingRounds::<T>::insert(0, MatchingRound {
+            start: 0u32.into(),
+            end: 100u32.into(),
+            contexts: 0,
+            distributed: None,
+        });
+        CurrentMatchingRound::<T>::put(0);
+
+        for i in 0 .. c {
+            let tipper: T::AccountId = account("tipper", i, 0);
+            <T as Config>::Currency::make_free_balance_be(&tipper, 1_000_000_000u32.into());
+            <T as Config>::Currency::reserve(&tipper, amount).unwrap();
+
+            // Make the tipper eligible for the matching round in progress
+            let tipper_context = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
+            let tipper_request = VerificationRequest::<T::AccountId> {
+                context: tipper_context.clone(),
+                action: VerificationAction::DnsTxtRecord,
+                holder: tipper.clone(),
+                status: VerificationStatus::Success,
+                key: "anagolay-domain-verification=test".into(),
+                id: None,
+            };
+            verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<T>::insert(tipper.clone(), tipper_context, tipper_request);
+
+            let tip = Tip {
+                amount,
+                net_amount: amount,
+                sender: tipper,
+                receiver: holder.clone(),
+                created_at: 0,
+                block_number: 0u32.into(),
+                memo: Some(vec![0u8; MAX_MEMO_LEN as usize].try_into().unwrap()),
+                state: TipSettlement::Pending(settles_at),
+            };
+            Tipping::<T>::do_store_tip(&holder, &context, tip.clone());
+            Tipping::<T>::do_hold_tip(&holder, &context, tip, settles_at).unwrap();
+        }
+    }: {
+        Tipping::<T>::on_initialize(settles_at);
+    }
 
 }
 
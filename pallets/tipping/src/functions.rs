@@ -3,14 +3,731 @@
 // Copyright (C) 2019-2023 Anagolay Network.
 
 use super::*;
-use crate::types::*;
-use core::{cmp::Ordering, convert::TryInto};
-use frame_support::sp_std::vec::Vec;
+use crate::{constants::MaxTipsPerVerificationContextGet, types::*};
+use codec::Encode;
+use core::convert::TryInto;
+use frame_support::{
+  dispatch::DispatchResult,
+  ensure,
+  sp_io::hashing::blake2_256,
+  sp_std::{vec, vec::Vec},
+  storage::with_storage_layer,
+  traits::{
+    BalanceStatus, Currency, ExistenceRequirement, Imbalance, OnUnbalanced, ReservableCurrency, UnixTime,
+    WithdrawReasons,
+  },
+  weights::Weight,
+  BoundedVec,
+};
+use sp_runtime::{
+  traits::{AccountIdConversion, One, Saturating, Zero},
+  DispatchError, PerThing, Perbill,
+};
 
-use verification::types::VerificationContext;
+use verification::types::{VerificationContext, VerificationStatus};
+
+/// Shift of the creation time in the cursor of `get_tips_by_sender`, leaving the lower bits to the
+/// sequence of the tip
+const SENT_TIP_CURSOR_TIME_SHIFT: u32 = 32;
+
+/// Maximum count of blocks whose pending tips are settled in a single block, while the backlog
+/// left by the storage migration is drained
+const MAX_SETTLEMENT_BLOCKS_PER_BLOCK: u64 = 32;
 
 /// Internal implementation of the tipping pallet
 impl<T: Config> Pallet<T> {
+  /// Ensure that no storage migration is running, since the calls would read or write storage in
+  /// the format that is being replaced
+  ///
+  /// # Errors
+  /// * `StorageMigrationInProgress` - If the storage is being migrated
+  pub fn ensure_storage_migrated() -> Result<(), Error<T>> {
+    ensure!(
+      !crate::migration::is_migrating::<T>(),
+      Error::<T>::StorageMigrationInProgress
+    );
+    Ok(())
+  }
+
+  /// The account that holds the escrowed tips, derived from the pallet id
+  ///
+  /// # Return
+  /// The escrow `AccountId`
+  pub fn escrow_account_id() -> T::AccountId {
+    T::PALLET_ID.into_account_truncating()
+  }
+
+  /// The account that holds the matching pool of a [`MatchingRound`], derived from the pallet id
+  ///
+  /// # Arguments
+  ///  * round_id - The identifier of the [`MatchingRound`]
+  ///
+  /// # Return
+  /// The `AccountId` of the matching round
+  pub fn matching_round_account_id(round_id: MatchingRoundId) -> T::AccountId {
+    T::PALLET_ID.into_sub_account_truncating(round_id)
+  }
+
+  /// Get the holder of the successful verification of a [`VerificationContext`], along with the
+  /// account configured to receive the tips
+  ///
+  /// # Arguments
+  ///  * context - The [`VerificationContext`] to query
+  ///
+  /// # Errors
+  /// * `InvalidVerificationContext` - If the [`VerificationContext`] is not available for tipping
+  /// * `InvalidConfiguration` - If tipping is disabled or not configured for the context
+  ///
+  /// # Return
+  /// A tuple of the holder and the receiver account
+  pub fn get_tipping_receiver(context: &VerificationContext) -> Result<(T::AccountId, T::AccountId), Error<T>> {
+    // Retrieve the successful verification request associated to the context
+    let requests =
+      verification::Pallet::<T>::get_requests(vec![context.clone()], Some(VerificationStatus::Success), None, 0, 1);
+    ensure!(requests.len() == 1, Error::<T>::InvalidVerificationContext);
+    let holder = requests.first().unwrap().holder.clone();
+
+    // Ensure that the tipping is enabled and configured for the context
+    let settings = TippingSettingsByAccountIdAndVerificationContext::<T>::get(holder.clone(), context.clone());
+    match (settings.enabled, settings.account) {
+      (true, Some(account)) => Ok((holder, account)),
+      _ => Err(Error::<T>::InvalidConfiguration),
+    }
+  }
+
+  /// Transfer the amount of a tip to the receiver, deducting the `ProtocolFee` that is handed over
+  /// to the `ProtocolFeeHandler`
+  ///
+  /// # Arguments
+  ///  * source - The account the amount is taken from
+  ///  * receiver - The account configured to receive the tips
+  ///  * amount - The gross amount of the tip
+  ///  * existence_requirement - The [`ExistenceRequirement`] of the source account
+  ///
+  /// # Return
+  /// `Result` containing the net amount transferred to the receiver
+  pub fn do_transfer_tip(
+    source: &T::AccountId,
+    receiver: &T::AccountId,
+    amount: BalanceOf<T>,
+    existence_requirement: ExistenceRequirement,
+  ) -> Result<BalanceOf<T>, DispatchError> {
+    let fee = T::PROTOCOL_FEE.mul_floor(amount);
+    let net_amount = amount.saturating_sub(fee);
+
+    <T as Config>::Currency::transfer(source, receiver, net_amount, existence_requirement)?;
+    if !fee.is_zero() {
+      let imbalance = <T as Config>::Currency::withdraw(source, fee, WithdrawReasons::FEE, existence_requirement)?;
+      T::ProtocolFeeHandler::on_unbalanced(imbalance);
+    }
+
+    Ok(net_amount)
+  }
+
+  /// Remove the [`TippingSettings`], the supporter badge configuration and the refund window of a
+  /// [`VerificationContext`], so that it can no longer be tipped. The tips already pending settle
+  /// as usual
+  ///
+  /// # Arguments
+  ///  * holder - The holder of the verification of the context
+  ///  * context - The [`VerificationContext`] to disable
+  ///
+  /// # Events
+  /// * `TippingSettingsRemoved` - when the [`TippingSettings`] are removed
+  pub fn do_remove_settings(holder: &T::AccountId, context: &VerificationContext) {
+    TippingSettingsByAccountIdAndVerificationContext::<T>::remove(holder, context);
+    SupporterBadgeThresholdByAccountIdAndVerificationContext::<T>::remove(holder, context);
+    RefundWindowByAccountIdAndVerificationContext::<T>::remove(holder, context);
+
+    Self::deposit_event(Event::TippingSettingsRemoved(holder.clone(), context.clone()));
+  }
+
+  /// Charge the sender of a tip for the attached memo, if any. The withdrawn amount is burned
+  ///
+  /// # Arguments
+  ///  * sender - The sender of the tip
+  ///  * memo - The optional [`Memo`] attached to the tip
+  ///
+  /// # Return
+  /// `DispatchResult` containing Unit type
+  pub fn charge_memo_fee(sender: &T::AccountId, memo: &Option<Memo>) -> DispatchResult {
+    if let Some(memo) = memo {
+      let memo_fee = T::MEMO_FEE_PER_BYTE.saturating_mul((memo.len() as u32).into());
+      let _ =
+        <T as Config>::Currency::withdraw(sender, memo_fee, WithdrawReasons::FEE, ExistenceRequirement::KeepAlive)?;
+    }
+    Ok(())
+  }
+
+  /// Transfer a tip from the sender to the account configured to receive the tips for a
+  /// [`VerificationContext`], charge the fee for the memo and store the resulting [`Tip`]. If the
+  /// holder configured a refund window, the amount is reserved on the sender instead and the
+  /// [`Tip`] is pending until it settles
+  ///
+  /// # Arguments
+  ///  * tipper - The sender of the tip
+  ///  * amount - The amount to tip
+  ///  * context - The tipped [`VerificationContext`]
+  ///  * memo - The optional [`Memo`] attached to the tip
+  ///
+  /// # Errors
+  /// * `InvalidVerificationContext` - If the [`VerificationContext`] is not available for tipping
+  /// * `InvalidConfiguration` - If tipping is disabled or not configured for the context
+  /// * `MaxPendingTipsPerBlockLimitReached` - If the tip would be held but the maximum number of
+  ///   pending tips settling in the same block has already been reached
+  ///
+  /// # Events
+  /// * `TipPending` - when the [`Tip`] is held in the refund window of the holder
+  ///
+  /// # Return
+  /// A tuple of the holder of the context and the stored [`Tip`]
+  pub fn do_tip(
+    tipper: &T::AccountId,
+    amount: BalanceOf<T>,
+    context: &VerificationContext,
+    memo: Option<Memo>,
+  ) -> Result<(T::AccountId, Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>), DispatchError> {
+    // Retrieve the holder of the context and the account configured to receive the tips
+    let (tipped, receiver_account) = Self::get_tipping_receiver(context)?;
+
+    // Make the transfer, or hold the amount if the holder configured a refund window
+    let block_number = <frame_system::Pallet<T>>::block_number();
+    let (net_amount, state) = match RefundWindowByAccountIdAndVerificationContext::<T>::get(&tipped, context) {
+      Some(window) => {
+        <T as Config>::Currency::reserve(tipper, amount)?;
+        let fee = T::PROTOCOL_FEE.mul_floor(amount);
+        (
+          amount.saturating_sub(fee),
+          TipSettlement::Pending(block_number.saturating_add(window)),
+        )
+      }
+      None => (
+        Self::do_transfer_tip(tipper, &receiver_account, amount, ExistenceRequirement::KeepAlive)?,
+        TipSettlement::Settled,
+      ),
+    };
+    Self::charge_memo_fee(tipper, &memo)?;
+
+    // Fill in tip information and store the tip
+    let tip = Tip {
+      amount,
+      net_amount,
+      sender: tipper.clone(),
+      receiver: receiver_account,
+      created_at: T::TimeProvider::now().as_secs(),
+      block_number,
+      memo,
+      state,
+    };
+    Self::do_store_tip(&tipped, context, tip.clone());
+    match state {
+      TipSettlement::Pending(settles_at) => Self::do_hold_tip(&tipped, context, tip.clone(), settles_at)?,
+      _ => Self::do_account_matching_contribution(&tipped, context, tipper, net_amount),
+    }
+
+    Ok((tipped, tip))
+  }
+
+  /// Record a [`Tip`] as pending until the block where it settles, so that the sender can cancel
+  /// it meanwhile
+  ///
+  /// # Arguments
+  ///  * holder - The holder of a successful [`VerificationRequest`] for the verification context
+  ///  * context - The tipped [`VerificationContext`]
+  ///  * tip - The pending [`Tip`]
+  ///  * settles_at - The block where the tip settles
+  ///
+  /// # Errors
+  /// * `MaxPendingTipsPerBlockLimitReached` - If the maximum number of pending tips settling in
+  ///   the same block has already been reached
+  ///
+  /// # Events
+  /// * `TipPending` - when the [`PendingTip`] is successfully recorded
+  ///
+  /// # Return
+  /// `DispatchResult` containing Unit type
+  pub fn do_hold_tip(
+    holder: &T::AccountId,
+    context: &VerificationContext,
+    tip: Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+    settles_at: T::BlockNumber,
+  ) -> DispatchResult {
+    let pending_tip_id = NextPendingTipId::<T>::get();
+    PendingTipsBySettlementBlock::<T>::try_mutate(settles_at, |pending_tip_ids| {
+      pending_tip_ids
+        .try_push(pending_tip_id)
+        .map_err(|_| Error::<T>::MaxPendingTipsPerBlockLimitReached)
+    })?;
+    NextPendingTipId::<T>::put(pending_tip_id.saturating_add(1));
+
+    let sender = tip.sender.clone();
+    PendingTips::<T>::insert(
+      pending_tip_id,
+      PendingTip {
+        holder: holder.clone(),
+        context: context.clone(),
+        tip,
+      },
+    );
+
+    Self::deposit_event(Event::TipPending(pending_tip_id, sender, settles_at));
+
+    Ok(())
+  }
+
+  /// Settle the pending tips whose refund window is over. The blocks are settled in order from the
+  /// one following `PendingTipsSettledUntil`, so that the tips of the blocks skipped while the
+  /// storage was migrated are settled as well. Each call settles at most
+  /// `MAX_PENDING_TIPS_PER_BLOCK` tips and visits at most [`MAX_SETTLEMENT_BLOCKS_PER_BLOCK`]
+  /// blocks, the backlog is drained by the following calls
+  ///
+  /// # Arguments
+  ///  * block_number - The current block
+  ///
+  /// # Return
+  /// The weight consumed by the settlement
+  pub fn do_settle_pending_tips(block_number: T::BlockNumber) -> Weight {
+    let mut next_block = PendingTipsSettledUntil::<T>::get()
+      .map_or(block_number, |settled_until| settled_until.saturating_add(One::one()));
+    let mut count = 0u32;
+    let mut blocks = 0u64;
+
+    while next_block <= block_number && blocks < MAX_SETTLEMENT_BLOCKS_PER_BLOCK {
+      let pending_tip_ids = PendingTipsBySettlementBlock::<T>::get(next_block);
+      let block_count = pending_tip_ids.len() as u32;
+      if count > 0 && count.saturating_add(block_count) > T::MAX_PENDING_TIPS_PER_BLOCK {
+        break;
+      }
+      PendingTipsBySettlementBlock::<T>::remove(next_block);
+      for pending_tip_id in pending_tip_ids {
+        if let Some(pending_tip) = PendingTips::<T>::take(pending_tip_id) {
+          Self::do_settle_tip(pending_tip_id, pending_tip);
+        }
+      }
+      count = count.saturating_add(block_count);
+      blocks = blocks.saturating_add(1);
+      next_block = next_block.saturating_add(One::one());
+    }
+    PendingTipsSettledUntil::<T>::put(next_block.saturating_sub(One::one()));
+
+    // The benchmark accounts for a single block of pending tips and a single read of the
+    // `MatchingContributorFilter` for each tip, which may read up to `MAX_READS` entries
+    <T as Config>::WeightInfo::settle_pending_tips(count)
+      .saturating_add(T::DbWeight::get().reads_writes(blocks.saturating_add(1), blocks.saturating_add(1)))
+      .saturating_add(
+        T::DbWeight::get().reads(T::MatchingContributorFilter::MAX_READS.saturating_mul(u64::from(count))),
+      )
+  }
+
+  /// Settle a [`PendingTip`]: the net amount is moved from the reserved balance of the sender to
+  /// the receiver and the protocol fee is handed over to the `ProtocolFeeHandler`. Then the tip
+  /// is accounted in the current matching round and may earn a supporter badge. If the net amount
+  /// cannot be moved, for instance because the receiver account cannot be created, the tip is
+  /// released back to the sender. If the reserved balance was partially slashed meanwhile, the tip
+  /// is recorded with the amounts actually moved and the running aggregates are reduced by the
+  /// difference
+  ///
+  /// # Arguments
+  ///  * pending_tip_id - The identifier of the [`PendingTip`]
+  ///  * pending_tip - The [`PendingTip`] to settle
+  ///
+  /// # Events
+  /// * `TipSettled` - when the [`Tip`] is successfully settled
+  /// * `TipRefunded` - when the [`Tip`] is released back to the sender
+  pub fn do_settle_tip(
+    pending_tip_id: PendingTipId,
+    pending_tip: PendingTip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+  ) {
+    let PendingTip { holder, context, tip } = pending_tip.clone();
+
+    let not_moved = match <T as Config>::Currency::repatriate_reserved(
+      &tip.sender,
+      &tip.receiver,
+      tip.net_amount,
+      BalanceStatus::Free,
+    ) {
+      Ok(not_moved) => not_moved,
+      Err(_) => return Self::do_release_tip(pending_tip_id, pending_tip),
+    };
+    let net_amount = tip.net_amount.saturating_sub(not_moved);
+    let fee = tip.amount.saturating_sub(tip.net_amount);
+    let mut fee_paid: BalanceOf<T> = Zero::zero();
+    if !fee.is_zero() {
+      let (imbalance, _) = <T as Config>::Currency::slash_reserved(&tip.sender, fee);
+      fee_paid = imbalance.peek();
+      T::ProtocolFeeHandler::on_unbalanced(imbalance);
+    }
+
+    let settled_tip = Tip {
+      amount: net_amount.saturating_add(fee_paid),
+      net_amount,
+      state: TipSettlement::Settled,
+      ..tip.clone()
+    };
+    Self::do_update_tip(&holder, &context, &tip, settled_tip.clone());
+    if !not_moved.is_zero() {
+      TipsAggregateByAccountIdAndVerificationContext::<T>::mutate(&holder, &context, |aggregate| {
+        aggregate.deduct(not_moved)
+      });
+      TipsAggregateByAccountIdAndVerificationContextAndSender::<T>::mutate(
+        (&holder, &context, tip.sender.clone()),
+        |aggregate| aggregate.deduct(not_moved),
+      );
+    }
+    let tip = settled_tip;
+    Self::do_account_matching_contribution(&holder, &context, &tip.sender, tip.net_amount);
+    Self::do_award_supporter_badge(&holder, &context, &tip);
+
+    Self::deposit_event(Event::TipSettled(pending_tip_id, holder, context, tip));
+  }
+
+  /// Release the amount of a [`PendingTip`] held as reserved balance back to the sender, marking
+  /// the [`Tip`] as refunded and removing its net amount from the running aggregates
+  ///
+  /// # Arguments
+  ///  * pending_tip_id - The identifier of the [`PendingTip`]
+  ///  * pending_tip - The [`PendingTip`] to release
+  ///
+  /// # Events
+  /// * `TipRefunded` - when the [`Tip`] is successfully refunded
+  pub fn do_release_tip(
+    pending_tip_id: PendingTipId,
+    pending_tip: PendingTip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+  ) {
+    let PendingTip { holder, context, tip } = pending_tip;
+
+    <T as Config>::Currency::unreserve(&tip.sender, tip.amount);
+    Self::do_update_tip(
+      &holder,
+      &context,
+      &tip,
+      Tip {
+        state: TipSettlement::Refunded,
+        ..tip.clone()
+      },
+    );
+    TipsAggregateByAccountIdAndVerificationContext::<T>::mutate(&holder, &context, |aggregate| aggregate.refund(&tip));
+    TipsAggregateByAccountIdAndVerificationContextAndSender::<T>::mutate(
+      (&holder, &context, tip.sender.clone()),
+      |aggregate| aggregate.refund(&tip),
+    );
+
+    Self::deposit_event(Event::TipRefunded(pending_tip_id, tip.sender, context, tip.amount));
+  }
+
+  /// Update a stored [`Tip`], for instance its settlement state, both in the tips of the receiver
+  /// and in the tips of the sender. Nothing is updated if the tip was already discarded
+  ///
+  /// # Arguments
+  ///  * holder - The holder of a successful [`VerificationRequest`] for the verification context
+  ///  * context - The tipped [`VerificationContext`]
+  ///  * tip - The [`Tip`] as stored
+  ///  * updated_tip - The [`Tip`] to store in place of it
+  pub fn do_update_tip(
+    holder: &T::AccountId,
+    context: &VerificationContext,
+    tip: &Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+    updated_tip: Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+  ) {
+    let update =
+      |tips: &mut BoundedVec<Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>, MaxTipsPerVerificationContextGet<T>>| {
+        if let Some(stored_tip) = tips.iter_mut().rev().find(|stored_tip| **stored_tip == *tip) {
+          *stored_tip = updated_tip.clone();
+        }
+      };
+    TipsByAccountIdAndVerificationContext::<T>::mutate(holder, context, update);
+    TipsBySenderAndVerificationContext::<T>::mutate(&tip.sender, context, update);
+  }
+
+  /// Store a [`Tip`] in `TipsByAccountIdAndVerificationContext`, index it in
+  /// `TipsBySenderAndVerificationContext` along with its sequence for the sender and account for it
+  /// in the running aggregates. Once the maximum number of tips for the context is reached, the
+  /// oldest tip is discarded
+  ///
+  /// # Arguments
+  ///  * holder - The holder of a successful [`VerificationRequest`] for the verification context
+  ///  * context - The tipped [`VerificationContext`]
+  ///  * tip - The [`Tip`] to store
+  pub fn do_store_tip(
+    holder: &T::AccountId,
+    context: &VerificationContext,
+    tip: Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+  ) {
+    TipsByAccountIdAndVerificationContext::<T>::mutate(holder, context, |existing_tips| {
+      Self::push_tip(existing_tips, tip.clone())
+    });
+    Self::do_index_sent_tip(context, tip.clone());
+    TipsAggregateByAccountIdAndVerificationContext::<T>::mutate(holder, context, |aggregate| aggregate.add(&tip));
+    TipsAggregateByAccountIdAndVerificationContextAndSender::<T>::mutate(
+      (holder, context, tip.sender.clone()),
+      |aggregate| aggregate.add(&tip),
+    );
+  }
+
+  /// Index a [`Tip`] by its sender in `TipsBySenderAndVerificationContext` and assign it the next
+  /// sequence of the sender in `SentTipSequencesBySenderAndVerificationContext`
+  ///
+  /// # Arguments
+  ///  * context - The tipped [`VerificationContext`]
+  ///  * tip - The [`Tip`] to index
+  pub(crate) fn do_index_sent_tip(context: &VerificationContext, tip: Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>) {
+    let sequence = SentTipsCountBySender::<T>::mutate(&tip.sender, |count| {
+      let sequence = *count;
+      *count = count.saturating_add(1);
+      sequence
+    });
+    SentTipSequencesBySenderAndVerificationContext::<T>::mutate(&tip.sender, context, |sequences| {
+      Self::push_tip(sequences, sequence)
+    });
+    TipsBySenderAndVerificationContext::<T>::mutate(&tip.sender, context, |existing_tips| {
+      Self::push_tip(existing_tips, tip.clone())
+    });
+  }
+
+  /// Mint a supporter badge into the account of the sender of a [`Tip`], if the holder of the
+  /// [`VerificationContext`] configured a threshold and the tip amount reaches it. The collection
+  /// of the holder is created along with the first badge. Minting is best effort: if the
+  /// `SupporterBadgeMinter` fails, the changes are reverted and no badge is awarded
+  ///
+  /// # Arguments
+  ///  * holder - The holder of a successful [`VerificationRequest`] for the verification context
+  ///  * context - The tipped [`VerificationContext`]
+  ///  * tip - The [`Tip`] that may earn the badge
+  ///
+  /// # Events
+  /// * `SupporterBadgeMinted` - when the badge is successfully minted
+  pub fn do_award_supporter_badge(
+    holder: &T::AccountId,
+    context: &VerificationContext,
+    tip: &Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+  ) {
+    match SupporterBadgeThresholdByAccountIdAndVerificationContext::<T>::get(holder, context) {
+      Some(threshold) if tip.amount >= threshold => (),
+      _ => return,
+    }
+
+    // The tip is already accounted for in the aggregate of the context
+    let tips_count = TipsAggregateByAccountIdAndVerificationContext::<T>::get(holder, context).count;
+    let metadata = SupporterBadgeMetadata {
+      context_hash: blake2_256(&context.encode()),
+      tip_index: tips_count.saturating_sub(1),
+      amount: tip.amount,
+      created_at: tip.created_at,
+      block_number: tip.block_number,
+    };
+    // Reserve a new collection identifier out of the storage layer, so that an identifier that
+    // cannot be created, e.g. because it is already in use, is skipped by the next attempts
+    let existing_collection = SupporterBadgeCollectionByAccountId::<T>::get(holder);
+    let collection_id = match &existing_collection {
+      Some(collection) => collection.collection_id,
+      None => NextSupporterBadgeCollectionId::<T>::mutate(|next_collection_id| {
+        let collection_id = *next_collection_id;
+        *next_collection_id = collection_id.saturating_add(One::one());
+        collection_id
+      }),
+    };
+
+    let minted = with_storage_layer(|| -> Result<_, DispatchError> {
+      let mut collection = match existing_collection {
+        Some(collection) => collection,
+        None => {
+          T::SupporterBadgeMinter::create_collection(&collection_id, holder)?;
+          SupporterBadgeCollection {
+            collection_id,
+            next_item_id: Zero::zero(),
+          }
+        }
+      };
+
+      let item_id = collection.next_item_id;
+      T::SupporterBadgeMinter::mint(&collection_id, &item_id, &tip.sender, metadata.encode())?;
+      collection.next_item_id = item_id.saturating_add(One::one());
+      SupporterBadgeCollectionByAccountId::<T>::insert(holder, collection);
+
+      Ok(item_id)
+    });
+
+    if let Ok(item_id) = minted {
+      Self::deposit_event(Event::SupporterBadgeMinted(
+        tip.sender.clone(),
+        holder.clone(),
+        context.clone(),
+        collection_id,
+        item_id,
+      ));
+    }
+  }
+
+  /// Push a [`Tip`], or its sequence, in a bounded collection, discarding the oldest one if the
+  /// collection is full
+  ///
+  /// # Arguments
+  ///  * tips - The collection to update
+  ///  * tip - The [`Tip`], or its sequence, to push
+  pub(crate) fn push_tip<Item: Clone>(tips: &mut BoundedVec<Item, MaxTipsPerVerificationContextGet<T>>, tip: Item) {
+    tips.try_push(tip.clone()).unwrap_or_else(|_| {
+      tips.slide(0, tips.len());
+      tips.force_push(tip);
+    })
+  }
+
+  /// Account for a tip in the current [`MatchingRound`], if the round is open and the sender is
+  /// eligible. Self-tips are never accounted and, once the maximum number of contexts of the round
+  /// is reached, tips to further contexts are ignored
+  ///
+  /// # Arguments
+  ///  * holder - The holder of a successful [`VerificationRequest`] for the verification context
+  ///  * context - The tipped [`VerificationContext`]
+  ///  * sender - The sender of the tip
+  ///  * amount - The amount received by the context, net of the protocol fee
+  pub fn do_account_matching_contribution(
+    holder: &T::AccountId,
+    context: &VerificationContext,
+    sender: &T::AccountId,
+    amount: BalanceOf<T>,
+  ) {
+    let round_id = match CurrentMatchingRound::<T>::get() {
+      Some(round_id) => round_id,
+      None => return,
+    };
+    let mut round = match MatchingRounds::<T>::get(round_id) {
+      Some(round) => round,
+      None => return,
+    };
+
+    let block_number = <frame_system::Pallet<T>>::block_number();
+    if block_number < round.start ||
+      block_number > round.end ||
+      sender == holder ||
+      !T::MatchingContributorFilter::is_eligible(sender)
+    {
+      return;
+    }
+
+    let mut tally = match MatchingRoundTallies::<T>::get(round_id, context) {
+      Some(tally) => tally,
+      None => {
+        if round.contexts >= T::MAX_CONTEXTS_PER_MATCHING_ROUND {
+          return;
+        }
+        round.contexts = round.contexts.saturating_add(1);
+        MatchingRounds::<T>::insert(round_id, round);
+        MatchingRoundTally::default()
+      }
+    };
+    MatchingRoundContributions::<T>::mutate((round_id, context, sender), |contribution| {
+      tally.add(*contribution, amount);
+      *contribution = contribution.saturating_add(amount);
+    });
+    MatchingRoundTallies::<T>::insert(round_id, context, tally);
+  }
+
+  /// Distribute the matching pool of a [`MatchingRound`] to the verification contexts that
+  /// received contributions, in proportion to the weight of their [`MatchingRoundTally`]. The
+  /// shares that cannot be paid out, because the context is no longer available for tipping, and
+  /// the rounding remainder are handed over to the `ProtocolFeeHandler`
+  ///
+  /// # Arguments
+  ///  * round_id - The identifier of the [`MatchingRound`]
+  ///
+  /// # Events
+  /// * `MatchingFundsDistributed` - for every context receiving matching funds
+  ///
+  /// # Return
+  /// The amount distributed to the verification contexts
+  pub fn do_distribute_matching_funds(round_id: MatchingRoundId) -> BalanceOf<T> {
+    let round_account = Self::matching_round_account_id(round_id);
+    let pool = <T as Config>::Currency::free_balance(&round_account);
+
+    let weights: Vec<(VerificationContext, BalanceOf<T>)> = MatchingRoundTallies::<T>::iter_prefix(round_id)
+      .map(|(context, tally)| (context, tally.weight()))
+      .collect();
+    let total_weight = weights
+      .iter()
+      .fold(BalanceOf::<T>::zero(), |acc, (_, weight)| acc.saturating_add(*weight));
+
+    let mut distributed = BalanceOf::<T>::zero();
+    if !total_weight.is_zero() {
+      for (context, weight) in weights {
+        let share = Perbill::from_rational(weight, total_weight).mul_floor(pool);
+        if share.is_zero() {
+          continue;
+        }
+        let paid_holder = Self::get_tipping_receiver(&context)
+          .ok()
+          .and_then(|(holder, receiver)| {
+            <T as Config>::Currency::transfer(&round_account, &receiver, share, ExistenceRequirement::AllowDeath)
+              .ok()
+              .map(|_| holder)
+          });
+        if let Some(holder) = paid_holder {
+          distributed = distributed.saturating_add(share);
+          Self::deposit_event(Event::MatchingFundsDistributed(round_id, context, holder, share));
+        }
+      }
+    }
+
+    let remainder = <T as Config>::Currency::free_balance(&round_account);
+    if !remainder.is_zero() {
+      if let Ok(imbalance) = <T as Config>::Currency::withdraw(
+        &round_account,
+        remainder,
+        WithdrawReasons::TRANSFER,
+        ExistenceRequirement::AllowDeath,
+      ) {
+        T::ProtocolFeeHandler::on_unbalanced(imbalance);
+      }
+    }
+
+    distributed
+  }
+
+  /// Transfer the amount of an [`EscrowedTip`] from the escrow account back to its sender
+  ///
+  /// # Arguments
+  ///  * escrow_account - The escrow account
+  ///  * context - The [`VerificationContext`] the tip was addressed to
+  ///  * escrowed_tip - The [`EscrowedTip`] to refund
+  ///
+  /// # Events
+  /// * `EscrowedTipRefunded` - when the [`EscrowedTip`] is successfully refunded
+  ///
+  /// # Return
+  /// `DispatchResult` containing Unit type
+  pub fn do_refund_escrowed_tip(
+    escrow_account: &T::AccountId,
+    context: &VerificationContext,
+    escrowed_tip: EscrowedTip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+  ) -> DispatchResult {
+    <T as Config>::Currency::transfer(
+      escrow_account,
+      &escrowed_tip.sender,
+      escrowed_tip.amount,
+      ExistenceRequirement::AllowDeath,
+    )?;
+    Self::deposit_event(Event::EscrowedTipRefunded(
+      escrowed_tip.sender,
+      context.clone(),
+      escrowed_tip.amount,
+    ));
+    Ok(())
+  }
+
+  /// Get the total balance held in escrow for a [`VerificationContext`]
+  ///
+  /// # Arguments
+  ///  * verification_context - The [`VerificationContext`] to query
+  ///
+  /// # Return
+  /// Total balance, sum of all [`EscrowedTip`]s for the specified verification context
+  pub fn escrowed_balance(verification_context: VerificationContext) -> BalanceOf<T> {
+    EscrowedTipsByVerificationContext::<T>::get(verification_context)
+      .iter()
+      .fold(Zero::zero(), |acc: BalanceOf<T>, escrowed_tip| {
+        acc.saturating_add(escrowed_tip.amount)
+      })
+  }
+
   /// Get the total balance of tips received for a [`VerificationContext`]
   ///
   /// # Arguments
@@ -18,11 +735,9 @@ impl<T: Config> Pallet<T> {
   ///  * verification_context - The [`VerificationContext`] to query
   ///
   /// # Return
-  /// Total balance, sum of all [`Tip`]s for the specified verification context
+  /// Total balance, sum of all [`Tip`]s ever received for the specified verification context
   pub fn total_received(holder: T::AccountId, verification_context: VerificationContext) -> BalanceOf<T> {
-    TipsByAccountIdAndVerificationContext::<T>::get(holder, verification_context)
-      .iter()
-      .fold(0u32.into(), |acc, tip| acc + tip.amount)
+    TipsAggregateByAccountIdAndVerificationContext::<T>::get(holder, verification_context).total_received
   }
 
   /// Get the count of tips for a [`VerificationContext`]
@@ -32,22 +747,43 @@ impl<T: Config> Pallet<T> {
   ///  * verification_context - The [`VerificationContext`] to query
   ///
   /// # Return
-  /// Count of [`Tip`]s for the specified verification context
+  /// Count of [`Tip`]s ever received for the specified verification context
   pub fn total(holder: T::AccountId, verification_context: VerificationContext) -> u64 {
-    TipsByAccountIdAndVerificationContext::<T>::get(holder, verification_context)
-      .iter()
-      .count()
-      .try_into()
-      .unwrap_or(0u64)
+    TipsAggregateByAccountIdAndVerificationContext::<T>::get(holder, verification_context).count
+  }
+
+  /// Get the running aggregates of the tips received for a [`VerificationContext`], optionally
+  /// restricted to the tips of a single sender
+  ///
+  /// # Arguments
+  ///  * holder - The holder of a successful [`VerificationRequest`] for the verification context
+  ///  * verification_context - The [`VerificationContext`] to query
+  ///  * sender - Additional filter on the sender account
+  ///
+  /// # Return
+  /// The [`TipsAggregate`] for the specified verification context
+  pub fn get_tips_aggregate(
+    holder: T::AccountId,
+    verification_context: VerificationContext,
+    sender: Option<T::AccountId>,
+  ) -> TipsAggregate<BalanceOf<T>, T::BlockNumber> {
+    match sender {
+      Some(sender) => {
+        TipsAggregateByAccountIdAndVerificationContextAndSender::<T>::get((holder, verification_context, sender))
+      }
+      None => TipsAggregateByAccountIdAndVerificationContext::<T>::get(holder, verification_context),
+    }
   }
 
-  /// Get the tips for an Account and a [`VerificationContext`]
+  /// Get the tips for an Account and a [`VerificationContext`], sorted by creation time DESC.
+  /// Superseded by [`Pallet::get_tips_page`], kept for the clients of the first version of the
+  /// runtime API
   ///
   /// # Arguments
   ///  * account_id - The account to query
   ///  * context - The [`VerificationContext`] to query
-  ///  * offset - The index, inside the ids set, of the first Tip on the page. Default is 0
-  ///  * limit - The count of Tips on the page. Default is 100
+  ///  * offset - The index, inside the sorted tips, of the first Tip on the page
+  ///  * limit - The count of Tips on the page
   ///
   /// # Return
   /// Collection of [`Tip`]
@@ -57,15 +793,144 @@ impl<T: Config> Pallet<T> {
     offset: u64,
     limit: u16,
   ) -> Vec<Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>> {
-    let mut page = Vec::new();
-    let mut tips = TipsByAccountIdAndVerificationContext::<T>::get(account_id, context);
-    tips.sort_by(|a, b| b.created_at.partial_cmp(&a.created_at).unwrap_or(Ordering::Equal));
+    let mut tips = TipsByAccountIdAndVerificationContext::<T>::get(account_id, context).into_inner();
+    tips.sort_by(|a, b| b.created_at.cmp(&a.created_at));
     tips
-      .iter()
-      .skip(offset.try_into().unwrap_or(0))
-      .take(limit.try_into().unwrap_or(100))
-      .for_each(|tip| page.push(tip.clone()));
+      .into_iter()
+      .skip(offset.try_into().unwrap_or(usize::MAX))
+      .take(limit as usize)
+      .collect()
+  }
+
+  /// Get a page of tips for an Account and a [`VerificationContext`]. Every stored tip is
+  /// identified by a sequence number, increasing with the insertion order, that is used as a
+  /// stable cursor: new tips do not shift the pages already read
+  ///
+  /// # Arguments
+  ///  * account_id - The account to query
+  ///  * context - The [`VerificationContext`] to query
+  ///  * sort - The order of the tips on the page
+  ///  * filter - The [`TipsFilter`] the tips on the page must satisfy
+  ///  * cursor - The sequence number of the first Tip on the page. Default is the first tip in
+  ///    the requested order
+  ///  * limit - The maximum count of Tips on the page
+  ///
+  /// # Return
+  /// A [`TipsPage`] containing the tips and the cursor of the next page, if any
+  pub fn get_tips_page(
+    account_id: T::AccountId,
+    context: VerificationContext,
+    sort: SortTips,
+    filter: TipsFilter<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+    cursor: Option<u64>,
+    limit: u16,
+  ) -> TipsPage<Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>> {
+    let tips = TipsByAccountIdAndVerificationContext::<T>::get(&account_id, &context);
+    let count = TipsAggregateByAccountIdAndVerificationContext::<T>::get(&account_id, &context).count;
+    let first_sequence = count.saturating_sub(tips.len() as u64);
+
+    let mut sequenced: Vec<(u64, Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>)> = tips
+      .into_iter()
+      .enumerate()
+      .map(|(index, tip)| (first_sequence.saturating_add(index as u64), tip))
+      .filter(|(sequence, tip)| {
+        let after_cursor = match (sort, cursor) {
+          (_, None) => true,
+          (SortTips::Asc, Some(cursor)) => *sequence >= cursor,
+          (SortTips::Desc, Some(cursor)) => *sequence <= cursor,
+        };
+        after_cursor && filter.matches(tip)
+      })
+      .collect();
+    if sort == SortTips::Desc {
+      sequenced.reverse();
+    }
+
+    let limit = limit as usize;
+    let next_cursor = sequenced.get(limit).map(|(sequence, _)| *sequence);
+    let tips = sequenced.into_iter().take(limit).map(|(_, tip)| tip).collect();
+
+    TipsPage { tips, next_cursor }
+  }
+
+  /// Get a page of tips given by a sender to any [`VerificationContext`], ordered by creation
+  /// time. Every tip is identified by its creation time along with its sequence for the sender,
+  /// that is used as a stable cursor: new tips, even to other contexts, do not shift the pages
+  /// already read
+  ///
+  /// # Arguments
+  ///  * sender - The sender to query
+  ///  * sort - The order of the tips on the page, by creation time
+  ///  * cursor - The identifier of the first Tip on the page. Default is the first tip in the
+  ///    requested order
+  ///  * limit - The maximum count of Tips on the page
+  ///
+  /// # Return
+  /// A [`TipsPage`] containing the [`SentTip`]s and the cursor of the next page, if any
+  pub fn get_tips_by_sender(
+    sender: T::AccountId,
+    sort: SortTips,
+    cursor: Option<u64>,
+    limit: u16,
+  ) -> TipsPage<SentTip<BalanceOf<T>, T::AccountId, T::BlockNumber>> {
+    let mut sequenced: Vec<(u64, SentTip<BalanceOf<T>, T::AccountId, T::BlockNumber>)> =
+      TipsBySenderAndVerificationContext::<T>::iter_prefix(&sender)
+        .flat_map(|(context, tips)| {
+          let sequences = SentTipSequencesBySenderAndVerificationContext::<T>::get(&sender, &context);
+          tips.into_iter().zip(sequences.into_iter()).map(move |(tip, sequence)| {
+            let sent_tip_id = (tip.created_at << SENT_TIP_CURSOR_TIME_SHIFT) | u64::from(sequence);
+            (
+              sent_tip_id,
+              SentTip {
+                context: context.clone(),
+                tip,
+              },
+            )
+          })
+        })
+        .filter(|(sent_tip_id, _)| match (sort, cursor) {
+          (_, None) => true,
+          (SortTips::Asc, Some(cursor)) => *sent_tip_id >= cursor,
+          (SortTips::Desc, Some(cursor)) => *sent_tip_id <= cursor,
+        })
+        .collect();
+    sequenced.sort_by_key(|(sent_tip_id, _)| *sent_tip_id);
+    if sort == SortTips::Desc {
+      sequenced.reverse();
+    }
+
+    let limit = limit as usize;
+    let next_cursor = sequenced.get(limit).map(|(sent_tip_id, _)| *sent_tip_id);
+    let tips = sequenced
+      .into_iter()
+      .take(limit)
+      .map(|(_, sent_tip)| sent_tip)
+      .collect();
+
+    TipsPage { tips, next_cursor }
+  }
+
+  /// Get the supporters of a [`VerificationContext`] that tipped the highest total amount
+  ///
+  /// # Arguments
+  ///  * holder - The holder of a successful [`VerificationRequest`] for the verification context
+  ///  * verification_context - The [`VerificationContext`] to query
+  ///  * count - The maximum count of supporters to return
+  ///
+  /// # Return
+  /// Collection of [`Supporter`] sorted by the total amount tipped DESC
+  pub fn top_supporters(
+    holder: T::AccountId,
+    verification_context: VerificationContext,
+    count: u16,
+  ) -> Vec<Supporter<BalanceOf<T>, T::AccountId, T::BlockNumber>> {
+    let mut supporters: Vec<Supporter<BalanceOf<T>, T::AccountId, T::BlockNumber>> =
+      TipsAggregateByAccountIdAndVerificationContextAndSender::<T>::iter_prefix((holder, verification_context))
+        .map(|(account, tips)| Supporter { account, tips })
+        .collect();
+    supporters.sort_by(|a, b| b.tips.total_received.cmp(&a.tips.total_received));
+    supporters.truncate(count as usize);
 
-    page
+    supporters
   }
 }
@@ -21,6 +21,7 @@
 
 mod benchmarking;
 mod functions;
+pub mod migration;
 pub mod types;
 pub mod weights;
 
@@ -28,12 +29,16 @@ pub mod weights;
 mod mock;
 #[cfg(test)]
 mod tests;
+#[cfg(test)]
+mod xcm_mock;
 
 pub use pallet::*;
 
 pub mod constants {
   use anagolay_support::getter_for_constant;
   getter_for_constant!(MaxTipsPerVerificationContext, u32);
+  getter_for_constant!(MaxEscrowedTipsPerVerificationContext, u32);
+  getter_for_constant!(MaxPendingTipsPerBlock, u32);
 }
 
 #[frame_support::pallet]
@@ -43,7 +48,12 @@ pub mod pallet {
   use frame_support::{
     pallet_prelude::*,
     sp_std::{vec::*, *},
-    traits::{Currency, ReservableCurrency},
+    traits::{Currency, OnUnbalanced, ReservableCurrency},
+    PalletId,
+  };
+  use sp_runtime::{
+    traits::{One, Saturating, Zero},
+    Permill,
   };
   use verification::types::{VerificationContext, VerificationStatus};
 
@@ -51,9 +61,18 @@ pub mod pallet {
   use frame_system::pallet_prelude::*;
 
   pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+  pub type NegativeImbalanceOf<T> =
+    <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::NegativeImbalance;
+  pub type SupporterBadgeCollectionIdOf<T> =
+    <<T as Config>::SupporterBadgeMinter as SupporterBadgeMinter<T>>::CollectionId;
+  pub type SupporterBadgeItemIdOf<T> = <<T as Config>::SupporterBadgeMinter as SupporterBadgeMinter<T>>::ItemId;
+
+  /// The current storage version
+  pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(5);
 
   #[pallet::pallet]
   #[pallet::generate_store(pub(super) trait Store)]
+  #[pallet::storage_version(STORAGE_VERSION)]
   pub struct Pallet<T>(_);
 
   #[pallet::config]
@@ -67,12 +86,68 @@ pub mod pallet {
     /// Timestamps provider
     type TimeProvider: UnixTime;
 
+    /// Handler for the protocol fee deducted from the tips, e.g. the treasury
+    type ProtocolFeeHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+    /// Minter of the supporter badges, [`NaiveSupporterBadgeMinter`] if they are not supported
+    type SupporterBadgeMinter: SupporterBadgeMinter<Self>;
+
+    /// The identifier of the first collection of supporter badges, the following ones are
+    /// allocated in sequence. The runtime reserves them to the pallet, so that they cannot be
+    /// taken by other collections
+    type FirstSupporterBadgeCollectionId: Get<SupporterBadgeCollectionIdOf<Self>>;
+
+    /// Location of a tipper on another consensus system, e.g. an XCM `MultiLocation`
+    type RemoteLocation: Member + Parameter + MaxEncodedLen;
+
+    /// The origin allowed to tip from another consensus system, e.g. an XCM `Transact` from the
+    /// relay chain or a sibling parachain. It resolves to the location of the tipper and to the
+    /// local account that funds the tip
+    type RemoteTipOrigin: EnsureOrigin<
+      <Self as frame_system::Config>::RuntimeOrigin,
+      Success = (Self::RemoteLocation, Self::AccountId),
+    >;
+
+    /// The origin allowed to create the quadratic funding matching rounds
+    type MatchingRoundOrigin: EnsureOrigin<<Self as frame_system::Config>::RuntimeOrigin>;
+
+    /// Filter of the senders whose tips are accounted in the matching rounds, e.g.
+    /// [`VerifiedMatchingContributorFilter`] to count only the verified senders
+    type MatchingContributorFilter: MatchingContributorFilter<Self>;
+
     /// Weight information for extrinsics for this pallet.
     type WeightInfo: WeightInfo;
 
     /// Maximum number of Tips recorded for a single VerificationContext. Once reached, old tips
     /// will be discarded to record the new tips
     const MAX_TIPS_PER_VERIFICATION_CONTEXT: u32;
+
+    /// The amount charged to the sender for each byte of the memo attached to a tip
+    const MEMO_FEE_PER_BYTE: BalanceOf<Self>;
+
+    /// The tipping pallet id, used to derive the account that holds the escrowed tips
+    const PALLET_ID: PalletId;
+
+    /// Number of blocks after which an escrowed tip can no longer be claimed and is refunded
+    const ESCROW_TIMEOUT: BlockNumberFor<Self>;
+
+    /// Maximum number of escrowed tips recorded for a single VerificationContext
+    const MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT: u32;
+
+    /// The portion of every tip that is deducted as protocol fee and handed over to the
+    /// `ProtocolFeeHandler`
+    const PROTOCOL_FEE: Permill;
+
+    /// Maximum number of verification contexts that take part in a single matching round. Once
+    /// reached, the tips to further contexts are no longer accounted in the round
+    const MAX_CONTEXTS_PER_MATCHING_ROUND: u32;
+
+    /// Maximum number of blocks a receiver can hold the tips for, before they settle
+    const MAX_REFUND_WINDOW: BlockNumberFor<Self>;
+
+    /// Maximum number of pending tips that settle in a single block. Once reached, the tips
+    /// that would settle in the same block are rejected
+    const MAX_PENDING_TIPS_PER_BLOCK: u32;
   }
 
   #[pallet::extra_constants]
@@ -81,15 +156,87 @@ pub mod pallet {
     fn max_tips_per_verification_context() -> u32 {
       T::MAX_TIPS_PER_VERIFICATION_CONTEXT
     }
+    /// The amount charged to the sender for each byte of the memo attached to a tip. The fee is
+    /// burned: the higher the amount, the less convenient it is to use memos to bloat the storage
+    #[pallet::constant_name(MemoFeePerByte)]
+    fn memo_fee_per_byte() -> BalanceOf<T> {
+      T::MEMO_FEE_PER_BYTE
+    }
+    /// Number of blocks after which an escrowed tip that was not claimed by the holder of a
+    /// successful verification of its context is refunded to the sender
+    #[pallet::constant_name(EscrowTimeout)]
+    fn escrow_timeout() -> BlockNumberFor<T> {
+      T::ESCROW_TIMEOUT
+    }
+    #[pallet::constant_name(MaxEscrowedTipsPerVerificationContext)]
+    fn max_escrowed_tips_per_verification_context() -> u32 {
+      T::MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT
+    }
+    /// The portion of every tip that is deducted as protocol fee to fund the verification
+    /// infrastructure
+    #[pallet::constant_name(ProtocolFee)]
+    fn protocol_fee() -> Permill {
+      T::PROTOCOL_FEE
+    }
+    #[pallet::constant_name(MaxContextsPerMatchingRound)]
+    fn max_contexts_per_matching_round() -> u32 {
+      T::MAX_CONTEXTS_PER_MATCHING_ROUND
+    }
+    /// Maximum number of blocks a receiver can hold the tips for, during which the senders can
+    /// cancel them
+    #[pallet::constant_name(MaxRefundWindow)]
+    fn max_refund_window() -> BlockNumberFor<T> {
+      T::MAX_REFUND_WINDOW
+    }
+    #[pallet::constant_name(MaxPendingTipsPerBlock)]
+    fn max_pending_tips_per_block() -> u32 {
+      T::MAX_PENDING_TIPS_PER_BLOCK
+    }
   }
 
   #[pallet::hooks]
   impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+    fn on_initialize(block_number: BlockNumberFor<T>) -> Weight {
+      // The storage migration resumes from where the previous block left it. The pending tips are
+      // not settled meanwhile, the blocks skipped are settled once the migration completes
+      if crate::migration::is_migrating::<T>() {
+        if !PendingTipsSettledUntil::<T>::exists() {
+          PendingTipsSettledUntil::<T>::put(block_number.saturating_sub(One::one()));
+        }
+        return crate::migration::migrate::<T>().saturating_add(T::DbWeight::get().reads_writes(1, 1));
+      }
+      T::DbWeight::get()
+        .reads(1)
+        .saturating_add(Self::do_settle_pending_tips(block_number))
+    }
+
+    fn on_runtime_upgrade() -> Weight {
+      crate::migration::migrate::<T>()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn pre_upgrade() -> Result<Vec<u8>, &'static str> {
+      crate::migration::pre_upgrade::<T>()
+    }
+
+    #[cfg(feature = "try-runtime")]
+    fn post_upgrade(state: Vec<u8>) -> Result<(), &'static str> {
+      crate::migration::post_upgrade::<T>(state)
+    }
+
     fn integrity_test() {
       assert!(
         T::MAX_TIPS_PER_VERIFICATION_CONTEXT > 0u32,
         "`MaxTipsPerVerificationContext` must be greater than 0"
       );
+      assert!(
+        T::MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT > 0u32,
+        "`MaxEscrowedTipsPerVerificationContext` must be greater than 0"
+      );
+      assert!(
+        T::MAX_PENDING_TIPS_PER_BLOCK > 0u32,
+        "`MaxPendingTipsPerBlock` must be greater than 0"
+      );
     }
   }
 
@@ -120,6 +267,191 @@ pub mod pallet {
     ValueQuery,
   >;
 
+  /// The map of collection of Tips indexed by their respective sender AccountId and
+  /// VerificationContext
+  #[pallet::storage]
+  #[pallet::getter(fn tips_by_sender_and_verification_context)]
+  pub type TipsBySenderAndVerificationContext<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Twox64Concat,
+    VerificationContext,
+    BoundedVec<Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>, MaxTipsPerVerificationContextGet<T>>,
+    ValueQuery,
+  >;
+
+  /// The map of the count of Tips ever indexed for a sender, used as the sequence of the next Tip
+  /// of the sender
+  #[pallet::storage]
+  #[pallet::getter(fn sent_tips_count_by_sender)]
+  pub type SentTipsCountBySender<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, u32, ValueQuery>;
+
+  /// The map of the sequences of the Tips in `TipsBySenderAndVerificationContext`, in the same
+  /// order, indexed by their respective sender AccountId and VerificationContext. The sequence tells
+  /// apart the tips of a sender created at the same time
+  #[pallet::storage]
+  #[pallet::getter(fn sent_tip_sequences_by_sender_and_verification_context)]
+  pub type SentTipSequencesBySenderAndVerificationContext<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Twox64Concat,
+    VerificationContext,
+    BoundedVec<u32, MaxTipsPerVerificationContextGet<T>>,
+    ValueQuery,
+  >;
+
+  /// The map of the running aggregates of the Tips indexed by their respective receiver AccountId
+  /// and VerificationContext
+  #[pallet::storage]
+  #[pallet::getter(fn tips_aggregate_by_account_id_and_verification_context)]
+  pub type TipsAggregateByAccountIdAndVerificationContext<T: Config> = StorageDoubleMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    Twox64Concat,
+    VerificationContext,
+    TipsAggregate<BalanceOf<T>, T::BlockNumber>,
+    ValueQuery,
+  >;
+
+  /// The map of the running aggregates of the Tips indexed by their respective receiver AccountId,
+  /// VerificationContext and sender AccountId
+  #[pallet::storage]
+  #[pallet::getter(fn tips_aggregate_by_account_id_and_verification_context_and_sender)]
+  pub type TipsAggregateByAccountIdAndVerificationContextAndSender<T: Config> = StorageNMap<
+    _,
+    (
+      NMapKey<Blake2_128Concat, T::AccountId>,
+      NMapKey<Twox64Concat, VerificationContext>,
+      NMapKey<Blake2_128Concat, T::AccountId>,
+    ),
+    TipsAggregate<BalanceOf<T>, T::BlockNumber>,
+    ValueQuery,
+  >;
+
+  /// The map of collection of escrowed Tips indexed by the VerificationContext they are addressed
+  /// to
+  #[pallet::storage]
+  #[pallet::getter(fn escrowed_tips_by_verification_context)]
+  pub type EscrowedTipsByVerificationContext<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    VerificationContext,
+    BoundedVec<EscrowedTip<BalanceOf<T>, T::AccountId, T::BlockNumber>, MaxEscrowedTipsPerVerificationContextGet<T>>,
+    ValueQuery,
+  >;
+
+  /// The map of the minimum tip amounts that earn a supporter badge, indexed by their respective
+  /// holder AccountId and VerificationContext
+  #[pallet::storage]
+  #[pallet::getter(fn supporter_badge_threshold_by_account_id_and_verification_context)]
+  pub type SupporterBadgeThresholdByAccountIdAndVerificationContext<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, VerificationContext, BalanceOf<T>, OptionQuery>;
+
+  /// The map of the collections of supporter badges indexed by the AccountId of their creator
+  #[pallet::storage]
+  #[pallet::getter(fn supporter_badge_collection_by_account_id)]
+  pub type SupporterBadgeCollectionByAccountId<T: Config> = StorageMap<
+    _,
+    Blake2_128Concat,
+    T::AccountId,
+    SupporterBadgeCollection<SupporterBadgeCollectionIdOf<T>, SupporterBadgeItemIdOf<T>>,
+    OptionQuery,
+  >;
+
+  /// The identifier of the next collection of supporter badges
+  #[pallet::storage]
+  #[pallet::getter(fn next_supporter_badge_collection_id)]
+  pub type NextSupporterBadgeCollectionId<T: Config> =
+    StorageValue<_, SupporterBadgeCollectionIdOf<T>, ValueQuery, T::FirstSupporterBadgeCollectionId>;
+
+  /// The identifier of the next matching round
+  #[pallet::storage]
+  #[pallet::getter(fn next_matching_round_id)]
+  pub type NextMatchingRoundId<T: Config> = StorageValue<_, MatchingRoundId, ValueQuery>;
+
+  /// The matching round that is not finalized yet, if any
+  #[pallet::storage]
+  #[pallet::getter(fn current_matching_round)]
+  pub type CurrentMatchingRound<T: Config> = StorageValue<_, MatchingRoundId, OptionQuery>;
+
+  /// Retrieve the [`MatchingRound`] by its identifier
+  #[pallet::storage]
+  #[pallet::getter(fn matching_round)]
+  pub type MatchingRounds<T: Config> =
+    StorageMap<_, Twox64Concat, MatchingRoundId, MatchingRound<BalanceOf<T>, T::BlockNumber>, OptionQuery>;
+
+  /// Retrieve the [`MatchingRoundTally`] of a VerificationContext in a matching round
+  #[pallet::storage]
+  #[pallet::getter(fn matching_round_tally)]
+  pub type MatchingRoundTallies<T: Config> = StorageDoubleMap<
+    _,
+    Twox64Concat,
+    MatchingRoundId,
+    Twox64Concat,
+    VerificationContext,
+    MatchingRoundTally<BalanceOf<T>>,
+    OptionQuery,
+  >;
+
+  /// Retrieve the total contributed by a sender to a VerificationContext in a matching round
+  #[pallet::storage]
+  #[pallet::getter(fn matching_round_contribution)]
+  pub type MatchingRoundContributions<T: Config> = StorageNMap<
+    _,
+    (
+      NMapKey<Twox64Concat, MatchingRoundId>,
+      NMapKey<Twox64Concat, VerificationContext>,
+      NMapKey<Blake2_128Concat, T::AccountId>,
+    ),
+    BalanceOf<T>,
+    ValueQuery,
+  >;
+
+  /// The map of the number of blocks the tips are held for before they settle, indexed by their
+  /// respective holder AccountId and VerificationContext
+  #[pallet::storage]
+  #[pallet::getter(fn refund_window_by_account_id_and_verification_context)]
+  pub type RefundWindowByAccountIdAndVerificationContext<T: Config> =
+    StorageDoubleMap<_, Blake2_128Concat, T::AccountId, Twox64Concat, VerificationContext, T::BlockNumber, OptionQuery>;
+
+  /// The identifier of the next pending tip
+  #[pallet::storage]
+  #[pallet::getter(fn next_pending_tip_id)]
+  pub type NextPendingTipId<T: Config> = StorageValue<_, PendingTipId, ValueQuery>;
+
+  /// Retrieve the [`PendingTip`] by its identifier, until it settles or it is cancelled
+  #[pallet::storage]
+  #[pallet::getter(fn pending_tip)]
+  pub type PendingTips<T: Config> =
+    StorageMap<_, Twox64Concat, PendingTipId, PendingTip<BalanceOf<T>, T::AccountId, T::BlockNumber>, OptionQuery>;
+
+  /// The map of the identifiers of the pending tips indexed by the block where they settle
+  #[pallet::storage]
+  #[pallet::getter(fn pending_tips_by_settlement_block)]
+  pub type PendingTipsBySettlementBlock<T: Config> =
+    StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<PendingTipId, MaxPendingTipsPerBlockGet<T>>, ValueQuery>;
+
+  /// The last block whose pending tips were settled
+  #[pallet::storage]
+  #[pallet::getter(fn pending_tips_settled_until)]
+  pub type PendingTipsSettledUntil<T: Config> = StorageValue<_, T::BlockNumber, OptionQuery>;
+
+  /// The map of the locations of the remote tippers on their consensus system, indexed by their
+  /// local AccountId, which is the sender of their tips
+  #[pallet::storage]
+  #[pallet::getter(fn remote_location_by_account_id)]
+  pub type RemoteLocationByAccountId<T: Config> =
+    StorageMap<_, Blake2_128Concat, T::AccountId, T::RemoteLocation, OptionQuery>;
+
+  /// The progress of the storage migration, when it did not complete within the block where it
+  /// started
+  #[pallet::storage]
+  #[pallet::getter(fn ongoing_migration)]
+  pub type OngoingMigration<T: Config> = StorageValue<_, MigrationCursor, OptionQuery>;
+
   #[pallet::error]
   pub enum Error<T> {
     /// The verification context is not associated to a successful verification request and cannot
@@ -127,143 +459,779 @@ pub mod pallet {
     InvalidVerificationContext,
     /// The verification context is not set-up to enable tipping
     InvalidConfiguration,
+    /// The verification context is already available for tipping, the tip does not need to be
+    /// escrowed
+    EscrowNotRequired,
+    /// There are already a number of escrowed tips for the same context and no more will be
+    /// accepted
+    MaxEscrowedTipsPerVerificationContextLimitReached,
+    /// The caller is not the holder of the successful verification of the context
+    NotVerificationHolder,
+    /// There are no tipping settings for the verification context
+    TippingSettingsNotFound,
+    /// A matching round is already in progress and must be finalized before creating a new one
+    MatchingRoundInProgress,
+    /// The start of the matching round must precede its end, which cannot be in the past
+    InvalidMatchingRound,
+    /// The matching round does not exist
+    MatchingRoundNotFound,
+    /// The matching round is already finalized
+    MatchingRoundAlreadyFinalized,
+    /// The matching round cannot be finalized before its end
+    MatchingRoundNotEnded,
+    /// The refund window must be greater than zero and cannot exceed `MaxRefundWindow`
+    InvalidRefundWindow,
+    /// There are already a number of pending tips settling in the same block and no more will be
+    /// accepted
+    MaxPendingTipsPerBlockLimitReached,
+    /// The pending tip does not exist, it may be already settled
+    PendingTipNotFound,
+    /// The caller is not the sender of the tip
+    NotTipSender,
+    /// The storage is being migrated, the calls are accepted again once the migration completes
+    StorageMigrationInProgress,
   }
 
   /// Events of the Poe pallet
   #[pallet::event]
   #[pallet::generate_deposit(pub(crate) fn deposit_event)]
   pub enum Event<T: Config> {
-    /// Produced upon settings update
-    TippingSettingsUpdated(T::AccountId, Vec<TippingSettings<T::AccountId>>),
+    /// Produced upon settings update, reporting the outcome of each of the submitted settings
+    TippingSettingsUpdated(
+      T::AccountId,
+      Vec<(TippingSettings<T::AccountId>, TippingSettingsOutcome)>,
+    ),
+    /// Produced when the settings of a verification context are removed, either by the holder or
+    /// because the verification was invalidated
+    TippingSettingsRemoved(T::AccountId, VerificationContext),
     /// Produced upon the newly created tip
     TipCreated(
       T::AccountId,
       T::AccountId,
       Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
     ),
+    /// Produced upon the newly created tip sent from another consensus system, providing the
+    /// location of the tipper
+    RemoteTipCreated(
+      T::RemoteLocation,
+      T::AccountId,
+      Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+    ),
+    /// Produced upon the newly escrowed tip
+    TipEscrowed(
+      T::AccountId,
+      VerificationContext,
+      EscrowedTip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+    ),
+    /// Produced when the holder claims the escrowed tips, providing the total amount claimed
+    EscrowedTipsClaimed(T::AccountId, VerificationContext, BalanceOf<T>),
+    /// Produced when an expired escrowed tip is refunded to the sender
+    EscrowedTipRefunded(T::AccountId, VerificationContext, BalanceOf<T>),
+    /// Produced when the holder updates the minimum tip amount that earns a supporter badge
+    SupporterBadgeUpdated(T::AccountId, VerificationContext, Option<BalanceOf<T>>),
+    /// Produced when a supporter badge is minted into the account of the sender of a tip,
+    /// providing the creator, the context, the collection and the item of the badge
+    SupporterBadgeMinted(
+      T::AccountId,
+      T::AccountId,
+      VerificationContext,
+      SupporterBadgeCollectionIdOf<T>,
+      SupporterBadgeItemIdOf<T>,
+    ),
+    /// Produced upon the creation of a matching round, providing its start and end blocks
+    MatchingRoundCreated(MatchingRoundId, T::BlockNumber, T::BlockNumber),
+    /// Produced when a donor adds funds to a matching round
+    MatchingRoundFunded(MatchingRoundId, T::AccountId, BalanceOf<T>),
+    /// Produced for each verification context that receives matching funds upon the
+    /// finalization of a round, providing the holder and the amount
+    MatchingFundsDistributed(MatchingRoundId, VerificationContext, T::AccountId, BalanceOf<T>),
+    /// Produced upon the finalization of a matching round, providing the amount distributed
+    MatchingRoundFinalized(MatchingRoundId, BalanceOf<T>),
+    /// Produced when the holder updates the number of blocks the tips are held for
+    RefundWindowUpdated(T::AccountId, VerificationContext, Option<T::BlockNumber>),
+    /// Produced when a tip is held in the refund window of the receiver, providing the identifier
+    /// that allows the sender to cancel it and the block where it settles
+    TipPending(PendingTipId, T::AccountId, T::BlockNumber),
+    /// Produced when a pending tip settles, providing the holder and the context
+    TipSettled(
+      PendingTipId,
+      T::AccountId,
+      VerificationContext,
+      Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+    ),
+    /// Produced when the sender cancels a pending tip, providing the amount released
+    TipRefunded(PendingTipId, T::AccountId, VerificationContext, BalanceOf<T>),
     /// This event is never raised: chain metadata does not include types used only in RPCs so as
     /// workaround we need to include it here
-    /// also this is NEVER USED, we had massive issues with the deserialization of the enum on the
-    /// JS side. Keeping this for now, to test the regressions
-    __TippingLookupTypes(SortTips),
+    __TippingLookupTypes(SortTips, TipsFilter<BalanceOf<T>, T::AccountId, T::BlockNumber>),
   }
 
   #[pallet::call]
   impl<T: Config> Pallet<T> {
     /// Accepts a collection of [`TippingSettings`] and stores it. A coherency check is performed
-    /// on the rightfulness of the caller to configure each setting
+    /// on the rightfulness of the caller to configure each setting and the outcome of every
+    /// setting is reported in the event, so that the settings which were not applied can be told
+    /// apart
     ///
     /// # Arguments
     /// * origin - the call origin
     /// * tipping_settings - the [`TippingSettings`]
     ///
+    /// # Errors
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
     /// # Events
-    /// * `SettingsUpdated` - when the [`TippingSettings`] is successfully updated
+    /// * `TippingSettingsUpdated` - with the [`TippingSettingsOutcome`] of each of the settings
     ///
     /// # Return
     /// `DispatchResultWithPostInfo` containing Unit type
-    #[pallet::weight(<T as Config>::WeightInfo::update_settings())]
+    #[pallet::weight(<T as Config>::WeightInfo::update_settings(tipping_settings.len() as u32))]
     pub fn update_settings(
       origin: OriginFor<T>,
       tipping_settings: Vec<TippingSettings<T::AccountId>>,
     ) -> DispatchResultWithPostInfo {
       let caller = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
 
-      let mut updated_settings: Vec<TippingSettings<T::AccountId>> = Vec::new();
+      let escrow_account_id = Self::escrow_account_id();
 
-      // Iterate through each setting and insert into the storage
-      let mut tipping_settings = tipping_settings;
-      tipping_settings.iter_mut().for_each(|setting| {
-        // Allow only to the verification holder to update the tipping settings
-        if let Some(holder) = verification::Pallet::<T>::account_ids_by_verification_context(setting.context.clone())
-          .iter()
-          .find(|account| caller == **account)
-        {
-          // Avoid misconfigured tipping settings - default to holder if no account is configured
-          let _ = setting.account.get_or_insert(holder.clone());
+      // Iterate through each setting, insert it into the storage and keep track of the outcome
+      let outcomes: Vec<(TippingSettings<T::AccountId>, TippingSettingsOutcome)> = tipping_settings
+        .into_iter()
+        .map(|mut setting| {
+          // Allow only to the verification holder to update the tipping settings
+          if !verification::Pallet::<T>::account_ids_by_verification_context(setting.context.clone()).contains(&caller)
+          {
+            return (setting, TippingSettingsOutcome::NotHolder);
+          }
 
           // Allow the caller to update the settings only if the status of the verification request related to
           // the context is success
-          if let Some(request) = verification::Pallet::<T>::verification_request_by_account_id_and_verification_context(
-            holder.clone(),
+          let verified = verification::Pallet::<T>::verification_request_by_account_id_and_verification_context(
+            caller.clone(),
             setting.context.clone(),
-          ) {
-            if request.status == VerificationStatus::Success {
-              TippingSettingsByAccountIdAndVerificationContext::<T>::insert(
-                caller.clone(),
-                setting.context.clone(),
-                setting.clone(),
-              );
-
-              updated_settings.push(setting.clone());
-            }
+          )
+          .map_or(false, |request| request.status == VerificationStatus::Success);
+          if !verified {
+            return (setting, TippingSettingsOutcome::NotVerified);
           }
-        }
-      });
 
-      // Emit an event that tells which settings have been updated
-      Self::deposit_event(Event::TippingSettingsUpdated(caller, updated_settings));
+          // Avoid misconfigured tipping settings - default to holder if no account is configured
+          let account = setting.account.get_or_insert(caller.clone());
+          if *account == escrow_account_id {
+            return (setting, TippingSettingsOutcome::InvalidAccount);
+          }
+
+          TippingSettingsByAccountIdAndVerificationContext::<T>::insert(
+            caller.clone(),
+            setting.context.clone(),
+            setting.clone(),
+          );
+          (setting, TippingSettingsOutcome::Applied)
+        })
+        .collect();
+
+      // Emit an event that tells the outcome of each setting
+      Self::deposit_event(Event::TippingSettingsUpdated(caller, outcomes));
+
+      Ok(().into())
+    }
+
+    /// Removes the [`TippingSettings`] of a [`VerificationContext`], disabling the tipping. The
+    /// supporter badge configuration is removed as well, while the tips already received are kept
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * context - the [`VerificationContext`]
+    ///
+    /// # Errors
+    /// * `TippingSettingsNotFound` - If the caller has no settings for the context
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `TippingSettingsRemoved` - when the [`TippingSettings`] are successfully removed
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::remove_settings())]
+    pub fn remove_settings(origin: OriginFor<T>, context: VerificationContext) -> DispatchResultWithPostInfo {
+      let caller = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      ensure!(
+        TippingSettingsByAccountIdAndVerificationContext::<T>::contains_key(&caller, &context),
+        Error::<T>::TippingSettingsNotFound
+      );
+      Self::do_remove_settings(&caller, &context);
 
       Ok(().into())
     }
 
     /// Accepts a [`Tip`] for a [`VerificationContext`] and stores them in the
     /// `TipsByAccountIdAndVerificationContext` while it transfers of the required amount from
-    /// the account of the sender to the account of the receiver.
+    /// the account of the sender to the account of the receiver. The `ProtocolFee` is deducted
+    /// from the amount and handed over to the `ProtocolFeeHandler`. If the holder configured a
+    /// refund window, the amount is held as reserved balance of the sender instead, and the tip
+    /// settles once the window is over unless the sender cancels it.
     ///
     /// # Arguments
     /// * origin - the call origin
-    /// * tip - the [`Tip`]
+    /// * amount - the amount to tip
     /// * context - the [`VerificationContext`]
+    /// * memo - an optional message for the receiver, charged `MemoFeePerByte` for each byte
     ///
     /// # Errors
     /// * `InvalidVerificationContext` - If the [`VerificationContext`] is not available for tipping
     /// * `InvalidConfiguration` - If tipping is disabled or not configured for the context
+    /// * `MaxPendingTipsPerBlockLimitReached` - If the tip would be held but the maximum number of
+    ///   pending tips settling in the same block has already been reached
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
     ///
     /// # Events
     /// * `TipCreated` - when the [`Tip`] is successfully created
+    /// * `TipPending` - when the [`Tip`] is held in the refund window of the holder
     ///
     /// # Return
     /// `DispatchResultWithPostInfo` containing Unit type
-    #[pallet::weight(<T as Config>::WeightInfo::tip())]
-    pub fn tip(origin: OriginFor<T>, amount: BalanceOf<T>, context: VerificationContext) -> DispatchResultWithPostInfo {
+    #[pallet::weight(<T as Config>::WeightInfo::tip(memo.as_ref().map_or(0, |memo| memo.len() as u32))
+      .saturating_add(T::DbWeight::get().reads(T::MatchingContributorFilter::MAX_READS)))]
+    pub fn tip(
+      origin: OriginFor<T>,
+      amount: BalanceOf<T>,
+      context: VerificationContext,
+      memo: Option<Memo>,
+    ) -> DispatchResultWithPostInfo {
       let tipper = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let (tipped, tip) = Self::do_tip(&tipper, amount, &context, memo)?;
+
+      // Emit an event that the tip has been created
+      Self::deposit_event(Event::TipCreated(tipper, tipped.clone(), tip.clone()));
+
+      // Pending tips earn the badge once settled
+      if tip.state == TipSettlement::Settled {
+        Self::do_award_supporter_badge(&tipped, &context, &tip);
+      }
+
+      Ok(().into())
+    }
+
+    /// Accepts a [`Tip`] for a [`VerificationContext`] from another consensus system, for instance
+    /// sent by a user of the relay chain or of a sibling parachain with an XCM `Transact`. The tip
+    /// is funded by the local account of the remote tipper, which is recorded as sender of the
+    /// [`Tip`], while the location of the remote tipper is recorded in `RemoteLocationByAccountId`
+    /// and provided in the event. The refund window of the holder applies as for the local tips.
+    ///
+    /// # Arguments
+    /// * origin - the call origin, resolved by the `RemoteTipOrigin`
+    /// * amount - the amount to tip
+    /// * context - the [`VerificationContext`]
+    /// * memo - an optional message for the receiver, charged `MemoFeePerByte` for each byte
+    ///
+    /// # Errors
+    /// * `BadOrigin` - If the origin is not accepted by the `RemoteTipOrigin`
+    /// * `InvalidVerificationContext` - If the [`VerificationContext`] is not available for tipping
+    /// * `InvalidConfiguration` - If tipping is disabled or not configured for the context
+    /// * `MaxPendingTipsPerBlockLimitReached` - If the tip would be held but the maximum number of
+    ///   pending tips settling in the same block has already been reached
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `RemoteTipCreated` - when the [`Tip`] is successfully created
+    /// * `TipPending` - when the [`Tip`] is held in the refund window of the holder
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::tip_remote(memo.as_ref().map_or(0, |memo| memo.len() as u32))
+      .saturating_add(T::DbWeight::get().reads(T::MatchingContributorFilter::MAX_READS)))]
+    pub fn tip_remote(
+      origin: OriginFor<T>,
+      amount: BalanceOf<T>,
+      context: VerificationContext,
+      memo: Option<Memo>,
+    ) -> DispatchResultWithPostInfo {
+      let (location, tipper) = T::RemoteTipOrigin::ensure_origin(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let (tipped, tip) = Self::do_tip(&tipper, amount, &context, memo)?;
+      RemoteLocationByAccountId::<T>::insert(&tipper, location.clone());
+
+      // Emit an event that the tip has been created
+      Self::deposit_event(Event::RemoteTipCreated(location, tipped.clone(), tip.clone()));
+
+      // Pending tips earn the badge once settled
+      if tip.state == TipSettlement::Settled {
+        Self::do_award_supporter_badge(&tipped, &context, &tip);
+      }
+
+      Ok(().into())
+    }
 
-      // Retrieve the successful verification request associated to the context
-      let requests =
-        verification::Pallet::<T>::get_requests(vec![context.clone()], Some(VerificationStatus::Success), None, 0, 1);
-      ensure!(requests.len() == 1, Error::<T>::InvalidVerificationContext);
-      let tipped = requests.first().unwrap().holder.clone();
+    /// Accepts a tip for a [`VerificationContext`] that is not available for tipping yet. The
+    /// amount is transferred to the escrow account of the pallet and the [`EscrowedTip`] is
+    /// stored in `EscrowedTipsByVerificationContext` until the holder of a successful
+    /// verification of the context claims it, or until it expires and is refunded to the sender.
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * amount - the amount to tip
+    /// * context - the [`VerificationContext`]
+    /// * memo - an optional message for the receiver, charged `MemoFeePerByte` for each byte
+    ///
+    /// # Errors
+    /// * `InvalidVerificationContext` - If the [`VerificationContext`] is `Unbounded`
+    /// * `EscrowNotRequired` - If the [`VerificationContext`] is already available for tipping
+    /// * `MaxEscrowedTipsPerVerificationContextLimitReached` - If the maximum number of escrowed
+    ///   tips has already been reached for this context
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `TipEscrowed` - when the [`EscrowedTip`] is successfully created
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::tip_escrow(memo.as_ref().map_or(0, |memo| memo.len() as u32)))]
+    pub fn tip_escrow(
+      origin: OriginFor<T>,
+      amount: BalanceOf<T>,
+      context: VerificationContext,
+      memo: Option<Memo>,
+    ) -> DispatchResultWithPostInfo {
+      let tipper = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
 
-      // Ensure that the tipping is enabled and configured for the context
-      let settings = TippingSettingsByAccountIdAndVerificationContext::<T>::get(tipped.clone(), context.clone());
       ensure!(
-        settings.enabled && settings.account.is_some(),
-        Error::<T>::InvalidConfiguration
+        context != VerificationContext::Unbounded,
+        Error::<T>::InvalidVerificationContext
+      );
+      ensure!(
+        Self::get_tipping_receiver(&context).is_err(),
+        Error::<T>::EscrowNotRequired
       );
 
-      // Fill in tip information
-      let receiver_account: T::AccountId = settings.account.unwrap();
+      // Fill in escrowed tip information
       let block_number = <frame_system::Pallet<T>>::block_number();
-      let tip = Tip {
+      let escrowed_tip = EscrowedTip {
         amount,
         sender: tipper.clone(),
-        receiver: receiver_account.clone(),
         created_at: T::TimeProvider::now().as_secs(),
         block_number,
+        expires_at: block_number.saturating_add(T::ESCROW_TIMEOUT),
+        memo,
       };
 
-      // Make the transfer and store the tip
-      <T as Config>::Currency::transfer(&tipper, &receiver_account, tip.amount, ExistenceRequirement::KeepAlive)?;
-      TipsByAccountIdAndVerificationContext::<T>::mutate(tipped.clone(), context, |existing_tips| {
-        existing_tips.try_push(tip.clone()).unwrap_or_else(|_| {
-          existing_tips.slide(0, existing_tips.len());
-          existing_tips.force_push(tip.clone());
-        })
+      // Make the transfer to the escrow account and store the escrowed tip
+      <T as Config>::Currency::transfer(
+        &tipper,
+        &Self::escrow_account_id(),
+        escrowed_tip.amount,
+        ExistenceRequirement::KeepAlive,
+      )?;
+      Self::charge_memo_fee(&tipper, &escrowed_tip.memo)?;
+      EscrowedTipsByVerificationContext::<T>::try_mutate(context.clone(), |escrowed_tips| {
+        escrowed_tips
+          .try_push(escrowed_tip.clone())
+          .map_err(|_| Error::<T>::MaxEscrowedTipsPerVerificationContextLimitReached)
+      })?;
+
+      // Emit an event that the tip has been escrowed
+      Self::deposit_event(Event::TipEscrowed(tipper, context, escrowed_tip));
+
+      Ok(().into())
+    }
+
+    /// Transfers the [`EscrowedTip`]s of a [`VerificationContext`] to the account configured to
+    /// receive the tips and stores them in `TipsByAccountIdAndVerificationContext`. Expired
+    /// escrowed tips are refunded to their senders instead.
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * context - the [`VerificationContext`]
+    ///
+    /// # Errors
+    /// * `InvalidVerificationContext` - If the [`VerificationContext`] is not available for tipping
+    /// * `InvalidConfiguration` - If tipping is disabled or not configured for the context
+    /// * `NotVerificationHolder` - If the caller is not the holder of the verification
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `EscrowedTipRefunded` - for each expired [`EscrowedTip`]
+    /// * `EscrowedTipsClaimed` - providing the total amount claimed
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing the actual weight, depending on the count of
+    /// [`EscrowedTip`]s processed
+    #[pallet::weight(<T as Config>::WeightInfo::claim_escrowed_tips(T::MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT))]
+    pub fn claim_escrowed_tips(origin: OriginFor<T>, context: VerificationContext) -> DispatchResultWithPostInfo {
+      let caller = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let (holder, receiver_account) = Self::get_tipping_receiver(&context)?;
+      ensure!(caller == holder, Error::<T>::NotVerificationHolder);
+
+      let escrow_account = Self::escrow_account_id();
+      let block_number = <frame_system::Pallet<T>>::block_number();
+      let mut claimed: BalanceOf<T> = 0u32.into();
+
+      let escrowed_tips = EscrowedTipsByVerificationContext::<T>::take(&context);
+      let count = escrowed_tips.len() as u32;
+      for escrowed_tip in escrowed_tips {
+        if escrowed_tip.expires_at < block_number {
+          Self::do_refund_escrowed_tip(&escrow_account, &context, escrowed_tip)?;
+        } else {
+          let net_amount = Self::do_transfer_tip(
+            &escrow_account,
+            &receiver_account,
+            escrowed_tip.amount,
+            ExistenceRequirement::AllowDeath,
+          )?;
+          claimed = claimed.saturating_add(net_amount);
+
+          let tip = Tip {
+            amount: escrowed_tip.amount,
+            net_amount,
+            sender: escrowed_tip.sender,
+            receiver: receiver_account.clone(),
+            created_at: escrowed_tip.created_at,
+            block_number: escrowed_tip.block_number,
+            memo: escrowed_tip.memo,
+            state: TipSettlement::Settled,
+          };
+          Self::do_store_tip(&holder, &context, tip.clone());
+          Self::do_award_supporter_badge(&holder, &context, &tip);
+        }
+      }
+
+      // Emit an event that the escrowed tips have been claimed
+      Self::deposit_event(Event::EscrowedTipsClaimed(holder, context, claimed));
+
+      Ok(Some(<T as Config>::WeightInfo::claim_escrowed_tips(count)).into())
+    }
+
+    /// Refunds the expired [`EscrowedTip`]s of a [`VerificationContext`] to their senders. Can be
+    /// called by any account.
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * context - the [`VerificationContext`]
+    ///
+    /// # Errors
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `EscrowedTipRefunded` - for each expired [`EscrowedTip`]
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing the actual weight, depending on the count of
+    /// [`EscrowedTip`]s processed
+    #[pallet::weight(<T as Config>::WeightInfo::refund_escrowed_tips(T::MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT))]
+    pub fn refund_escrowed_tips(origin: OriginFor<T>, context: VerificationContext) -> DispatchResultWithPostInfo {
+      ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let escrow_account = Self::escrow_account_id();
+      let block_number = <frame_system::Pallet<T>>::block_number();
+
+      let (expired, pending): (Vec<_>, Vec<_>) = EscrowedTipsByVerificationContext::<T>::get(&context)
+        .into_iter()
+        .partition(|escrowed_tip| escrowed_tip.expires_at < block_number);
+
+      let count = expired.len() as u32;
+      for escrowed_tip in expired {
+        Self::do_refund_escrowed_tip(&escrow_account, &context, escrowed_tip)?;
+      }
+      EscrowedTipsByVerificationContext::<T>::insert(&context, BoundedVec::truncate_from(pending));
+
+      Ok(Some(<T as Config>::WeightInfo::refund_escrowed_tips(count)).into())
+    }
+
+    /// Configures the supporter badges for a [`VerificationContext`]: every tip of at least the
+    /// threshold amount earns the sender a badge, minted in the collection of the holder by the
+    /// `SupporterBadgeMinter`. Failing to mint a badge does not prevent the tip.
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * context - the [`VerificationContext`]
+    /// * threshold - the minimum tip amount that earns a badge, `None` to turn the badges off
+    ///
+    /// # Errors
+    /// * `NotVerificationHolder` - If the caller is not the holder of the successful verification
+    ///   of the context
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `SupporterBadgeUpdated` - when the threshold is successfully updated
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::set_supporter_badge())]
+    pub fn set_supporter_badge(
+      origin: OriginFor<T>,
+      context: VerificationContext,
+      threshold: Option<BalanceOf<T>>,
+    ) -> DispatchResultWithPostInfo {
+      let caller = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let requests = verification::Pallet::<T>::get_requests(
+        vec![context.clone()],
+        Some(VerificationStatus::Success),
+        Some(caller.clone()),
+        0,
+        1,
+      );
+      ensure!(requests.len() == 1, Error::<T>::NotVerificationHolder);
+
+      SupporterBadgeThresholdByAccountIdAndVerificationContext::<T>::set(&caller, &context, threshold);
+
+      // Emit an event that the supporter badge has been updated
+      Self::deposit_event(Event::SupporterBadgeUpdated(caller, context, threshold));
+
+      Ok(().into())
+    }
+
+    /// Configures the refund window for a [`VerificationContext`]: the tips are held as reserved
+    /// balance of their senders for the provided number of blocks, during which the senders can
+    /// cancel them. Afterwards, the tips settle automatically. The tips already pending are not
+    /// affected.
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * context - the [`VerificationContext`]
+    /// * window - the number of blocks the tips are held for, `None` to make the tips final
+    ///   immediately
+    ///
+    /// # Errors
+    /// * `NotVerificationHolder` - If the caller is not the holder of the successful verification
+    ///   of the context
+    /// * `InvalidRefundWindow` - If the window is zero or exceeds `MaxRefundWindow`
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `RefundWindowUpdated` - when the refund window is successfully updated
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::set_refund_window())]
+    pub fn set_refund_window(
+      origin: OriginFor<T>,
+      context: VerificationContext,
+      window: Option<T::BlockNumber>,
+    ) -> DispatchResultWithPostInfo {
+      let caller = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let requests = verification::Pallet::<T>::get_requests(
+        vec![context.clone()],
+        Some(VerificationStatus::Success),
+        Some(caller.clone()),
+        0,
+        1,
+      );
+      ensure!(requests.len() == 1, Error::<T>::NotVerificationHolder);
+      if let Some(window) = window {
+        ensure!(
+          !window.is_zero() && window <= T::MAX_REFUND_WINDOW,
+          Error::<T>::InvalidRefundWindow
+        );
+      }
+
+      RefundWindowByAccountIdAndVerificationContext::<T>::set(&caller, &context, window);
+
+      // Emit an event that the refund window has been updated
+      Self::deposit_event(Event::RefundWindowUpdated(caller, context, window));
+
+      Ok(().into())
+    }
+
+    /// Cancels a [`Tip`] that is still in the refund window of its receiver. The amount held as
+    /// reserved balance is released to the sender, while the fee of the memo is not refunded. The
+    /// tip stays in the history of the context, marked as refunded.
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * pending_tip_id - the identifier of the [`PendingTip`]
+    ///
+    /// # Errors
+    /// * `PendingTipNotFound` - If the tip does not exist or it is already settled
+    /// * `NotTipSender` - If the caller is not the sender of the tip
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `TipRefunded` - when the [`Tip`] is successfully cancelled
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::cancel_tip())]
+    pub fn cancel_tip(origin: OriginFor<T>, pending_tip_id: PendingTipId) -> DispatchResultWithPostInfo {
+      let caller = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let pending_tip = PendingTips::<T>::get(pending_tip_id).ok_or(Error::<T>::PendingTipNotFound)?;
+      ensure!(pending_tip.tip.sender == caller, Error::<T>::NotTipSender);
+
+      if let TipSettlement::Pending(settles_at) = pending_tip.tip.state {
+        PendingTipsBySettlementBlock::<T>::mutate(settles_at, |pending_tip_ids| {
+          pending_tip_ids.retain(|id| *id != pending_tip_id)
+        });
+      }
+      PendingTips::<T>::remove(pending_tip_id);
+
+      Self::do_release_tip(pending_tip_id, pending_tip);
+
+      Ok(().into())
+    }
+
+    /// Creates a quadratic funding [`MatchingRound`]. The tips sent while the round is open, from
+    /// the senders accepted by the `MatchingContributorFilter`, are accounted as contributions
+    /// to the tipped contexts. Only one round at a time can be in progress.
+    ///
+    /// # Arguments
+    /// * origin - the call origin, accepted by the `MatchingRoundOrigin`
+    /// * start - the first block where the tips are accounted in the round
+    /// * end - the last block where the tips are accounted in the round
+    ///
+    /// # Errors
+    /// * `MatchingRoundInProgress` - If another round is not finalized yet
+    /// * `InvalidMatchingRound` - If the start does not precede the end or the end is in the past
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `MatchingRoundCreated` - when the [`MatchingRound`] is successfully created
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::create_matching_round())]
+    pub fn create_matching_round(
+      origin: OriginFor<T>,
+      start: T::BlockNumber,
+      end: T::BlockNumber,
+    ) -> DispatchResultWithPostInfo {
+      T::MatchingRoundOrigin::ensure_origin(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      ensure!(
+        CurrentMatchingRound::<T>::get().is_none(),
+        Error::<T>::MatchingRoundInProgress
+      );
+      ensure!(
+        start < end && end >= <frame_system::Pallet<T>>::block_number(),
+        Error::<T>::InvalidMatchingRound
+      );
+
+      let round_id = NextMatchingRoundId::<T>::mutate(|id| {
+        let round_id = *id;
+        *id = id.saturating_add(1);
+        round_id
       });
+      MatchingRounds::<T>::insert(
+        round_id,
+        MatchingRound {
+          start,
+          end,
+          contexts: 0,
+          distributed: None,
+        },
+      );
+      CurrentMatchingRound::<T>::put(round_id);
 
-      // Emit an event that the tip has been created
-      Self::deposit_event(Event::TipCreated(tipper, tipped, tip));
+      // Emit an event that the matching round has been created
+      Self::deposit_event(Event::MatchingRoundCreated(round_id, start, end));
+
+      Ok(().into())
+    }
+
+    /// Adds funds to the matching pool of a [`MatchingRound`]. The funds are held by the account
+    /// of the round, which can be funded by the treasury as well.
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * round_id - the identifier of the [`MatchingRound`]
+    /// * amount - the amount to add to the matching pool
+    ///
+    /// # Errors
+    /// * `MatchingRoundNotFound` - If the round does not exist
+    /// * `MatchingRoundAlreadyFinalized` - If the round is already finalized
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `MatchingRoundFunded` - when the funds are successfully added
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::fund_matching_round())]
+    pub fn fund_matching_round(
+      origin: OriginFor<T>,
+      round_id: MatchingRoundId,
+      amount: BalanceOf<T>,
+    ) -> DispatchResultWithPostInfo {
+      let donor = ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let round = MatchingRounds::<T>::get(round_id).ok_or(Error::<T>::MatchingRoundNotFound)?;
+      ensure!(round.distributed.is_none(), Error::<T>::MatchingRoundAlreadyFinalized);
+
+      <T as Config>::Currency::transfer(
+        &donor,
+        &Self::matching_round_account_id(round_id),
+        amount,
+        ExistenceRequirement::KeepAlive,
+      )?;
+
+      // Emit an event that the matching round has been funded
+      Self::deposit_event(Event::MatchingRoundFunded(round_id, donor, amount));
+
+      Ok(().into())
+    }
+
+    /// Finalizes a [`MatchingRound`] once it is ended, distributing the matching pool to the
+    /// verification contexts in proportion to the square of the sum of the square roots of the
+    /// contributions they received. Every context receives its share on the account configured
+    /// to receive the tips; the shares that cannot be paid out and the rounding remainder are
+    /// handed over to the `ProtocolFeeHandler`.
+    ///
+    /// # Arguments
+    /// * origin - the call origin
+    /// * round_id - the identifier of the [`MatchingRound`]
+    ///
+    /// # Errors
+    /// * `MatchingRoundNotFound` - If the round does not exist
+    /// * `MatchingRoundAlreadyFinalized` - If the round is already finalized
+    /// * `MatchingRoundNotEnded` - If the end of the round is not reached yet
+    /// * `StorageMigrationInProgress` - If the storage is being migrated
+    ///
+    /// # Events
+    /// * `MatchingFundsDistributed` - for every context receiving matching funds
+    /// * `MatchingRoundFinalized` - when the round is successfully finalized
+    ///
+    /// # Return
+    /// `DispatchResultWithPostInfo` containing Unit type
+    #[pallet::weight(<T as Config>::WeightInfo::finalize_matching_round(T::MAX_CONTEXTS_PER_MATCHING_ROUND))]
+    pub fn finalize_matching_round(origin: OriginFor<T>, round_id: MatchingRoundId) -> DispatchResultWithPostInfo {
+      ensure_signed(origin)?;
+      Self::ensure_storage_migrated()?;
+
+      let round = MatchingRounds::<T>::get(round_id).ok_or(Error::<T>::MatchingRoundNotFound)?;
+      ensure!(round.distributed.is_none(), Error::<T>::MatchingRoundAlreadyFinalized);
+      ensure!(
+        <frame_system::Pallet<T>>::block_number() > round.end,
+        Error::<T>::MatchingRoundNotEnded
+      );
+
+      let distributed = Self::do_distribute_matching_funds(round_id);
+
+      MatchingRounds::<T>::insert(
+        round_id,
+        MatchingRound {
+          distributed: Some(distributed),
+          ..round
+        },
+      );
+      CurrentMatchingRound::<T>::kill();
+
+      // Emit an event that the matching round has been finalized
+      Self::deposit_event(Event::MatchingRoundFinalized(round_id, distributed));
 
       Ok(().into())
     }
@@ -0,0 +1,725 @@
+// This file is part of Anagolay Network.
+
+// Copyright (C) 2019-2023 Anagolay Network.
+
+//! Storage migrations of the tipping pallet. Each module `vN` contains the logic to upgrade the
+//! storage from version `N - 1` to version `N`, along with the types that were stored before.
+
+use super::*;
+use crate::types::{Memo, MigrationCursor, Tip, TipSettlement};
+use core::convert::TryFrom;
+use frame_support::{pallet_prelude::*, sp_std::vec::Vec, storage::StoragePrefixedMap, traits::StorageVersion};
+use verification::types::VerificationContext;
+
+/// The maximum count of storage reads and writes performed by the migrations in a single block
+pub const MAX_MIGRATION_OPERATIONS_PER_BLOCK: u64 = 2_000;
+
+/// The maximum count of bytes read and written by the migrations in a single block, a fraction of
+/// the proof size of a parachain block since the entries of tips can be large
+pub const MAX_MIGRATION_PROOF_SIZE_PER_BLOCK: u64 = 1024 * 1024;
+
+/// Whether the on-chain storage is older than the current [`STORAGE_VERSION`], meaning that the
+/// migrations are still running
+pub fn is_migrating<T: Config>() -> bool {
+  Pallet::<T>::on_chain_storage_version() < STORAGE_VERSION
+}
+
+/// Run the migrations needed to bring the on-chain storage to the current [`STORAGE_VERSION`],
+/// up to [`MAX_MIGRATION_OPERATIONS_PER_BLOCK`] and [`MAX_MIGRATION_PROOF_SIZE_PER_BLOCK`]. The
+/// migrations that do not complete are resumed in the following blocks from the cursor stored in
+/// `OngoingMigration`
+///
+/// # Return
+/// The weight consumed by the migrations, including their proof size
+pub fn migrate<T: Config>() -> Weight {
+  migrate_with_limit::<T>(MAX_MIGRATION_OPERATIONS_PER_BLOCK, MAX_MIGRATION_PROOF_SIZE_PER_BLOCK)
+}
+
+/// Run the migrations needed to bring the on-chain storage to the current [`STORAGE_VERSION`],
+/// pausing once the count of storage reads and writes or the count of bytes they access reaches
+/// its limit
+///
+/// # Arguments
+///  * operations_limit - The maximum count of storage reads and writes to perform
+///  * proof_size_limit - The maximum count of bytes to read and write
+///
+/// # Return
+/// The weight consumed by the migrations, including their proof size
+pub fn migrate_with_limit<T: Config>(operations_limit: u64, proof_size_limit: u64) -> Weight {
+  let mut budget = MigrationBudget::new(operations_limit, proof_size_limit);
+
+  loop {
+    let version = Pallet::<T>::on_chain_storage_version();
+    budget.consume(1, 0);
+    if version >= STORAGE_VERSION {
+      break;
+    }
+    if budget.exhausted() {
+      if !OngoingMigration::<T>::exists() {
+        OngoingMigration::<T>::put(MigrationCursor::default());
+        budget.consume(0, 1);
+      }
+      break;
+    }
+
+    let cursor = OngoingMigration::<T>::take().unwrap_or_default();
+    budget.consume(1, 1);
+    let paused_at = if version < 1 {
+      v1::migrate::<T>(cursor, &mut budget)
+    } else if version < 2 {
+      v2::migrate::<T>(cursor, &mut budget)
+    } else if version < 3 {
+      v3::migrate::<T>(cursor, &mut budget)
+    } else if version < 4 {
+      v4::migrate::<T>(cursor, &mut budget)
+    } else {
+      v5::migrate::<T>(cursor, &mut budget)
+    };
+
+    if let Some(cursor) = paused_at {
+      OngoingMigration::<T>::put(cursor);
+      budget.consume(0, 1);
+      break;
+    }
+  }
+
+  budget.weight::<T>()
+}
+
+/// Count of the storage reads and writes performed by the migrations in the current block, and of
+/// the bytes they access, which end up in the proof of the block
+pub struct MigrationBudget {
+  operations_limit: u64,
+  proof_size_limit: u64,
+  reads: u64,
+  writes: u64,
+  proof_size: u64,
+}
+
+impl MigrationBudget {
+  fn new(operations_limit: u64, proof_size_limit: u64) -> Self {
+    MigrationBudget {
+      operations_limit,
+      proof_size_limit,
+      reads: 0,
+      writes: 0,
+      proof_size: 0,
+    }
+  }
+
+  fn consume(&mut self, reads: u64, writes: u64) {
+    self.reads = self.reads.saturating_add(reads);
+    self.writes = self.writes.saturating_add(writes);
+  }
+
+  fn consume_proof_size(&mut self, bytes: usize) {
+    self.proof_size = self.proof_size.saturating_add(bytes as u64);
+  }
+
+  fn exhausted(&self) -> bool {
+    self.reads.saturating_add(self.writes) >= self.operations_limit || self.proof_size >= self.proof_size_limit
+  }
+
+  fn weight<T: Config>(&self) -> Weight {
+    T::DbWeight::get()
+      .reads_writes(self.reads, self.writes)
+      .set_proof_size(self.proof_size)
+  }
+}
+
+/// Build the cursor that resumes a migration after the entry stored at `last_key`
+///
+/// # Arguments
+///  * last_key - The raw storage key of the last entry completely migrated, if any
+///  * migrated_tips - The count of the tips already migrated in the following entry
+///
+/// # Return
+/// The [`MigrationCursor`], or `None` if the key is too long to be stored: the migration can not
+/// pause there and goes on in the same block
+fn cursor_at(last_key: Option<Vec<u8>>, migrated_tips: u32) -> Option<MigrationCursor> {
+  match last_key {
+    Some(key) => BoundedVec::try_from(key).ok().map(|key| MigrationCursor {
+      last_key: Some(key),
+      migrated_tips,
+    }),
+    None => Some(MigrationCursor {
+      last_key: None,
+      migrated_tips,
+    }),
+  }
+}
+
+/// Translate the entries of a map one by one, pausing once the budget is exhausted
+///
+/// # Arguments
+///  * entries - The entries to translate, in storage order
+///  * budget - The [`MigrationBudget`] of the current block
+///  * translate - Store the translation of an entry and return its raw storage key along with the
+///    encoded size of the translation
+///
+/// # Return
+/// The cursor to resume from, if the migration paused before the end of `entries`
+fn translate_entries<K1, K2, V: Encode>(
+  entries: impl Iterator<Item = (K1, K2, V)>,
+  budget: &mut MigrationBudget,
+  mut translate: impl FnMut(K1, K2, V) -> (Vec<u8>, usize),
+) -> Option<MigrationCursor> {
+  for (key1, key2, value) in entries {
+    let read_size = value.encoded_size();
+    let (raw_key, written_size) = translate(key1, key2, value);
+    budget.consume(1, 1);
+    budget.consume_proof_size(raw_key.len().saturating_add(read_size).saturating_add(written_size));
+    if budget.exhausted() {
+      if let Some(cursor) = cursor_at(Some(raw_key), 0) {
+        return Some(cursor);
+      }
+    }
+  }
+  None
+}
+
+/// Visit the tips of `TipsByAccountIdAndVerificationContext`, as stored before version 4, one by
+/// one, pausing once the budget is exhausted
+///
+/// # Arguments
+///  * cursor - The [`MigrationCursor`] to resume from
+///  * budget - The [`MigrationBudget`] of the current block
+///  * migrate_tip - Migrate a tip received by the holder for the context and return the count of
+///    bytes it read and wrote
+///
+/// # Return
+/// The cursor to resume from, if the migration paused before the last tip
+fn for_each_old_tip<T: Config>(
+  cursor: MigrationCursor,
+  budget: &mut MigrationBudget,
+  mut migrate_tip: impl FnMut(
+    &T::AccountId,
+    &VerificationContext,
+    v4::OldTip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+  ) -> usize,
+) -> Option<MigrationCursor> {
+  let mut previous_key = cursor.last_key.map(|key| key.into_inner());
+  let mut skipped_tips = cursor.migrated_tips as usize;
+  let entries = match previous_key.clone() {
+    Some(key) => v4::TipsByAccountIdAndVerificationContext::<T>::iter_from(key),
+    None => v4::TipsByAccountIdAndVerificationContext::<T>::iter(),
+  };
+
+  for (holder, context, tips) in entries {
+    let raw_key = v4::TipsByAccountIdAndVerificationContext::<T>::hashed_key_for(&holder, &context);
+    budget.consume(1, 0);
+    budget.consume_proof_size(raw_key.len().saturating_add(tips.encoded_size()));
+    let tips_count = tips.len();
+    for (index, tip) in tips.into_iter().enumerate().skip(skipped_tips) {
+      let accessed_size = migrate_tip(&holder, &context, tip);
+      budget.consume(2, 2);
+      budget.consume_proof_size(accessed_size);
+      if budget.exhausted() {
+        let migrated_tips = index.saturating_add(1);
+        let paused_at = if migrated_tips == tips_count {
+          cursor_at(Some(raw_key.clone()), 0)
+        } else {
+          cursor_at(previous_key.clone(), migrated_tips as u32)
+        };
+        if paused_at.is_some() {
+          return paused_at;
+        }
+      }
+    }
+    skipped_tips = 0;
+    previous_key = Some(raw_key);
+  }
+  None
+}
+
+/// Count the entries of the maps of tips before the upgrade
+///
+/// # Return
+/// The encoded count of the entries of `TipsByAccountIdAndVerificationContext` and
+/// `TipsBySenderAndVerificationContext`
+#[cfg(feature = "try-runtime")]
+pub fn pre_upgrade<T: Config>() -> Result<Vec<u8>, &'static str> {
+  let holder_entries = crate::TipsByAccountIdAndVerificationContext::<T>::iter_keys().count() as u64;
+  let sender_entries = crate::TipsBySenderAndVerificationContext::<T>::iter_keys().count() as u64;
+  Ok((holder_entries, sender_entries).encode())
+}
+
+/// Check the maps of tips after the upgrade: no entry of a holder is lost, the entries of the
+/// senders can only be added and, once the migrations complete, every entry decodes in the current
+/// format
+///
+/// # Arguments
+///  * state - The output of [`pre_upgrade`]
+#[cfg(feature = "try-runtime")]
+pub fn post_upgrade<T: Config>(state: Vec<u8>) -> Result<(), &'static str> {
+  let (holder_entries, sender_entries): (u64, u64) =
+    Decode::decode(&mut &state[..]).map_err(|_| "The state of the tipping pre_upgrade can not be decoded")?;
+
+  let holder_keys = crate::TipsByAccountIdAndVerificationContext::<T>::iter_keys().count() as u64;
+  let sender_keys = crate::TipsBySenderAndVerificationContext::<T>::iter_keys().count() as u64;
+  ensure!(
+    holder_keys == holder_entries,
+    "Tips of the holders were lost by the migration"
+  );
+  ensure!(
+    sender_keys >= sender_entries,
+    "Tips of the senders were lost by the migration"
+  );
+
+  if is_migrating::<T>() {
+    ensure!(
+      OngoingMigration::<T>::exists(),
+      "The tipping migration paused without storing its cursor"
+    );
+  } else {
+    ensure!(
+      !OngoingMigration::<T>::exists(),
+      "The tipping migration completed without removing its cursor"
+    );
+    ensure!(
+      crate::TipsByAccountIdAndVerificationContext::<T>::iter().count() as u64 == holder_keys,
+      "Tips of the holders do not decode in the current format"
+    );
+    ensure!(
+      crate::TipsBySenderAndVerificationContext::<T>::iter().count() as u64 == sender_keys,
+      "Tips of the senders do not decode in the current format"
+    );
+    ensure!(
+      crate::TipsBySenderAndVerificationContext::<T>::iter().all(|(sender, context, tips)| {
+        SentTipSequencesBySenderAndVerificationContext::<T>::get(sender, context).len() == tips.len()
+      }),
+      "Tips of the senders are not all sequenced"
+    );
+  }
+
+  Ok(())
+}
+
+/// Version 1: a [`Memo`] is attached to every [`Tip`]
+pub mod v1 {
+  use super::*;
+  use crate::constants::MaxTipsPerVerificationContextGet;
+  use core::fmt::Debug;
+
+  /// Structure representing a tip as stored before version 1
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebugNoBound, TypeInfo)]
+  pub struct OldTip<Balance: Debug, Account: Debug, BlockNumber: Debug> {
+    pub amount: Balance,
+    pub sender: Account,
+    pub receiver: Account,
+    pub created_at: u64,
+    pub block_number: BlockNumber,
+  }
+
+  /// Collection of tips as stored before version 1
+  pub type OldTips<T> = BoundedVec<
+    OldTip<BalanceOf<T>, <T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>,
+    MaxTipsPerVerificationContextGet<T>,
+  >;
+
+  /// `TipsByAccountIdAndVerificationContext` as stored before version 1
+  #[frame_support::storage_alias]
+  pub type TipsByAccountIdAndVerificationContext<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::AccountId,
+    Twox64Concat,
+    VerificationContext,
+    OldTips<T>,
+    ValueQuery,
+  >;
+
+  /// Translate the stored tips into the new format, leaving the memo empty
+  ///
+  /// # Arguments
+  ///  * cursor - The [`MigrationCursor`] to resume from
+  ///  * budget - The [`MigrationBudget`] of the current block
+  ///
+  /// # Return
+  /// The cursor to resume from, if the migration paused before completing
+  pub fn migrate<T: Config>(cursor: MigrationCursor, budget: &mut MigrationBudget) -> Option<MigrationCursor> {
+    let entries = match cursor.last_key {
+      Some(key) => TipsByAccountIdAndVerificationContext::<T>::iter_from(key.into_inner()),
+      None => TipsByAccountIdAndVerificationContext::<T>::iter(),
+    };
+    let paused_at = translate_entries(entries, budget, |holder, context, old_tips| {
+      let tips = old_tips
+        .into_iter()
+        .map(|old_tip| v4::OldTip {
+          amount: old_tip.amount,
+          sender: old_tip.sender,
+          receiver: old_tip.receiver,
+          created_at: old_tip.created_at,
+          block_number: old_tip.block_number,
+          memo: None,
+        })
+        .collect::<Vec<_>>();
+      let tips: v4::OldTips<T> = BoundedVec::truncate_from(tips);
+      let written_size = tips.encoded_size();
+      v4::TipsByAccountIdAndVerificationContext::<T>::insert(&holder, &context, tips);
+      (
+        v4::TipsByAccountIdAndVerificationContext::<T>::hashed_key_for(&holder, &context),
+        written_size,
+      )
+    });
+    if paused_at.is_some() {
+      return paused_at;
+    }
+
+    StorageVersion::new(1).put::<Pallet<T>>();
+    budget.consume(0, 1);
+    None
+  }
+}
+
+/// Version 2: the running aggregates of the tips are stored in
+/// `TipsAggregateByAccountIdAndVerificationContext` and
+/// `TipsAggregateByAccountIdAndVerificationContextAndSender`
+pub mod v2 {
+  use super::*;
+
+  /// Initialize the running aggregates out of the tips currently stored. The tips that were
+  /// already discarded from `TipsByAccountIdAndVerificationContext` cannot be accounted for
+  ///
+  /// # Arguments
+  ///  * cursor - The [`MigrationCursor`] to resume from
+  ///  * budget - The [`MigrationBudget`] of the current block
+  ///
+  /// # Return
+  /// The cursor to resume from, if the migration paused before completing
+  pub fn migrate<T: Config>(cursor: MigrationCursor, budget: &mut MigrationBudget) -> Option<MigrationCursor> {
+    let paused_at = for_each_old_tip::<T>(cursor, budget, |holder, context, old_tip| {
+      let tip: Tip<BalanceOf<T>, T::AccountId, T::BlockNumber> = v5::OldTip::from(old_tip).into();
+      let aggregate_size = TipsAggregateByAccountIdAndVerificationContext::<T>::mutate(holder, context, |aggregate| {
+        aggregate.add(&tip);
+        aggregate.encoded_size()
+      });
+      let sender_aggregate_size = TipsAggregateByAccountIdAndVerificationContextAndSender::<T>::mutate(
+        (holder, context, tip.sender.clone()),
+        |aggregate| {
+          aggregate.add(&tip);
+          aggregate.encoded_size()
+        },
+      );
+      aggregate_size.saturating_add(sender_aggregate_size).saturating_mul(2)
+    });
+    if paused_at.is_some() {
+      return paused_at;
+    }
+
+    StorageVersion::new(2).put::<Pallet<T>>();
+    budget.consume(0, 1);
+    None
+  }
+}
+
+/// Version 3: the Tips are indexed by their sender in `TipsBySenderAndVerificationContext`, along
+/// with their sequence for the sender in `SentTipSequencesBySenderAndVerificationContext`
+pub mod v3 {
+  use super::*;
+
+  /// Index the tips currently stored by their sender. The tips that were already discarded from
+  /// `TipsByAccountIdAndVerificationContext` cannot be indexed. The sequences follow the order of
+  /// the migration, that only tells apart the tips of a sender created at the same time
+  ///
+  /// # Arguments
+  ///  * cursor - The [`MigrationCursor`] to resume from
+  ///  * budget - The [`MigrationBudget`] of the current block
+  ///
+  /// # Return
+  /// The cursor to resume from, if the migration paused before completing
+  pub fn migrate<T: Config>(cursor: MigrationCursor, budget: &mut MigrationBudget) -> Option<MigrationCursor> {
+    let paused_at = for_each_old_tip::<T>(cursor, budget, |_, context, tip| {
+      let (sequence, count_size) = SentTipsCountBySender::<T>::mutate(&tip.sender, |count| {
+        let sequence = *count;
+        *count = count.saturating_add(1);
+        (sequence, count.encoded_size())
+      });
+      let sequences_size =
+        SentTipSequencesBySenderAndVerificationContext::<T>::mutate(&tip.sender, context, |sequences| {
+          Pallet::<T>::push_tip(sequences, sequence);
+          sequences.encoded_size()
+        });
+      let tips_size =
+        v4::TipsBySenderAndVerificationContext::<T>::mutate(tip.sender.clone(), context, |existing_tips| {
+          existing_tips.try_push(tip.clone()).unwrap_or_else(|_| {
+            existing_tips.slide(0, existing_tips.len());
+            existing_tips.force_push(tip);
+          });
+          existing_tips.encoded_size()
+        });
+      count_size
+        .saturating_add(sequences_size)
+        .saturating_add(tips_size)
+        .saturating_mul(2)
+    });
+    if paused_at.is_some() {
+      return paused_at;
+    }
+
+    StorageVersion::new(3).put::<Pallet<T>>();
+    budget.consume(0, 1);
+    None
+  }
+}
+
+/// Version 4: the net amount, after the deduction of the protocol fee, is stored in every [`Tip`]
+pub mod v4 {
+  use super::*;
+  use crate::constants::MaxTipsPerVerificationContextGet;
+  use core::fmt::Debug;
+
+  /// Structure representing a tip as stored before version 4
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebugNoBound, TypeInfo)]
+  pub struct OldTip<Balance: Debug, Account: Debug, BlockNumber: Debug> {
+    pub amount: Balance,
+    pub sender: Account,
+    pub receiver: Account,
+    pub created_at: u64,
+    pub block_number: BlockNumber,
+    pub memo: Option<Memo>,
+  }
+
+  impl<Balance, Account, BlockNumber> From<OldTip<Balance, Account, BlockNumber>>
+    for v5::OldTip<Balance, Account, BlockNumber>
+  where
+    Balance: Debug + Copy,
+    Account: Debug,
+    BlockNumber: Debug,
+  {
+    fn from(old_tip: OldTip<Balance, Account, BlockNumber>) -> Self {
+      v5::OldTip {
+        amount: old_tip.amount,
+        net_amount: old_tip.amount,
+        sender: old_tip.sender,
+        receiver: old_tip.receiver,
+        created_at: old_tip.created_at,
+        block_number: old_tip.block_number,
+        memo: old_tip.memo,
+      }
+    }
+  }
+
+  /// Collection of tips as stored before version 4
+  pub type OldTips<T> = BoundedVec<
+    OldTip<BalanceOf<T>, <T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>,
+    MaxTipsPerVerificationContextGet<T>,
+  >;
+
+  /// `TipsByAccountIdAndVerificationContext` as stored before version 4
+  #[frame_support::storage_alias]
+  pub type TipsByAccountIdAndVerificationContext<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::AccountId,
+    Twox64Concat,
+    VerificationContext,
+    OldTips<T>,
+    ValueQuery,
+  >;
+
+  /// `TipsBySenderAndVerificationContext` as stored before version 4
+  #[frame_support::storage_alias]
+  pub type TipsBySenderAndVerificationContext<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::AccountId,
+    Twox64Concat,
+    VerificationContext,
+    OldTips<T>,
+    ValueQuery,
+  >;
+
+  /// Translate the stored tips into the new format. No protocol fee was deducted from the tips
+  /// stored so far, so the net amount equals the amount. The tips of the holders are translated
+  /// first, then the tips of the senders
+  ///
+  /// # Arguments
+  ///  * cursor - The [`MigrationCursor`] to resume from
+  ///  * budget - The [`MigrationBudget`] of the current block
+  ///
+  /// # Return
+  /// The cursor to resume from, if the migration paused before completing
+  pub fn migrate<T: Config>(cursor: MigrationCursor, budget: &mut MigrationBudget) -> Option<MigrationCursor> {
+    let translate = |old_tips: OldTips<T>| -> v5::OldTips<T> {
+      let tips = old_tips.into_iter().map(v5::OldTip::from).collect::<Vec<_>>();
+      BoundedVec::truncate_from(tips)
+    };
+    let sender_prefix = TipsBySenderAndVerificationContext::<T>::final_prefix();
+    let last_key = cursor.last_key.map(|key| key.into_inner());
+    let resume_senders = last_key.as_ref().map_or(false, |key| key.starts_with(&sender_prefix));
+
+    if !resume_senders {
+      let entries = match last_key.clone() {
+        Some(key) => TipsByAccountIdAndVerificationContext::<T>::iter_from(key),
+        None => TipsByAccountIdAndVerificationContext::<T>::iter(),
+      };
+      let paused_at = translate_entries(entries, budget, |holder, context, old_tips| {
+        let tips = translate(old_tips);
+        let written_size = tips.encoded_size();
+        v5::TipsByAccountIdAndVerificationContext::<T>::insert(&holder, &context, tips);
+        (
+          v5::TipsByAccountIdAndVerificationContext::<T>::hashed_key_for(&holder, &context),
+          written_size,
+        )
+      });
+      if paused_at.is_some() {
+        return paused_at;
+      }
+    }
+
+    let entries = match last_key.filter(|_| resume_senders) {
+      Some(key) => TipsBySenderAndVerificationContext::<T>::iter_from(key),
+      None => TipsBySenderAndVerificationContext::<T>::iter(),
+    };
+    let paused_at = translate_entries(entries, budget, |sender, context, old_tips| {
+      let tips = translate(old_tips);
+      let written_size = tips.encoded_size();
+      v5::TipsBySenderAndVerificationContext::<T>::insert(&sender, &context, tips);
+      (
+        v5::TipsBySenderAndVerificationContext::<T>::hashed_key_for(&sender, &context),
+        written_size,
+      )
+    });
+    if paused_at.is_some() {
+      return paused_at;
+    }
+
+    StorageVersion::new(4).put::<Pallet<T>>();
+    budget.consume(0, 1);
+    None
+  }
+}
+
+/// Version 5: the settlement state is stored in every [`Tip`], since the tips can be held in the
+/// refund window of their receiver
+pub mod v5 {
+  use super::*;
+  use crate::constants::MaxTipsPerVerificationContextGet;
+  use core::fmt::Debug;
+
+  /// Structure representing a tip as stored before version 5
+  #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebugNoBound, TypeInfo)]
+  pub struct OldTip<Balance: Debug, Account: Debug, BlockNumber: Debug> {
+    pub amount: Balance,
+    pub net_amount: Balance,
+    pub sender: Account,
+    pub receiver: Account,
+    pub created_at: u64,
+    pub block_number: BlockNumber,
+    pub memo: Option<Memo>,
+  }
+
+  impl<Balance, Account, BlockNumber> From<OldTip<Balance, Account, BlockNumber>> for Tip<Balance, Account, BlockNumber>
+  where
+    Balance: Debug,
+    Account: Debug,
+    BlockNumber: Debug,
+  {
+    fn from(old_tip: OldTip<Balance, Account, BlockNumber>) -> Self {
+      Tip {
+        amount: old_tip.amount,
+        net_amount: old_tip.net_amount,
+        sender: old_tip.sender,
+        receiver: old_tip.receiver,
+        created_at: old_tip.created_at,
+        block_number: old_tip.block_number,
+        memo: old_tip.memo,
+        state: TipSettlement::Settled,
+      }
+    }
+  }
+
+  /// Collection of tips as stored before version 5
+  pub type OldTips<T> = BoundedVec<
+    OldTip<BalanceOf<T>, <T as frame_system::Config>::AccountId, <T as frame_system::Config>::BlockNumber>,
+    MaxTipsPerVerificationContextGet<T>,
+  >;
+
+  /// `TipsByAccountIdAndVerificationContext` as stored before version 5
+  #[frame_support::storage_alias]
+  pub type TipsByAccountIdAndVerificationContext<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::AccountId,
+    Twox64Concat,
+    VerificationContext,
+    OldTips<T>,
+    ValueQuery,
+  >;
+
+  /// `TipsBySenderAndVerificationContext` as stored before version 5
+  #[frame_support::storage_alias]
+  pub type TipsBySenderAndVerificationContext<T: Config> = StorageDoubleMap<
+    Pallet<T>,
+    Blake2_128Concat,
+    <T as frame_system::Config>::AccountId,
+    Twox64Concat,
+    VerificationContext,
+    OldTips<T>,
+    ValueQuery,
+  >;
+
+  /// Translate the stored tips into the new format. All the tips stored so far were transferred
+  /// immediately, so they are settled. The tips of the holders are translated first, then the
+  /// tips of the senders
+  ///
+  /// # Arguments
+  ///  * cursor - The [`MigrationCursor`] to resume from
+  ///  * budget - The [`MigrationBudget`] of the current block
+  ///
+  /// # Return
+  /// The cursor to resume from, if the migration paused before completing
+  pub fn migrate<T: Config>(cursor: MigrationCursor, budget: &mut MigrationBudget) -> Option<MigrationCursor> {
+    let translate = |old_tips: OldTips<T>| -> BoundedVec<
+      Tip<BalanceOf<T>, T::AccountId, T::BlockNumber>,
+      MaxTipsPerVerificationContextGet<T>,
+    > {
+      let tips = old_tips.into_iter().map(Tip::from).collect::<Vec<_>>();
+      BoundedVec::truncate_from(tips)
+    };
+    let sender_prefix = TipsBySenderAndVerificationContext::<T>::final_prefix();
+    let last_key = cursor.last_key.map(|key| key.into_inner());
+    let resume_senders = last_key.as_ref().map_or(false, |key| key.starts_with(&sender_prefix));
+
+    if !resume_senders {
+      let entries = match last_key.clone() {
+        Some(key) => TipsByAccountIdAndVerificationContext::<T>::iter_from(key),
+        None => TipsByAccountIdAndVerificationContext::<T>::iter(),
+      };
+      let paused_at = translate_entries(entries, budget, |holder, context, old_tips| {
+        let tips = translate(old_tips);
+        let written_size = tips.encoded_size();
+        crate::TipsByAccountIdAndVerificationContext::<T>::insert(&holder, &context, tips);
+        (
+          crate::TipsByAccountIdAndVerificationContext::<T>::hashed_key_for(&holder, &context),
+          written_size,
+        )
+      });
+      if paused_at.is_some() {
+        return paused_at;
+      }
+    }
+
+    let entries = match last_key.filter(|_| resume_senders) {
+      Some(key) => TipsBySenderAndVerificationContext::<T>::iter_from(key),
+      None => TipsBySenderAndVerificationContext::<T>::iter(),
+    };
+    let paused_at = translate_entries(entries, budget, |sender, context, old_tips| {
+      let tips = translate(old_tips);
+      let written_size = tips.encoded_size();
+      crate::TipsBySenderAndVerificationContext::<T>::insert(&sender, &context, tips);
+      (
+        crate::TipsBySenderAndVerificationContext::<T>::hashed_key_for(&sender, &context),
+        written_size,
+      )
+    });
+    if paused_at.is_some() {
+      return paused_at;
+    }
+
+    StorageVersion::new(5).put::<Pallet<T>>();
+    budget.consume(0, 1);
+    None
+  }
+}
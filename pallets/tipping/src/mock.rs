@@ -5,17 +5,27 @@
 //! Test utilities
 
 use crate as tipping;
-use crate::Config;
+use crate::{
+  types::{SupporterBadgeMinter, TippingVerificationInvalidator, VerifiedMatchingContributorFilter},
+  Config, NegativeImbalanceOf,
+};
 use core::{
+  cell::RefCell,
   convert::{TryFrom, TryInto},
   time::Duration,
 };
-use frame_support::{parameter_types, traits::UnixTime};
+use frame_support::{
+  dispatch::DispatchResult,
+  parameter_types,
+  traits::{Currency, EnsureOrigin, OnUnbalanced, UnixTime},
+  PalletId,
+};
 use pallet_balances::AccountData;
 use sp_core::{sr25519, sr25519::Signature, H256};
 use sp_runtime::{
   testing::{Header, TestXt, UintAuthorityId},
   traits::{BlakeTwo256, IdentityLookup},
+  Permill,
 };
 use verification::types::*;
 
@@ -89,6 +99,7 @@ impl pallet_balances::Config for Test {
 parameter_types! {
   pub const Period: u32 = 360000;
   pub const Offset: u32 = 0;
+  pub const FirstSupporterBadgeCollectionId: u32 = 100;
 }
 
 impl pallet_session::Config for Test {
@@ -107,7 +118,7 @@ impl verification::Config for Test {
   type AuthorityId = verification::crypto::VerificationAuthId;
   type RuntimeEvent = RuntimeEvent;
   type VerificationKeyGenerator = NaiveVerificationKeyGenerator<Self>;
-  type VerificationInvalidator = NaiveVerificationInvalidator<Self>;
+  type VerificationInvalidator = TippingVerificationInvalidator<Self>;
   type WeightInfo = ();
   type Currency = Balances;
 
@@ -136,13 +147,86 @@ impl UnixTime for MockTime {
   }
 }
 
+/// The account that collects the protocol fees in the tests
+pub const TREASURY: sr25519::Public = sr25519::Public([7u8; 32]);
+
+pub struct MockProtocolFeeHandler {}
+
+impl OnUnbalanced<NegativeImbalanceOf<Test>> for MockProtocolFeeHandler {
+  fn on_nonzero_unbalanced(amount: NegativeImbalanceOf<Test>) {
+    Balances::resolve_creating(&TREASURY, amount);
+  }
+}
+
+/// Accepts the signed origins as remote tippers, located at their own account
+pub struct EnsureRemoteTipper {}
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureRemoteTipper {
+  type Success = (sr25519::Public, sr25519::Public);
+
+  fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+    frame_system::EnsureSigned::<sr25519::Public>::try_origin(o).map(|who| (who, who))
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn successful_origin() -> RuntimeOrigin {
+    RuntimeOrigin::signed(sr25519::Public([0u8; 32]))
+  }
+}
+
+/// The metadata limit of the supporter badges, as the `StringLimit` of `pallet_uniques` in the
+/// runtime
+pub const BADGE_METADATA_LIMIT: usize = 128;
+
+thread_local! {
+  /// The metadata of the supporter badges minted in the tests
+  pub static MINTED_BADGE_METADATA: RefCell<Vec<Vec<u8>>> = RefCell::new(Vec::new());
+}
+
+/// Records the metadata of the minted supporter badges, rejecting the metadata that the runtime
+/// could not store
+#[derive(Clone)]
+pub struct MockSupporterBadgeMinter {}
+
+impl SupporterBadgeMinter<Test> for MockSupporterBadgeMinter {
+  type CollectionId = u32;
+  type ItemId = u32;
+
+  fn create_collection(_collection: &u32, _creator: &sr25519::Public) -> DispatchResult {
+    Ok(())
+  }
+
+  fn mint(_collection: &u32, _item: &u32, _supporter: &sr25519::Public, metadata: Vec<u8>) -> DispatchResult {
+    if metadata.len() > BADGE_METADATA_LIMIT {
+      return Err("Badge metadata too long".into());
+    }
+    MINTED_BADGE_METADATA.with(|minted| minted.borrow_mut().push(metadata));
+    Ok(())
+  }
+}
+
 impl Config for Test {
   type RuntimeEvent = RuntimeEvent;
   type Currency = Balances;
   type TimeProvider = MockTime;
+  type ProtocolFeeHandler = MockProtocolFeeHandler;
+  type SupporterBadgeMinter = MockSupporterBadgeMinter;
+  type FirstSupporterBadgeCollectionId = FirstSupporterBadgeCollectionId;
+  type RemoteLocation = sr25519::Public;
+  type RemoteTipOrigin = EnsureRemoteTipper;
+  type MatchingRoundOrigin = frame_system::EnsureRoot<sr25519::Public>;
+  type MatchingContributorFilter = VerifiedMatchingContributorFilter<Self>;
   type WeightInfo = ();
 
   const MAX_TIPS_PER_VERIFICATION_CONTEXT: u32 = 3;
+  const MEMO_FEE_PER_BYTE: u64 = 1;
+  const PALLET_ID: PalletId = PalletId(*b"anag/tip");
+  const ESCROW_TIMEOUT: u64 = 10;
+  const MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT: u32 = 2;
+  const PROTOCOL_FEE: Permill = Permill::from_percent(10);
+  const MAX_CONTEXTS_PER_MATCHING_ROUND: u32 = 2;
+  const MAX_REFUND_WINDOW: u64 = 5;
+  const MAX_PENDING_TIPS_PER_BLOCK: u32 = 2;
 }
 
 // Build genesis storage according to the mock runtime.
@@ -152,5 +236,8 @@ pub fn new_test_ext(balances: Vec<(sr25519::Public, u64)>) -> sp_io::TestExterna
     .assimilate_storage(&mut ext)
     .unwrap();
 
-  ext.into()
+  // The genesis of the pallet is not built, so the storage version is set as a new chain would do
+  let mut ext: sp_io::TestExternalities = ext.into();
+  ext.execute_with(|| tipping::STORAGE_VERSION.put::<TippingTest>());
+  ext
 }
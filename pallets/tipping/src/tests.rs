@@ -6,13 +6,17 @@
 
 use super::{mock::*, *};
 use crate::{constants::MaxTipsPerVerificationContextGet, types::*};
+use codec::{Decode, Encode};
 use core::convert::TryInto;
-use frame_support::*;
+use frame_support::{
+  traits::{GetStorageVersion, Hooks, StorageVersion},
+  *,
+};
 use sp_core::{sr25519, Pair};
 
 use verification::{
   consts::MaxVerificationRequestsPerContextGet,
-  types::{VerificationAction, VerificationContext, VerificationRequest, VerificationStatus},
+  types::{VerificationAction, VerificationContext, VerificationInvalidator, VerificationRequest, VerificationStatus},
 };
 
 fn mock_account(ss58: &str) -> sr25519::Public {
@@ -169,6 +173,97 @@ fn update_settings_for_failed_request() {
   });
 }
 
+#[test]
+fn update_settings_outcome() {
+  new_test_ext(Vec::new()).execute_with(|| {
+    System::set_block_number(1);
+    let holder = mock_account("//Alice");
+    let origin = mock::RuntimeOrigin::signed(holder);
+
+    let context_applied =
+      VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(
+      holder.clone(),
+      context_applied.clone(),
+      VerificationStatus::Success,
+      false,
+    );
+    let context_not_holder = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
+    mock_verification_context_for_tipping::<Test>(
+      mock_account("//Bob"),
+      context_not_holder.clone(),
+      VerificationStatus::Success,
+      false,
+    );
+    let context_not_verified = VerificationContext::UrlForDomain("https://macula.link".into(), "macula.link".into());
+    mock_verification_context_for_tipping::<Test>(
+      holder.clone(),
+      context_not_verified.clone(),
+      VerificationStatus::Pending,
+      false,
+    );
+    let context_invalid_account =
+      VerificationContext::UrlForDomain("https://idiyanale.network".into(), "idiyanale.network".into());
+    mock_verification_context_for_tipping::<Test>(
+      holder.clone(),
+      context_invalid_account.clone(),
+      VerificationStatus::Success,
+      false,
+    );
+
+    let settings_applied = TippingSettings {
+      context: context_applied.clone(),
+      enabled: true,
+      account: None,
+    };
+    let settings_not_holder = TippingSettings {
+      context: context_not_holder.clone(),
+      enabled: true,
+      account: None,
+    };
+    let settings_not_verified = TippingSettings {
+      context: context_not_verified.clone(),
+      enabled: true,
+      account: None,
+    };
+    let settings_invalid_account = TippingSettings {
+      context: context_invalid_account.clone(),
+      enabled: true,
+      account: Some(TippingTest::escrow_account_id()),
+    };
+
+    let res = TippingTest::update_settings(
+      origin,
+      vec![
+        settings_applied.clone(),
+        settings_not_holder.clone(),
+        settings_not_verified.clone(),
+        settings_invalid_account.clone(),
+      ],
+    );
+    assert_ok!(res);
+
+    let settings_applied = TippingSettings {
+      account: Some(holder.clone()),
+      ..settings_applied
+    };
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::TippingSettingsUpdated(
+      holder.clone(),
+      vec![
+        (settings_applied.clone(), TippingSettingsOutcome::Applied),
+        (settings_not_holder, TippingSettingsOutcome::NotHolder),
+        (settings_not_verified, TippingSettingsOutcome::NotVerified),
+        (settings_invalid_account, TippingSettingsOutcome::InvalidAccount),
+      ],
+    )));
+
+    let res = TippingSettingsByAccountIdAndVerificationContext::<Test>::get(holder.clone(), context_applied);
+    assert_eq!(settings_applied, res, "applied settings were not stored correctly");
+    let res = TippingSettingsByAccountIdAndVerificationContext::<Test>::get(holder.clone(), context_invalid_account);
+    assert!(!res.enabled, "settings with an invalid account must not be stored");
+  });
+}
+
 #[test]
 fn tip_error_invalid_verification_context() {
   new_test_ext(Vec::new()).execute_with(|| {
@@ -181,7 +276,7 @@ fn tip_error_invalid_verification_context() {
     mock_verification_context_for_tipping::<Test>(holder, context, VerificationStatus::Success, true);
     let context = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
 
-    let res = TippingTest::tip(origin.clone(), 1u32.into(), context);
+    let res = TippingTest::tip(origin.clone(), 1u32.into(), context, None);
     assert_noop!(res, Error::<Test>::InvalidVerificationContext);
 
     let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
@@ -192,7 +287,7 @@ fn tip_error_invalid_verification_context() {
       true,
     );
 
-    let res = TippingTest::tip(origin, 1u32.into(), context);
+    let res = TippingTest::tip(origin, 1u32.into(), context, None);
     assert_noop!(res, Error::<Test>::InvalidVerificationContext);
   });
 }
@@ -208,7 +303,7 @@ fn tip_error_invalid_configuration() {
     let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
     mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, false);
 
-    let res = TippingTest::tip(origin, 1u32.into(), context);
+    let res = TippingTest::tip(origin, 1u32.into(), context, None);
     assert_noop!(res, Error::<Test>::InvalidConfiguration);
   });
 }
@@ -224,7 +319,7 @@ fn tip_error_insufficient_balance() {
     let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
     mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
 
-    let res = TippingTest::tip(origin, 1u32.into(), context);
+    let res = TippingTest::tip(origin, 1u32.into(), context, None);
     assert_noop!(res, pallet_balances::Error::<Test>::InsufficientBalance);
   });
 }
@@ -240,23 +335,30 @@ fn tip_test() {
     let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
     mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
 
-    let res = TippingTest::tip(origin.clone(), 4u32.into(), context.clone());
+    let res = TippingTest::tip(origin.clone(), 4u32.into(), context.clone(), None);
     assert_ok!(res);
-    let res = TippingTest::tip(origin.clone(), 3u32.into(), context.clone());
+    let res = TippingTest::tip(origin.clone(), 3u32.into(), context.clone(), None);
     assert_ok!(res);
-    let res = TippingTest::tip(origin.clone(), 2u32.into(), context.clone());
+    let res = TippingTest::tip(origin.clone(), 2u32.into(), context.clone(), None);
     assert_ok!(res);
-    let res = TippingTest::tip(origin.clone(), 1u32.into(), context.clone());
+    let res = TippingTest::tip(origin.clone(), 1u32.into(), context.clone(), None);
     assert_ok!(res);
 
     let tips: BoundedVec<
       Tip<BalanceOf<Test>, <Test as frame_system::Config>::AccountId, <Test as pallet_balances::Config>::Balance>,
       MaxTipsPerVerificationContextGet<Test>,
-    > = TipsByAccountIdAndVerificationContext::get(holder.clone(), context);
+    > = TipsByAccountIdAndVerificationContext::get(holder.clone(), context.clone());
     let tip_amounts: Vec<u64> = tips.iter().map(|tip| tip.amount).collect();
     assert_eq!(vec![3, 2, 1], tip_amounts);
     assert_eq!(Balances::free_balance(&tipper), 90);
     assert_eq!(Balances::free_balance(&holder), 10);
+
+    // Aggregates account for the discarded tip as well
+    assert_eq!(4, TippingTest::total(holder.clone(), context.clone()));
+    assert_eq!(10, TippingTest::total_received(holder.clone(), context.clone()));
+    let aggregate = TippingTest::get_tips_aggregate(holder, context, Some(tipper));
+    assert_eq!(4, aggregate.count);
+    assert_eq!(10, aggregate.total_received);
   });
 }
 
@@ -275,34 +377,81 @@ fn rpc_get_tips_sort() {
     > = vec![
       Tip {
         amount: 1u64,
+        net_amount: 1u64,
         sender: tipper.clone(),
         receiver: holder.clone(),
         created_at: 10u64.into(),
         block_number: 10u64.into(),
+        memo: None,
+        state: TipSettlement::Settled,
       },
       Tip {
         amount: 5u64,
+        net_amount: 5u64,
         sender: tipper.clone(),
         receiver: holder.clone(),
         created_at: 20u64.into(),
         block_number: 20u64.into(),
+        memo: None,
+        state: TipSettlement::Settled,
       },
       Tip {
         amount: 2u64,
+        net_amount: 2u64,
         sender: tipper.clone(),
         receiver: holder.clone(),
         created_at: 30u64.into(),
         block_number: 30u64.into(),
+        memo: None,
+        state: TipSettlement::Settled,
       },
     ]
     .try_into()
     .unwrap();
 
-    TipsByAccountIdAndVerificationContext::insert(holder.clone(), context.clone(), tips);
+    tips
+      .into_iter()
+      .for_each(|tip| TippingTest::do_store_tip(&holder, &context, tip));
 
-    let res = TippingTest::get_tips(holder.clone(), context.clone(), 0, 2);
-    let tip_amounts: Vec<u64> = res.iter().map(|tip| tip.amount).collect();
+    let res = TippingTest::get_tips_page(
+      holder.clone(),
+      context.clone(),
+      SortTips::Desc,
+      TipsFilter::default(),
+      None,
+      2,
+    );
+    let tip_amounts: Vec<u64> = res.tips.iter().map(|tip| tip.amount).collect();
     assert_eq!(vec![2, 5], tip_amounts);
+    assert_eq!(Some(0), res.next_cursor);
+
+    let res = TippingTest::get_tips_page(
+      holder.clone(),
+      context.clone(),
+      SortTips::Desc,
+      TipsFilter::default(),
+      res.next_cursor,
+      2,
+    );
+    let tip_amounts: Vec<u64> = res.tips.iter().map(|tip| tip.amount).collect();
+    assert_eq!(vec![1], tip_amounts);
+    assert_eq!(None, res.next_cursor);
+
+    let res = TippingTest::get_tips_page(
+      holder.clone(),
+      context.clone(),
+      SortTips::Asc,
+      TipsFilter::default(),
+      None,
+      2,
+    );
+    let tip_amounts: Vec<u64> = res.tips.iter().map(|tip| tip.amount).collect();
+    assert_eq!(vec![1, 5], tip_amounts);
+    assert_eq!(Some(2), res.next_cursor);
+
+    let res = TippingTest::get_tips(holder.clone(), context.clone(), 1, 10);
+    let tip_amounts: Vec<u64> = res.iter().map(|tip| tip.amount).collect();
+    assert_eq!(vec![5, 1], tip_amounts);
 
     let res = TippingTest::total(holder.clone(), context.clone());
     assert_eq!(3, res);
@@ -311,3 +460,1657 @@ fn rpc_get_tips_sort() {
     assert_eq!(8, res);
   });
 }
+
+#[test]
+fn rpc_get_tips_filter() {
+  let tipper_1 = mock_account("//Alice");
+  let tipper_2 = mock_account("//Charlie");
+  new_test_ext(vec![(tipper_1, 100), (tipper_2, 100)]).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    vec![
+      (tipper_1, 1u64, 10u64),
+      (tipper_2, 5u64, 20u64),
+      (tipper_1, 2u64, 30u64),
+    ]
+    .into_iter()
+    .for_each(|(sender, amount, block)| {
+      let tip = Tip {
+        amount,
+        net_amount: amount,
+        sender,
+        receiver: holder.clone(),
+        created_at: block * 1000,
+        block_number: block,
+        memo: None,
+        state: TipSettlement::Settled,
+      };
+      TippingTest::do_store_tip(&holder, &context, tip)
+    });
+
+    let filter = TipsFilter {
+      sender: Some(tipper_1),
+      ..Default::default()
+    };
+    let res = TippingTest::get_tips_page(holder.clone(), context.clone(), SortTips::Asc, filter, None, 10);
+    let tip_amounts: Vec<u64> = res.tips.iter().map(|tip| tip.amount).collect();
+    assert_eq!(vec![1, 2], tip_amounts);
+
+    let filter = TipsFilter {
+      block_from: Some(15),
+      created_to: Some(25000),
+      ..Default::default()
+    };
+    let res = TippingTest::get_tips_page(holder.clone(), context.clone(), SortTips::Asc, filter, None, 10);
+    let tip_amounts: Vec<u64> = res.tips.iter().map(|tip| tip.amount).collect();
+    assert_eq!(vec![5], tip_amounts);
+
+    let filter = TipsFilter {
+      min_amount: Some(2),
+      ..Default::default()
+    };
+    let res = TippingTest::get_tips_page(holder.clone(), context.clone(), SortTips::Desc, filter.clone(), None, 1);
+    let tip_amounts: Vec<u64> = res.tips.iter().map(|tip| tip.amount).collect();
+    assert_eq!(vec![2], tip_amounts);
+    assert_eq!(Some(1), res.next_cursor);
+
+    // A new tip does not shift the next page
+    let tip = Tip {
+      amount: 7u64,
+      net_amount: 7u64,
+      sender: tipper_2,
+      receiver: holder.clone(),
+      created_at: 40000,
+      block_number: 40,
+      memo: None,
+      state: TipSettlement::Settled,
+    };
+    TippingTest::do_store_tip(&holder, &context, tip);
+
+    let res = TippingTest::get_tips_page(holder, context, SortTips::Desc, filter, res.next_cursor, 1);
+    let tip_amounts: Vec<u64> = res.tips.iter().map(|tip| tip.amount).collect();
+    assert_eq!(vec![5], tip_amounts);
+    assert_eq!(None, res.next_cursor);
+  });
+}
+
+#[test]
+fn rpc_get_tips_by_sender() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let holder_1 = mock_account("//Bob");
+    let holder_2 = mock_account("//Charlie");
+
+    let context_1 = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder_1, context_1.clone(), VerificationStatus::Success, true);
+    let context_2 = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
+    mock_verification_context_for_tipping::<Test>(holder_2, context_2.clone(), VerificationStatus::Success, true);
+
+    vec![
+      (holder_1, &context_1, 1u64, 10u64),
+      (holder_2, &context_2, 2u64, 20u64),
+      (holder_1, &context_1, 3u64, 30u64),
+    ]
+    .into_iter()
+    .for_each(|(holder, context, amount, block)| {
+      let tip = Tip {
+        amount,
+        net_amount: amount,
+        sender: tipper,
+        receiver: holder,
+        created_at: block * 1000,
+        block_number: block,
+        memo: None,
+        state: TipSettlement::Settled,
+      };
+      TippingTest::do_store_tip(&holder, context, tip)
+    });
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Desc, None, 10);
+    let sent: Vec<(VerificationContext, u64)> = res
+      .tips
+      .into_iter()
+      .map(|sent| (sent.context, sent.tip.amount))
+      .collect();
+    assert_eq!(
+      vec![(context_1.clone(), 3), (context_2.clone(), 2), (context_1.clone(), 1)],
+      sent
+    );
+    assert_eq!(None, res.next_cursor);
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Asc, None, 1);
+    let sent: Vec<(VerificationContext, u64)> = res
+      .tips
+      .into_iter()
+      .map(|sent| (sent.context, sent.tip.amount))
+      .collect();
+    assert_eq!(vec![(context_1.clone(), 1)], sent);
+    assert!(res.next_cursor.is_some());
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Asc, res.next_cursor, 1);
+    let sent: Vec<(VerificationContext, u64)> = res
+      .tips
+      .into_iter()
+      .map(|sent| (sent.context, sent.tip.amount))
+      .collect();
+    assert_eq!(vec![(context_2.clone(), 2)], sent);
+
+    // A tip sent after the first pages were read does not shift the following ones
+    let tip = Tip {
+      amount: 4,
+      net_amount: 4,
+      sender: tipper,
+      receiver: holder_2,
+      created_at: 500,
+      block_number: 5,
+      memo: None,
+      state: TipSettlement::Settled,
+    };
+    TippingTest::do_store_tip(&holder_2, &context_2, tip);
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Asc, res.next_cursor, 1);
+    let sent: Vec<(VerificationContext, u64)> = res
+      .tips
+      .into_iter()
+      .map(|sent| (sent.context, sent.tip.amount))
+      .collect();
+    assert_eq!(vec![(context_1.clone(), 3)], sent);
+    assert_eq!(None, res.next_cursor);
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Desc, None, 2);
+    let sent: Vec<(VerificationContext, u64)> = res
+      .tips
+      .into_iter()
+      .map(|sent| (sent.context, sent.tip.amount))
+      .collect();
+    assert_eq!(vec![(context_1.clone(), 3), (context_2.clone(), 2)], sent);
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Desc, res.next_cursor, 2);
+    let sent: Vec<(VerificationContext, u64)> = res
+      .tips
+      .into_iter()
+      .map(|sent| (sent.context, sent.tip.amount))
+      .collect();
+    assert_eq!(vec![(context_1, 1), (context_2, 4)], sent);
+    assert_eq!(None, res.next_cursor);
+  });
+}
+
+#[test]
+fn rpc_get_tips_by_sender_created_at_the_same_time() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    vec![1u32, 2u32, 3u32].into_iter().for_each(|amount| {
+      assert_ok!(TippingTest::tip(
+        mock::RuntimeOrigin::signed(tipper),
+        amount.into(),
+        context.clone(),
+        None
+      ));
+    });
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Asc, None, 2);
+    let amounts: Vec<u64> = res.tips.iter().map(|sent| sent.tip.amount).collect();
+    assert_eq!(vec![1, 2], amounts);
+
+    // A tip to another context, created at the same time, does not shift the following pages
+    let other_holder = mock_account("//Charlie");
+    let other_context = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
+    mock_verification_context_for_tipping::<Test>(
+      other_holder,
+      other_context.clone(),
+      VerificationStatus::Success,
+      true,
+    );
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(tipper),
+      4u32.into(),
+      other_context.clone(),
+      None
+    ));
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Asc, res.next_cursor, 2);
+    let sent: Vec<(VerificationContext, u64)> = res
+      .tips
+      .into_iter()
+      .map(|sent| (sent.context, sent.tip.amount))
+      .collect();
+    assert_eq!(vec![(context, 3), (other_context, 4)], sent);
+    assert_eq!(None, res.next_cursor);
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Desc, None, 1);
+    let amounts: Vec<u64> = res.tips.iter().map(|sent| sent.tip.amount).collect();
+    assert_eq!(vec![4], amounts);
+
+    let res = TippingTest::get_tips_by_sender(tipper, SortTips::Desc, res.next_cursor, 5);
+    let amounts: Vec<u64> = res.tips.iter().map(|sent| sent.tip.amount).collect();
+    assert_eq!(vec![3, 2, 1], amounts);
+    assert_eq!(None, res.next_cursor);
+  });
+}
+
+#[test]
+fn rpc_top_supporters() {
+  let tipper_1 = mock_account("//Alice");
+  let tipper_2 = mock_account("//Charlie");
+  let tipper_3 = mock_account("//Dave");
+  new_test_ext(vec![(tipper_1, 100), (tipper_2, 100), (tipper_3, 100)]).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    vec![(tipper_1, 5u32), (tipper_2, 3u32), (tipper_3, 4u32), (tipper_2, 3u32)]
+      .into_iter()
+      .for_each(|(tipper, amount)| {
+        assert_ok!(TippingTest::tip(
+          mock::RuntimeOrigin::signed(tipper),
+          amount.into(),
+          context.clone(),
+          None
+        ));
+      });
+
+    let res = TippingTest::top_supporters(holder, context, 2);
+    let supporters: Vec<(sr25519::Public, u64, u64)> = res
+      .into_iter()
+      .map(|supporter| (supporter.account, supporter.tips.total_received, supporter.tips.count))
+      .collect();
+    assert_eq!(vec![(tipper_2, 6, 2), (tipper_1, 5, 1)], supporters);
+  });
+}
+
+#[test]
+fn tip_with_memo() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let memo: Memo = "thanks for release 2.0".as_bytes().to_vec().try_into().unwrap();
+    let res = TippingTest::tip(origin, 10u32.into(), context.clone(), Some(memo.clone()));
+    assert_ok!(res);
+
+    let res = TippingTest::get_tips_page(holder.clone(), context, SortTips::Desc, TipsFilter::default(), None, 1);
+    assert_eq!(Some(memo.clone()), res.tips[0].memo);
+    assert_eq!(Balances::free_balance(&tipper), 90 - memo.len() as u64);
+    assert_eq!(Balances::free_balance(&holder), 9);
+  });
+}
+
+#[test]
+fn tip_with_protocol_fee() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    System::set_block_number(1);
+    let res = TippingTest::tip(origin, 50u32.into(), context.clone(), None);
+    assert_ok!(res);
+
+    let tip = Tip {
+      amount: 50,
+      net_amount: 45,
+      sender: tipper,
+      receiver: holder,
+      created_at: 1,
+      block_number: 1,
+      memo: None,
+      state: TipSettlement::Settled,
+    };
+    let res = TippingTest::get_tips_page(
+      holder.clone(),
+      context.clone(),
+      SortTips::Desc,
+      TipsFilter::default(),
+      None,
+      1,
+    );
+    assert_eq!(vec![tip.clone()], res.tips);
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::TipCreated(tipper, holder, tip)));
+
+    assert_eq!(45, TippingTest::total_received(holder.clone(), context));
+    assert_eq!(Balances::free_balance(&tipper), 50);
+    assert_eq!(Balances::free_balance(&holder), 45);
+    assert_eq!(Balances::free_balance(&TREASURY), 5);
+  });
+}
+
+#[test]
+fn tip_remote_test() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    System::set_block_number(1);
+    let res = TippingTest::tip_remote(origin, 50u32.into(), context.clone(), None);
+    assert_ok!(res);
+
+    let tip = Tip {
+      amount: 50,
+      net_amount: 45,
+      sender: tipper,
+      receiver: holder,
+      created_at: 1,
+      block_number: 1,
+      memo: None,
+      state: TipSettlement::Settled,
+    };
+    let res = TippingTest::get_tips_by_sender(tipper.clone(), SortTips::Desc, None, 1);
+    assert_eq!(
+      vec![SentTip {
+        context: context.clone(),
+        tip: tip.clone()
+      }],
+      res.tips
+    );
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::RemoteTipCreated(
+      tipper, holder, tip,
+    )));
+    assert_eq!(Some(tipper), TippingTest::remote_location_by_account_id(tipper));
+
+    assert_eq!(45, TippingTest::total_received(holder.clone(), context));
+    assert_eq!(Balances::free_balance(&tipper), 50);
+    assert_eq!(Balances::free_balance(&holder), 45);
+  });
+}
+
+#[test]
+fn tip_remote_error_bad_origin() {
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let res = TippingTest::tip_remote(mock::RuntimeOrigin::root(), 50u32.into(), context, None);
+    assert_noop!(res, sp_runtime::traits::BadOrigin);
+  });
+}
+
+#[test]
+fn tip_with_memo_error_insufficient_balance() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 20)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let memo: Memo = "thanks for release 2.0".as_bytes().to_vec().try_into().unwrap();
+    let res = TippingTest::tip(origin, 10u32.into(), context, Some(memo));
+    assert_noop!(res, pallet_balances::Error::<Test>::InsufficientBalance);
+  });
+}
+
+#[test]
+fn migrate_tips() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    StorageVersion::new(0).put::<TippingTest>();
+    let old_tips: migration::v1::OldTips<Test> = vec![migration::v1::OldTip {
+      amount: 1u64,
+      sender: tipper.clone(),
+      receiver: holder.clone(),
+      created_at: 10u64,
+      block_number: 10u64,
+    }]
+    .try_into()
+    .unwrap();
+    storage::unhashed::put(
+      &TipsByAccountIdAndVerificationContext::<Test>::hashed_key_for(holder.clone(), context.clone()),
+      &old_tips,
+    );
+
+    migration::migrate::<Test>();
+
+    let tips = TipsByAccountIdAndVerificationContext::<Test>::get(holder.clone(), context.clone());
+    assert_eq!(
+      vec![Tip {
+        amount: 1u64,
+        net_amount: 1u64,
+        sender: tipper,
+        receiver: holder,
+        created_at: 10u64,
+        block_number: 10u64,
+        memo: None,
+        state: TipSettlement::Settled,
+      }],
+      tips.into_inner()
+    );
+    assert_eq!(1, TippingTest::total(holder.clone(), context.clone()));
+    assert_eq!(1, TippingTest::total_received(holder, context.clone()));
+    assert_eq!(
+      1,
+      TipsBySenderAndVerificationContext::<Test>::get(tipper, context).len()
+    );
+    assert_eq!(StorageVersion::new(5), TippingTest::on_chain_storage_version());
+  });
+}
+
+#[test]
+fn migrate_tips_aggregates_across_blocks() {
+  let alice = mock_account("//Alice");
+  let charlie = mock_account("//Charlie");
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+    let context_a = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    let context_b = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
+    let old_tip = |sender: sr25519::Public, amount: u64, block_number: u64| migration::v4::OldTip {
+      amount,
+      sender,
+      receiver: holder,
+      created_at: block_number * 10,
+      block_number,
+      memo: None,
+    };
+
+    StorageVersion::new(1).put::<TippingTest>();
+    let old_tips: migration::v4::OldTips<Test> =
+      vec![old_tip(alice, 1, 1), old_tip(charlie, 2, 2), old_tip(alice, 4, 3)]
+        .try_into()
+        .unwrap();
+    migration::v4::TipsByAccountIdAndVerificationContext::<Test>::insert(holder, context_a.clone(), old_tips);
+    let old_tips: migration::v4::OldTips<Test> = vec![old_tip(charlie, 8, 4), old_tip(charlie, 16, 5)]
+      .try_into()
+      .unwrap();
+    migration::v4::TipsByAccountIdAndVerificationContext::<Test>::insert(holder, context_b.clone(), old_tips);
+
+    // Every block migrates a single tip, the calls are rejected until the migration completes
+    let mut blocks = 0;
+    while migration::is_migrating::<Test>() {
+      migration::migrate_with_limit::<Test>(5, migration::MAX_MIGRATION_PROOF_SIZE_PER_BLOCK);
+      blocks += 1;
+      assert!(blocks < 50, "the migration does not progress");
+      if migration::is_migrating::<Test>() {
+        assert!(TippingTest::ongoing_migration().is_some());
+        assert_noop!(
+          TippingTest::update_settings(mock::RuntimeOrigin::signed(holder), vec![]),
+          Error::<Test>::StorageMigrationInProgress
+        );
+      }
+    }
+    assert!(blocks > 5);
+    assert_eq!(None, TippingTest::ongoing_migration());
+    assert_eq!(StorageVersion::new(5), TippingTest::on_chain_storage_version());
+
+    // Each tip is accounted for exactly once, even when the migration paused in the middle of an
+    // entry
+    let aggregate = TippingTest::tips_aggregate_by_account_id_and_verification_context(holder, context_a.clone());
+    assert_eq!((3, 7), (aggregate.count, aggregate.total_received));
+    assert_eq!((1, 3), (aggregate.first_block_number, aggregate.last_block_number));
+    let aggregate =
+      TippingTest::tips_aggregate_by_account_id_and_verification_context_and_sender((holder, context_a.clone(), alice));
+    assert_eq!((2, 5), (aggregate.count, aggregate.total_received));
+    let aggregate = TippingTest::tips_aggregate_by_account_id_and_verification_context_and_sender((
+      holder,
+      context_a.clone(),
+      charlie,
+    ));
+    assert_eq!((1, 2), (aggregate.count, aggregate.total_received));
+    let aggregate = TippingTest::tips_aggregate_by_account_id_and_verification_context(holder, context_b.clone());
+    assert_eq!((2, 24), (aggregate.count, aggregate.total_received));
+    let aggregate = TippingTest::tips_aggregate_by_account_id_and_verification_context_and_sender((
+      holder,
+      context_b.clone(),
+      charlie,
+    ));
+    assert_eq!((2, 24), (aggregate.count, aggregate.total_received));
+
+    assert_eq!(3, TippingTest::total(holder, context_a.clone()));
+    assert_eq!(
+      2,
+      TipsBySenderAndVerificationContext::<Test>::get(alice, context_a.clone()).len()
+    );
+    assert_eq!(
+      1,
+      TipsBySenderAndVerificationContext::<Test>::get(charlie, context_a).len()
+    );
+    assert_eq!(
+      2,
+      TipsBySenderAndVerificationContext::<Test>::get(charlie, context_b.clone()).len()
+    );
+
+    // Every indexed tip is sequenced once for its sender
+    assert_eq!(3, TippingTest::sent_tips_count_by_sender(charlie));
+    let mut sequences =
+      TippingTest::sent_tip_sequences_by_sender_and_verification_context(charlie, context_a).into_inner();
+    sequences
+      .extend(TippingTest::sent_tip_sequences_by_sender_and_verification_context(charlie, context_b).into_inner());
+    sequences.sort();
+    assert_eq!(vec![0, 1, 2], sequences);
+    let res = TippingTest::get_tips_by_sender(charlie, SortTips::Asc, None, 10);
+    let amounts: Vec<u64> = res.tips.iter().map(|sent| sent.tip.amount).collect();
+    assert_eq!(vec![2, 8, 16], amounts);
+  });
+}
+
+#[test]
+fn migrate_tips_within_proof_size_limit() {
+  let alice = mock_account("//Alice");
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+    let context_a = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    let context_b = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
+    let old_tip = |amount: u64| migration::v4::OldTip {
+      amount,
+      sender: alice,
+      receiver: holder,
+      created_at: 10,
+      block_number: 1,
+      memo: None,
+    };
+
+    StorageVersion::new(3).put::<TippingTest>();
+    let old_tips: migration::v4::OldTips<Test> = vec![old_tip(1), old_tip(2)].try_into().unwrap();
+    migration::v4::TipsByAccountIdAndVerificationContext::<Test>::insert(holder, context_a.clone(), old_tips);
+    let old_tips: migration::v4::OldTips<Test> = vec![old_tip(4)].try_into().unwrap();
+    migration::v4::TipsByAccountIdAndVerificationContext::<Test>::insert(holder, context_b.clone(), old_tips);
+
+    // The operations are not limited, the bytes accessed by a single entry exhaust the budget
+    let mut blocks = 0;
+    while migration::is_migrating::<Test>() {
+      let weight = migration::migrate_with_limit::<Test>(u64::MAX, 1);
+      assert!(weight.proof_size() > 0);
+      blocks += 1;
+      assert!(blocks < 50, "the migration does not progress");
+    }
+    assert!(blocks >= 4);
+    assert_eq!(StorageVersion::new(5), TippingTest::on_chain_storage_version());
+
+    let amounts = |context: VerificationContext| -> Vec<(u64, u64, TipSettlement<u64>)> {
+      TipsByAccountIdAndVerificationContext::<Test>::get(holder, context)
+        .iter()
+        .map(|tip| (tip.amount, tip.net_amount, tip.state))
+        .collect()
+    };
+    assert_eq!(
+      vec![(1, 1, TipSettlement::Settled), (2, 2, TipSettlement::Settled)],
+      amounts(context_a)
+    );
+    assert_eq!(vec![(4, 4, TipSettlement::Settled)], amounts(context_b));
+  });
+}
+
+#[test]
+fn tip_escrow_test() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    let res = TippingTest::tip_escrow(origin.clone(), 10u32.into(), context.clone(), None);
+    assert_ok!(res);
+    let res = TippingTest::tip_escrow(origin, 5u32.into(), context.clone(), None);
+    assert_ok!(res);
+
+    let escrowed_tips = EscrowedTipsByVerificationContext::<Test>::get(context.clone());
+    let escrowed_amounts: Vec<u64> = escrowed_tips.iter().map(|tip| tip.amount).collect();
+    assert_eq!(vec![10, 5], escrowed_amounts);
+    assert_eq!(escrowed_tips[0].expires_at, 10);
+    assert_eq!(15, TippingTest::escrowed_balance(context));
+    assert_eq!(Balances::free_balance(&tipper), 85);
+    assert_eq!(Balances::free_balance(&TippingTest::escrow_account_id()), 15);
+  });
+}
+
+#[test]
+fn tip_escrow_error_escrow_not_required() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let res = TippingTest::tip_escrow(origin.clone(), 10u32.into(), context, None);
+    assert_noop!(res, Error::<Test>::EscrowNotRequired);
+
+    let res = TippingTest::tip_escrow(origin, 10u32.into(), VerificationContext::Unbounded, None);
+    assert_noop!(res, Error::<Test>::InvalidVerificationContext);
+  });
+}
+
+#[test]
+fn tip_escrow_error_max_escrowed_tips() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    assert_ok!(TippingTest::tip_escrow(
+      origin.clone(),
+      1u32.into(),
+      context.clone(),
+      None
+    ));
+    assert_ok!(TippingTest::tip_escrow(
+      origin.clone(),
+      1u32.into(),
+      context.clone(),
+      None
+    ));
+    let res = TippingTest::tip_escrow(origin, 1u32.into(), context, None);
+    assert_noop!(res, Error::<Test>::MaxEscrowedTipsPerVerificationContextLimitReached);
+  });
+}
+
+#[test]
+fn claim_escrowed_tips_test() {
+  let tipper_1 = mock_account("//Alice");
+  let tipper_2 = mock_account("//Charlie");
+  new_test_ext(vec![(tipper_1, 100), (tipper_2, 100)]).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    System::set_block_number(1);
+    let res = TippingTest::tip_escrow(
+      mock::RuntimeOrigin::signed(tipper_1),
+      10u32.into(),
+      context.clone(),
+      None,
+    );
+    assert_ok!(res);
+    System::set_block_number(5);
+    let res = TippingTest::tip_escrow(
+      mock::RuntimeOrigin::signed(tipper_2),
+      20u32.into(),
+      context.clone(),
+      None,
+    );
+    assert_ok!(res);
+
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    // The first escrowed tip is expired and refunded, the second is claimed
+    System::set_block_number(12);
+    let res = TippingTest::claim_escrowed_tips(mock::RuntimeOrigin::signed(holder), context.clone());
+    // Only the escrowed tips actually processed are charged
+    assert_eq!(
+      Some(<() as crate::weights::WeightInfo>::claim_escrowed_tips(2)),
+      res.unwrap().actual_weight
+    );
+
+    let tips = TippingTest::get_tips_page(
+      holder.clone(),
+      context.clone(),
+      SortTips::Desc,
+      TipsFilter::default(),
+      None,
+      10,
+    )
+    .tips;
+    assert_eq!(1, tips.len());
+    assert_eq!(20, tips[0].amount);
+    assert_eq!(18, tips[0].net_amount);
+    assert_eq!(tipper_2, tips[0].sender);
+    assert_eq!(holder, tips[0].receiver);
+    assert_eq!(5, tips[0].block_number);
+
+    assert_eq!(0, TippingTest::escrowed_balance(context));
+    assert_eq!(Balances::free_balance(&tipper_1), 100);
+    assert_eq!(Balances::free_balance(&tipper_2), 80);
+    assert_eq!(Balances::free_balance(&holder), 18);
+    assert_eq!(Balances::free_balance(&TREASURY), 2);
+    assert_eq!(Balances::free_balance(&TippingTest::escrow_account_id()), 0);
+  });
+}
+
+#[test]
+fn claim_escrowed_tips_error_not_holder() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    let res = TippingTest::tip_escrow(mock::RuntimeOrigin::signed(tipper), 10u32.into(), context.clone(), None);
+    assert_ok!(res);
+
+    let res = TippingTest::claim_escrowed_tips(mock::RuntimeOrigin::signed(holder), context.clone());
+    assert_noop!(res, Error::<Test>::InvalidVerificationContext);
+
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let res = TippingTest::claim_escrowed_tips(mock::RuntimeOrigin::signed(tipper), context);
+    assert_noop!(res, Error::<Test>::NotVerificationHolder);
+  });
+}
+
+#[test]
+fn refund_escrowed_tips_test() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    System::set_block_number(1);
+    assert_ok!(TippingTest::tip_escrow(
+      origin.clone(),
+      10u32.into(),
+      context.clone(),
+      None
+    ));
+    System::set_block_number(5);
+    assert_ok!(TippingTest::tip_escrow(
+      origin.clone(),
+      20u32.into(),
+      context.clone(),
+      None
+    ));
+
+    // Nothing is expired yet
+    let res = TippingTest::refund_escrowed_tips(origin.clone(), context.clone());
+    assert_eq!(
+      Some(<() as crate::weights::WeightInfo>::refund_escrowed_tips(0)),
+      res.unwrap().actual_weight
+    );
+    assert_eq!(30, TippingTest::escrowed_balance(context.clone()));
+
+    System::set_block_number(12);
+    let res = TippingTest::refund_escrowed_tips(origin, context.clone());
+    assert_eq!(
+      Some(<() as crate::weights::WeightInfo>::refund_escrowed_tips(1)),
+      res.unwrap().actual_weight
+    );
+    assert_eq!(20, TippingTest::escrowed_balance(context));
+    assert_eq!(Balances::free_balance(&tipper), 80);
+  });
+}
+
+#[test]
+fn set_supporter_badge_test() {
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let res = TippingTest::set_supporter_badge(mock::RuntimeOrigin::signed(holder), context.clone(), Some(5));
+    assert_ok!(res);
+    assert_eq!(
+      Some(5),
+      SupporterBadgeThresholdByAccountIdAndVerificationContext::<Test>::get(holder, context.clone())
+    );
+
+    let res = TippingTest::set_supporter_badge(mock::RuntimeOrigin::signed(holder), context.clone(), None);
+    assert_ok!(res);
+    assert_eq!(
+      None,
+      SupporterBadgeThresholdByAccountIdAndVerificationContext::<Test>::get(holder, context)
+    );
+  });
+}
+
+#[test]
+fn set_supporter_badge_error_not_holder() {
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+    let other = mock_account("//Alice");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let res = TippingTest::set_supporter_badge(mock::RuntimeOrigin::signed(other), context, Some(5));
+    assert_noop!(res, Error::<Test>::NotVerificationHolder);
+  });
+}
+
+#[test]
+fn tip_with_supporter_badge() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+    assert_ok!(TippingTest::set_supporter_badge(
+      mock::RuntimeOrigin::signed(holder),
+      context.clone(),
+      Some(5)
+    ));
+
+    System::set_block_number(1);
+
+    // Below the threshold, no badge is minted
+    assert_ok!(TippingTest::tip(origin.clone(), 4u32.into(), context.clone(), None));
+    assert_eq!(None, SupporterBadgeCollectionByAccountId::<Test>::get(holder));
+
+    assert_ok!(TippingTest::tip(origin.clone(), 5u32.into(), context.clone(), None));
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::SupporterBadgeMinted(
+      tipper,
+      holder,
+      context.clone(),
+      100,
+      0,
+    )));
+
+    assert_ok!(TippingTest::tip(origin, 10u32.into(), context.clone(), None));
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::SupporterBadgeMinted(
+      tipper, holder, context, 100, 1,
+    )));
+
+    assert_eq!(
+      Some(SupporterBadgeCollection {
+        collection_id: 100,
+        next_item_id: 2
+      }),
+      SupporterBadgeCollectionByAccountId::<Test>::get(holder)
+    );
+    assert_eq!(101, NextSupporterBadgeCollectionId::<Test>::get());
+  });
+}
+
+#[test]
+fn tip_with_supporter_badge_long_context() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let domain = format!("{}.anagolay.network", "subdomain".repeat(20));
+    let url = format!("https://{}/{}", domain, "path/".repeat(40));
+    let context = VerificationContext::UrlForDomain(url.as_str().into(), domain.as_str().into());
+    assert!(context.encode().len() > BADGE_METADATA_LIMIT);
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+    assert_ok!(TippingTest::set_supporter_badge(
+      mock::RuntimeOrigin::signed(holder),
+      context.clone(),
+      Some(5)
+    ));
+
+    System::set_block_number(1);
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(tipper),
+      5u32.into(),
+      context.clone(),
+      None
+    ));
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::SupporterBadgeMinted(
+      tipper,
+      holder,
+      context.clone(),
+      100,
+      0,
+    )));
+
+    let minted = MINTED_BADGE_METADATA.with(|minted| minted.borrow().clone());
+    assert_eq!(1, minted.len());
+    assert!(minted[0].len() <= BADGE_METADATA_LIMIT);
+    let metadata = SupporterBadgeMetadata::<u64, u64>::decode(&mut &minted[0][..]).unwrap();
+    assert_eq!(
+      SupporterBadgeMetadata {
+        context_hash: sp_io::hashing::blake2_256(&context.encode()),
+        tip_index: 0,
+        amount: 5,
+        created_at: 1,
+        block_number: 1,
+      },
+      metadata
+    );
+  });
+}
+
+#[test]
+fn remove_settings_test() {
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+    SupporterBadgeThresholdByAccountIdAndVerificationContext::<Test>::insert(holder, context.clone(), 5);
+
+    let res = TippingTest::remove_settings(mock::RuntimeOrigin::signed(holder), context.clone());
+    assert_ok!(res);
+
+    assert!(!TippingSettingsByAccountIdAndVerificationContext::<Test>::contains_key(
+      holder,
+      context.clone()
+    ));
+    assert!(!SupporterBadgeThresholdByAccountIdAndVerificationContext::<Test>::contains_key(holder, context.clone()));
+    assert_noop!(
+      TippingTest::tip(mock::RuntimeOrigin::signed(holder), 1u32.into(), context, None),
+      Error::<Test>::InvalidConfiguration
+    );
+  });
+}
+
+#[test]
+fn remove_settings_error_not_found() {
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+    let other = mock_account("//Alice");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let res = TippingTest::remove_settings(mock::RuntimeOrigin::signed(other), context);
+    assert_noop!(res, Error::<Test>::TippingSettingsNotFound);
+  });
+}
+
+#[test]
+fn verification_invalidation_removes_settings() {
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    let request =
+      verification::pallet::VerificationRequestByAccountIdAndVerificationContext::<Test>::get(holder, context.clone())
+        .unwrap();
+    TippingVerificationInvalidator::<Test>::invalidate(&request).unwrap();
+
+    assert!(!TippingSettingsByAccountIdAndVerificationContext::<Test>::contains_key(
+      holder, context
+    ));
+  });
+}
+
+#[test]
+fn matching_round_payout() {
+  let charlie = mock_account("//Charlie");
+  let dave = mock_account("//Dave");
+  let eve = mock_account("//Eve");
+  let ferdie = mock_account("//Ferdie");
+  let donor = mock_account("//Donor");
+  new_test_ext(vec![
+    (charlie, 100),
+    (dave, 100),
+    (eve, 100),
+    (ferdie, 200),
+    (donor, 2000),
+  ])
+  .execute_with(|| {
+    System::set_block_number(1);
+
+    let holder_a = mock_account("//Alice");
+    let context_a = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder_a, context_a.clone(), VerificationStatus::Success, true);
+    let holder_b = mock_account("//Bob");
+    let context_b = VerificationContext::UrlForDomain("https://kelp.digital".into(), "kelp.digital".into());
+    mock_verification_context_for_tipping::<Test>(holder_b, context_b.clone(), VerificationStatus::Success, true);
+    let holder_c = mock_account("//Grace");
+    let context_c = VerificationContext::UrlForDomain("https://macula.link".into(), "macula.link".into());
+    mock_verification_context_for_tipping::<Test>(holder_c, context_c.clone(), VerificationStatus::Success, true);
+
+    // Only Charlie, Dave and Eve are verified, hence eligible to contribute
+    for (tipper, url) in [
+      (charlie, "https://charlie.dev"),
+      (dave, "https://dave.dev"),
+      (eve, "https://eve.dev"),
+    ] {
+      let context = VerificationContext::UrlForDomain(url.into(), url.into());
+      mock_verification_context_for_tipping::<Test>(tipper, context, VerificationStatus::Success, false);
+    }
+
+    assert_ok!(TippingTest::create_matching_round(mock::RuntimeOrigin::root(), 1, 10));
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::MatchingRoundCreated(0, 1, 10)));
+    assert_ok!(TippingTest::fund_matching_round(
+      mock::RuntimeOrigin::signed(donor),
+      0,
+      1001
+    ));
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::MatchingRoundFunded(
+      0, donor, 1001,
+    )));
+
+    // Two senders contribute 4 each to context A, one sender contributes 9 to context B in two tips
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(charlie),
+      4,
+      context_a.clone(),
+      None
+    ));
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(dave),
+      4,
+      context_a.clone(),
+      None
+    ));
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(eve),
+      4,
+      context_b.clone(),
+      None
+    ));
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(eve),
+      5,
+      context_b.clone(),
+      None
+    ));
+    // The tips of a sender that is not verified are not accounted
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(ferdie),
+      100,
+      context_a.clone(),
+      None
+    ));
+    // The maximum number of contexts in the round is reached, context C is not accounted
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(charlie),
+      4,
+      context_c.clone(),
+      None
+    ));
+
+    assert_eq!(
+      Some(MatchingRoundTally {
+        sqrt_sum: 4,
+        total: 8,
+        contributors: 2,
+      }),
+      TippingTest::matching_round_tally(0, context_a.clone())
+    );
+    assert_eq!(
+      Some(MatchingRoundTally {
+        sqrt_sum: 3,
+        total: 9,
+        contributors: 1,
+      }),
+      TippingTest::matching_round_tally(0, context_b.clone())
+    );
+    assert_eq!(None, TippingTest::matching_round_tally(0, context_c));
+    assert_eq!(9, TippingTest::matching_round_contribution((0, context_b.clone(), eve)));
+
+    System::set_block_number(10);
+    let res = TippingTest::finalize_matching_round(mock::RuntimeOrigin::signed(donor), 0);
+    assert_noop!(res, Error::<Test>::MatchingRoundNotEnded);
+
+    System::set_block_number(11);
+    assert_ok!(TippingTest::finalize_matching_round(
+      mock::RuntimeOrigin::signed(donor),
+      0
+    ));
+
+    // Weights are 4² = 16 for context A and 3² = 9 for context B: the pool of 1001 is split in
+    // 640 and 360, the remainder goes to the protocol fee handler
+    System::assert_has_event(mock::RuntimeEvent::TippingTest(Event::MatchingFundsDistributed(
+      0, context_a, holder_a, 640,
+    )));
+    System::assert_has_event(mock::RuntimeEvent::TippingTest(Event::MatchingFundsDistributed(
+      0, context_b, holder_b, 360,
+    )));
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::MatchingRoundFinalized(0, 1000)));
+
+    assert_eq!(Balances::free_balance(&holder_a), 4 + 4 + 90 + 640);
+    assert_eq!(Balances::free_balance(&holder_b), 4 + 5 + 360);
+    assert_eq!(Balances::free_balance(&TREASURY), 10 + 1);
+    assert_eq!(Balances::free_balance(&TippingTest::matching_round_account_id(0)), 0);
+    assert_eq!(Some(1000), TippingTest::matching_round(0).unwrap().distributed);
+    assert_eq!(None, TippingTest::current_matching_round());
+  });
+}
+
+#[test]
+fn matching_round_errors() {
+  let donor = mock_account("//Donor");
+  new_test_ext(vec![(donor, 2000)]).execute_with(|| {
+    System::set_block_number(1);
+
+    let res = TippingTest::create_matching_round(mock::RuntimeOrigin::signed(donor), 1, 10);
+    assert_noop!(res, sp_runtime::traits::BadOrigin);
+    let res = TippingTest::create_matching_round(mock::RuntimeOrigin::root(), 10, 10);
+    assert_noop!(res, Error::<Test>::InvalidMatchingRound);
+    let res = TippingTest::fund_matching_round(mock::RuntimeOrigin::signed(donor), 0, 100);
+    assert_noop!(res, Error::<Test>::MatchingRoundNotFound);
+
+    assert_ok!(TippingTest::create_matching_round(mock::RuntimeOrigin::root(), 1, 10));
+    let res = TippingTest::create_matching_round(mock::RuntimeOrigin::root(), 1, 10);
+    assert_noop!(res, Error::<Test>::MatchingRoundInProgress);
+
+    System::set_block_number(11);
+    assert_ok!(TippingTest::finalize_matching_round(
+      mock::RuntimeOrigin::signed(donor),
+      0
+    ));
+    assert_eq!(Some(0), TippingTest::matching_round(0).unwrap().distributed);
+
+    let res = TippingTest::finalize_matching_round(mock::RuntimeOrigin::signed(donor), 0);
+    assert_noop!(res, Error::<Test>::MatchingRoundAlreadyFinalized);
+    let res = TippingTest::fund_matching_round(mock::RuntimeOrigin::signed(donor), 0, 100);
+    assert_noop!(res, Error::<Test>::MatchingRoundAlreadyFinalized);
+
+    assert_ok!(TippingTest::create_matching_round(mock::RuntimeOrigin::root(), 12, 20));
+    assert_eq!(Some(1), TippingTest::current_matching_round());
+  });
+}
+
+#[test]
+fn tip_with_refund_window_settles() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+    assert_ok!(TippingTest::set_refund_window(
+      mock::RuntimeOrigin::signed(holder),
+      context.clone(),
+      Some(3)
+    ));
+
+    System::set_block_number(1);
+
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(tipper),
+      10u32.into(),
+      context.clone(),
+      None
+    ));
+    System::assert_has_event(mock::RuntimeEvent::TippingTest(Event::TipPending(0, tipper, 4)));
+
+    // The amount is held on the tipper until the tip settles
+    assert_eq!(Balances::free_balance(&tipper), 90);
+    assert_eq!(Balances::reserved_balance(&tipper), 10);
+    assert_eq!(Balances::free_balance(&holder), 0);
+    let tips = TipsByAccountIdAndVerificationContext::<Test>::get(holder, context.clone());
+    assert_eq!(TipSettlement::Pending(4), tips[0].state);
+    assert_eq!(9, TippingTest::total_received(holder, context.clone()));
+
+    TippingTest::on_initialize(3);
+    assert_eq!(Balances::reserved_balance(&tipper), 10);
+
+    TippingTest::on_initialize(4);
+    assert_eq!(Balances::free_balance(&tipper), 90);
+    assert_eq!(Balances::reserved_balance(&tipper), 0);
+    assert_eq!(Balances::free_balance(&holder), 9);
+    assert_eq!(Balances::free_balance(&TREASURY), 1);
+    assert_eq!(None, PendingTips::<Test>::get(0));
+
+    let tip = Tip {
+      amount: 10u64,
+      net_amount: 9u64,
+      sender: tipper,
+      receiver: holder,
+      created_at: 1u64,
+      block_number: 1u64,
+      memo: None,
+      state: TipSettlement::Settled,
+    };
+    assert_eq!(
+      vec![tip.clone()],
+      TipsByAccountIdAndVerificationContext::<Test>::get(holder, context.clone()).into_inner()
+    );
+    assert_eq!(
+      vec![tip.clone()],
+      TipsBySenderAndVerificationContext::<Test>::get(tipper, context.clone()).into_inner()
+    );
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::TipSettled(
+      0, holder, context, tip,
+    )));
+  });
+}
+
+#[test]
+fn tip_with_refund_window_settles_slashed_reserve() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+    assert_ok!(TippingTest::set_refund_window(
+      mock::RuntimeOrigin::signed(holder),
+      context.clone(),
+      Some(3)
+    ));
+
+    System::set_block_number(1);
+    assert_ok!(TippingTest::tip(
+      mock::RuntimeOrigin::signed(tipper),
+      10u32.into(),
+      context.clone(),
+      None
+    ));
+
+    // Part of the reserved balance is slashed before the tip settles
+    let _ = <Balances as traits::ReservableCurrency<_>>::slash_reserved(&tipper, 5);
+    assert_eq!(Balances::reserved_balance(&tipper), 5);
+
+    TippingTest::on_initialize(4);
+    assert_eq!(Balances::reserved_balance(&tipper), 0);
+    assert_eq!(Balances::free_balance(&holder), 5);
+    assert_eq!(Balances::free_balance(&TREASURY), 0);
+
+    // The tip is recorded with the amounts actually moved
+    let tip = Tip {
+      amount: 5u64,
+      net_amount: 5u64,
+      sender: tipper,
+      receiver: holder,
+      created_at: 1u64,
+      block_number: 1u64,
+      memo: None,
+      state: TipSettlement::Settled,
+    };
+    assert_eq!(
+      vec![tip.clone()],
+      TipsByAccountIdAndVerificationContext::<Test>::get(holder, context.clone()).into_inner()
+    );
+    assert_eq!(
+      vec![tip.clone()],
+      TipsBySenderAndVerificationContext::<Test>::get(tipper, context.clone()).into_inner()
+    );
+    assert_eq!(5, TippingTest::total_received(holder, context.clone()));
+    assert_eq!(
+      5,
+      TippingTest::get_tips_aggregate(holder, context.clone(), Some(tipper)).total_received
+    );
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::TipSettled(
+      0, holder, context, tip,
+    )));
+  });
+}
+
+#[test]
+fn settle_pending_tips_backlog_after_migration() {
+  let tippers: Vec<sr25519::Public> = vec!["//Alice", "//Charlie", "//Dave", "//Eve"]
+    .into_iter()
+    .map(mock_account)
+    .collect();
+  new_test_ext(tippers.iter().map(|tipper| (*tipper, 100)).collect()).execute_with(|| {
+    let holder = mock_account("//Bob");
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+
+    // Two blocks full of pending tips
+    tippers.iter().enumerate().for_each(|(index, tipper)| {
+      let settles_at = 4 + index as u64 / 2;
+      let tip = Tip {
+        amount: 10u64,
+        net_amount: 10u64,
+        sender: *tipper,
+        receiver: holder,
+        created_at: 1u64,
+        block_number: 1u64,
+        memo: None,
+        state: TipSettlement::Pending(settles_at),
+      };
+      assert_ok!(<Balances as traits::ReservableCurrency<_>>::reserve(tipper, 10));
+      assert_ok!(TippingTest::do_hold_tip(&holder, &context, tip, settles_at));
+    });
+
+    // The pending tips are not settled while the storage is migrated
+    StorageVersion::new(4).put::<TippingTest>();
+    TippingTest::on_initialize(4);
+    assert!(!migration::is_migrating::<Test>());
+    assert_eq!(Some(3), TippingTest::pending_tips_settled_until());
+    assert_eq!(Balances::free_balance(&holder), 0);
+
+    // The backlog is drained without exceeding the maximum count of tips settled in a block
+    TippingTest::on_initialize(5);
+    assert_eq!(Some(4), TippingTest::pending_tips_settled_until());
+    assert_eq!(Balances::free_balance(&holder), 20);
+    assert_eq!(2, PendingTipsBySettlementBlock::<Test>::get(5).len());
+
+    TippingTest::on_initialize(6);
+    assert_eq!(Some(6), TippingTest::pending_tips_settled_until());
+    assert_eq!(Balances::free_balance(&holder), 40);
+    tippers
+      .iter()
+      .for_each(|tipper| assert_eq!(Balances::reserved_balance(tipper), 0));
+  });
+}
+
+#[test]
+fn cancel_tip_test() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+    assert_ok!(TippingTest::set_refund_window(
+      mock::RuntimeOrigin::signed(holder),
+      context.clone(),
+      Some(3)
+    ));
+
+    System::set_block_number(1);
+
+    assert_ok!(TippingTest::tip(origin.clone(), 10u32.into(), context.clone(), None));
+    assert_noop!(
+      TippingTest::cancel_tip(mock::RuntimeOrigin::signed(holder), 0),
+      Error::<Test>::NotTipSender
+    );
+
+    assert_ok!(TippingTest::cancel_tip(origin.clone(), 0));
+    System::assert_last_event(mock::RuntimeEvent::TippingTest(Event::TipRefunded(
+      0,
+      tipper,
+      context.clone(),
+      10,
+    )));
+    assert_eq!(Balances::free_balance(&tipper), 100);
+    assert_eq!(Balances::reserved_balance(&tipper), 0);
+    assert!(PendingTipsBySettlementBlock::<Test>::get(4).is_empty());
+
+    // The tip stays in the history, while it is no longer accounted as received
+    let tips = TipsByAccountIdAndVerificationContext::<Test>::get(holder, context.clone());
+    assert_eq!(TipSettlement::Refunded, tips[0].state);
+    assert_eq!(0, TippingTest::total_received(holder, context.clone()));
+    assert_eq!(1, TippingTest::total(holder, context.clone()));
+    assert_eq!(
+      0,
+      TippingTest::get_tips_aggregate(holder, context, Some(tipper)).total_received
+    );
+
+    assert_noop!(TippingTest::cancel_tip(origin, 0), Error::<Test>::PendingTipNotFound);
+
+    TippingTest::on_initialize(4);
+    assert_eq!(Balances::free_balance(&tipper), 100);
+    assert_eq!(Balances::free_balance(&holder), 0);
+  });
+}
+
+#[test]
+fn set_refund_window_errors() {
+  new_test_ext(Vec::new()).execute_with(|| {
+    let holder = mock_account("//Bob");
+    let other = mock_account("//Alice");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+
+    assert_noop!(
+      TippingTest::set_refund_window(mock::RuntimeOrigin::signed(other), context.clone(), Some(3)),
+      Error::<Test>::NotVerificationHolder
+    );
+    assert_noop!(
+      TippingTest::set_refund_window(mock::RuntimeOrigin::signed(holder), context.clone(), Some(0)),
+      Error::<Test>::InvalidRefundWindow
+    );
+    assert_noop!(
+      TippingTest::set_refund_window(mock::RuntimeOrigin::signed(holder), context.clone(), Some(6)),
+      Error::<Test>::InvalidRefundWindow
+    );
+
+    assert_ok!(TippingTest::set_refund_window(
+      mock::RuntimeOrigin::signed(holder),
+      context.clone(),
+      Some(5)
+    ));
+    assert_ok!(TippingTest::remove_settings(
+      mock::RuntimeOrigin::signed(holder),
+      context.clone()
+    ));
+    assert_eq!(
+      None,
+      RefundWindowByAccountIdAndVerificationContext::<Test>::get(holder, context)
+    );
+  });
+}
+
+#[test]
+fn tip_error_max_pending_tips_per_block() {
+  let tipper = mock_account("//Alice");
+  new_test_ext(vec![(tipper, 100)]).execute_with(|| {
+    let origin = mock::RuntimeOrigin::signed(tipper);
+    let holder = mock_account("//Bob");
+
+    let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+    mock_verification_context_for_tipping::<Test>(holder, context.clone(), VerificationStatus::Success, true);
+    assert_ok!(TippingTest::set_refund_window(
+      mock::RuntimeOrigin::signed(holder),
+      context.clone(),
+      Some(3)
+    ));
+
+    assert_ok!(TippingTest::tip(origin.clone(), 10u32.into(), context.clone(), None));
+    assert_ok!(TippingTest::tip(origin.clone(), 10u32.into(), context.clone(), None));
+    assert_noop!(
+      TippingTest::tip(origin, 10u32.into(), context, None),
+      Error::<Test>::MaxPendingTipsPerBlockLimitReached
+    );
+  });
+}
+
+#[test]
+fn tip_remote_from_relay_chain() {
+  use crate::xcm_mock::{parachain, *};
+  use xcm::latest::prelude::*;
+
+  MockNet::reset();
+
+  let holder = BOB;
+  let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+  ParaA::execute_with(|| {
+    mock_verification_context_for_tipping::<parachain::Runtime>(
+      holder.clone(),
+      context.clone(),
+      VerificationStatus::Success,
+      true,
+    );
+  });
+
+  let call = parachain::RuntimeCall::Tipping(crate::Call::tip_remote {
+    amount: 100,
+    context: context.clone(),
+    memo: None,
+  });
+  Relay::execute_with(|| {
+    // The relay chain itself cannot tip with its sovereign account
+    assert_ok!(RelayChainPalletXcm::send_xcm(
+      Here,
+      Parachain(1),
+      Xcm(vec![Transact {
+        origin_type: OriginKind::Xcm,
+        require_weight_at_most: TRANSACT_WEIGHT,
+        call: call.encode().into(),
+      }]),
+    ));
+    // While its users tip with their own local account
+    assert_ok!(RelayChainPalletXcm::send_xcm(
+      X1(Junction::AccountId32 {
+        network: NetworkId::Any,
+        id: ALICE.into(),
+      }),
+      Parachain(1),
+      Xcm(vec![Transact {
+        origin_type: OriginKind::Xcm,
+        require_weight_at_most: TRANSACT_WEIGHT,
+        call: call.encode().into(),
+      }]),
+    ));
+  });
+
+  ParaA::execute_with(|| {
+    let location = relay_account_location(ALICE);
+    let sender = remote_account_id(location.clone());
+    assert_ne!(sender, location_account_id(MultiLocation::parent()));
+    let tip = Tip {
+      amount: 100,
+      net_amount: 90,
+      sender: sender.clone(),
+      receiver: holder.clone(),
+      created_at: 1,
+      block_number: 1,
+      memo: None,
+      state: TipSettlement::Settled,
+    };
+    parachain::System::assert_has_event(parachain::RuntimeEvent::Tipping(Event::RemoteTipCreated(
+      location.clone(),
+      holder.clone(),
+      tip.clone(),
+    )));
+
+    let res = parachain::Tipping::get_tips_by_sender(sender.clone(), SortTips::Desc, None, 1);
+    assert_eq!(vec![SentTip { context, tip }], res.tips);
+    assert_eq!(
+      Some(location),
+      parachain::Tipping::remote_location_by_account_id(&sender)
+    );
+    assert_eq!(parachain::Balances::free_balance(&sender), INITIAL_BALANCE - 100);
+    assert_eq!(parachain::Balances::free_balance(&holder), INITIAL_BALANCE + 90);
+    assert_eq!(
+      parachain::Balances::free_balance(&location_account_id(MultiLocation::parent())),
+      INITIAL_BALANCE
+    );
+  });
+}
+
+#[test]
+fn tip_remote_from_sibling_parachain() {
+  use crate::xcm_mock::{parachain, *};
+  use xcm::latest::prelude::*;
+
+  MockNet::reset();
+
+  let holder = BOB;
+  let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+  ParaA::execute_with(|| {
+    mock_verification_context_for_tipping::<parachain::Runtime>(
+      holder.clone(),
+      context.clone(),
+      VerificationStatus::Success,
+      true,
+    );
+  });
+
+  let call = parachain::RuntimeCall::Tipping(crate::Call::tip_remote {
+    amount: 100,
+    context: context.clone(),
+    memo: None,
+  });
+  ParaB::execute_with(|| {
+    // The sibling parachain itself cannot tip with its sovereign account
+    assert_ok!(ParachainPalletXcm::send_xcm(
+      Here,
+      (Parent, Parachain(1)),
+      Xcm(vec![Transact {
+        origin_type: OriginKind::Xcm,
+        require_weight_at_most: TRANSACT_WEIGHT,
+        call: call.encode().into(),
+      }]),
+    ));
+    // While its users tip with their own local account
+    assert_ok!(ParachainPalletXcm::send_xcm(
+      X1(Junction::AccountId32 {
+        network: NetworkId::Any,
+        id: ALICE.into(),
+      }),
+      (Parent, Parachain(1)),
+      Xcm(vec![Transact {
+        origin_type: OriginKind::Xcm,
+        require_weight_at_most: TRANSACT_WEIGHT,
+        call: call.encode().into(),
+      }]),
+    ));
+  });
+
+  ParaA::execute_with(|| {
+    let location = sibling_account_location(2, ALICE);
+    let sender = remote_account_id(location.clone());
+    let sovereign_account = location_account_id(MultiLocation::new(1, X1(Parachain(2))));
+    assert_ne!(sender, sovereign_account);
+    let tip = Tip {
+      amount: 100,
+      net_amount: 90,
+      sender: sender.clone(),
+      receiver: holder.clone(),
+      created_at: 1,
+      block_number: 1,
+      memo: None,
+      state: TipSettlement::Settled,
+    };
+    parachain::System::assert_has_event(parachain::RuntimeEvent::Tipping(Event::RemoteTipCreated(
+      location.clone(),
+      holder.clone(),
+      tip.clone(),
+    )));
+
+    let res = parachain::Tipping::get_tips_by_sender(sender.clone(), SortTips::Desc, None, 1);
+    assert_eq!(vec![SentTip { context, tip }], res.tips);
+    assert_eq!(
+      Some(location),
+      parachain::Tipping::remote_location_by_account_id(&sender)
+    );
+    assert_eq!(parachain::Balances::free_balance(&sender), INITIAL_BALANCE - 100);
+    assert_eq!(parachain::Balances::free_balance(&holder), INITIAL_BALANCE + 90);
+    assert_eq!(parachain::Balances::free_balance(&sovereign_account), INITIAL_BALANCE);
+  });
+}
+
+#[test]
+fn tip_remote_users_cannot_transact_as_signed() {
+  use crate::xcm_mock::{parachain, *};
+  use xcm::latest::prelude::*;
+
+  MockNet::reset();
+
+  let holder = BOB;
+  let context = VerificationContext::UrlForDomain("https://anagolay.network".into(), "anagolay.network".into());
+  ParaA::execute_with(|| {
+    mock_verification_context_for_tipping::<parachain::Runtime>(
+      holder.clone(),
+      context.clone(),
+      VerificationStatus::Success,
+      true,
+    );
+  });
+
+  let call = parachain::RuntimeCall::Tipping(crate::Call::tip {
+    amount: 100,
+    context,
+    memo: None,
+  });
+  Relay::execute_with(|| {
+    // The hashed account of a remote user is not a signed origin for any other call
+    assert_ok!(RelayChainPalletXcm::send_xcm(
+      X1(Junction::AccountId32 {
+        network: NetworkId::Any,
+        id: ALICE.into(),
+      }),
+      Parachain(1),
+      Xcm(vec![Transact {
+        origin_type: OriginKind::SovereignAccount,
+        require_weight_at_most: TRANSACT_WEIGHT,
+        call: call.encode().into(),
+      }]),
+    ));
+  });
+
+  ParaA::execute_with(|| {
+    let sender = remote_account_id(relay_account_location(ALICE));
+    let res = parachain::Tipping::get_tips_by_sender(sender.clone(), SortTips::Desc, None, 1);
+    assert!(res.tips.is_empty());
+    assert_eq!(parachain::Balances::free_balance(&sender), INITIAL_BALANCE);
+    assert_eq!(parachain::Balances::free_balance(&holder), INITIAL_BALANCE);
+  });
+}
@@ -2,21 +2,88 @@
 
 // Copyright (C) 2019-2023 Anagolay Network.
 
+use anagolay_support::getter_for_hardcoded_constant;
 use codec::{Decode, Encode};
 use core::fmt::Debug;
-use frame_support::pallet_prelude::*;
-use verification::types::VerificationContext;
+use frame_support::{dispatch::DispatchResult, pallet_prelude::*, sp_std::vec::Vec};
+use sp_runtime::traits::{AtLeast32BitUnsigned, IntegerSquareRoot, Saturating, Zero};
+use verification::types::{VerificationContext, VerificationInvalidator, VerificationRequest, VerificationStatus};
+
+getter_for_hardcoded_constant!(MaxMemoLen, u32, 128);
+getter_for_hardcoded_constant!(MaxMigrationKeyLen, u32, 4096);
+
+/// A short message attached by the sender to a [`Tip`]
+pub type Memo = BoundedVec<u8, MaxMemoLenGet>;
 
-/// NOT USED ATM. Keeping it because we might need it in the future.
 /// An enum used in RPCs to indicate the order with which Tips are paged
-#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 #[cfg_attr(feature = "std", serde(rename_all(deserialize = "camelCase")))]
 pub enum SortTips {
+  /// Oldest tips first
   Asc,
+  /// Newest tips first
+  #[default]
   Desc,
 }
 
+/// Filters used in RPCs to restrict the Tips that are paged. Every filter is optional and the
+/// ranges are inclusive
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TipsFilter<Balance: Debug, Account: Debug, BlockNumber: Debug> {
+  /// Only the tips from this sender
+  pub sender: Option<Account>,
+  /// Only the tips created at or after this timestamp
+  pub created_from: Option<u64>,
+  /// Only the tips created at or before this timestamp
+  pub created_to: Option<u64>,
+  /// Only the tips inserted at or after this block
+  pub block_from: Option<BlockNumber>,
+  /// Only the tips inserted at or before this block
+  pub block_to: Option<BlockNumber>,
+  /// Only the tips with at least this amount
+  pub min_amount: Option<Balance>,
+  /// Only the tips with at most this amount
+  pub max_amount: Option<Balance>,
+}
+
+impl<Balance, Account, BlockNumber> TipsFilter<Balance, Account, BlockNumber>
+where
+  Balance: Debug + PartialOrd,
+  Account: Debug + PartialEq,
+  BlockNumber: Debug + PartialOrd,
+{
+  /// Check whether a [`Tip`] satisfies all the filters
+  ///
+  /// # Arguments
+  ///  * tip - The [`Tip`] to check
+  ///
+  /// # Return
+  /// True if the tip satisfies all the filters, false otherwise
+  pub fn matches(&self, tip: &Tip<Balance, Account, BlockNumber>) -> bool {
+    self.sender.as_ref().map_or(true, |sender| *sender == tip.sender) &&
+      self.created_from.map_or(true, |from| tip.created_at >= from) &&
+      self.created_to.map_or(true, |to| tip.created_at <= to) &&
+      self.block_from.as_ref().map_or(true, |from| tip.block_number >= *from) &&
+      self.block_to.as_ref().map_or(true, |to| tip.block_number <= *to) &&
+      self.min_amount.as_ref().map_or(true, |min| tip.amount >= *min) &&
+      self.max_amount.as_ref().map_or(true, |max| tip.amount <= *max)
+  }
+}
+
+/// A page of Tips returned by RPCs, either [`Tip`]s or [`SentTip`]s
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TipsPage<Item: Debug> {
+  /// The tips on the page
+  pub tips: Vec<Item>,
+  /// The cursor to pass to retrieve the next page, if there is one
+  pub next_cursor: Option<u64>,
+}
+
 /// A structure associated with every `VerificationContext`, providing the tipping settings
 #[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
@@ -39,12 +106,42 @@ impl<Account: Debug> Default for TippingSettings<Account> {
   }
 }
 
+/// The outcome of the update of a single [`TippingSettings`], reported back to the caller
+#[derive(Encode, Decode, Clone, Copy, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum TippingSettingsOutcome {
+  /// The settings have been stored
+  Applied,
+  /// The caller is not among the holders of a verification for the context
+  NotHolder,
+  /// The verification request of the caller for the context is not successful
+  NotVerified,
+  /// The configured account cannot receive tips, for instance it is the escrow account
+  InvalidAccount,
+}
+
+/// The settlement state of a [`Tip`]
+#[derive(Encode, Decode, Clone, Copy, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub enum TipSettlement<BlockNumber> {
+  /// The net amount is transferred to the receiver, the tip is final
+  #[default]
+  Settled,
+  /// The amount is held as reserved balance of the sender, who can cancel the tip until it
+  /// settles automatically at the beginning of the provided block
+  Pending(BlockNumber),
+  /// The tip was cancelled by the sender within the refund window and the amount was released
+  Refunded,
+}
+
 /// Structure representing a tip
 #[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo)]
 #[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tip<Balance: Debug, Account: Debug, BlockNumber: Debug> {
-  /// Quantity of tokens tipped
+  /// Quantity of tokens tipped by the sender, including the protocol fee
   pub amount: Balance,
+  /// Quantity of tokens received by the receiver, net of the protocol fee
+  pub net_amount: Balance,
   /// The user that is tipping
   pub sender: Account,
   /// The account that is receiving the tip
@@ -53,4 +150,366 @@ pub struct Tip<Balance: Debug, Account: Debug, BlockNumber: Debug> {
   pub created_at: u64,
   /// Block where the tip was inserted
   pub block_number: BlockNumber,
+  /// Optional message from the sender to the receiver
+  pub memo: Option<Memo>,
+  /// Whether the tip is final, still in its refund window or refunded
+  pub state: TipSettlement<BlockNumber>,
+}
+
+/// The identifier of a [`PendingTip`]
+pub type PendingTipId = u64;
+
+/// Structure representing a tip still in the refund window of its receiver, along with the
+/// [`VerificationContext`] it was addressed to
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct PendingTip<Balance: Debug, Account: Debug, BlockNumber: Debug> {
+  /// The holder of the successful verification of the context
+  pub holder: Account,
+  /// The tipped verification context
+  pub context: VerificationContext,
+  /// The tip, as stored in the history of the context
+  pub tip: Tip<Balance, Account, BlockNumber>,
+}
+
+/// Structure representing a tip held in escrow until the [`VerificationContext`] is verified and
+/// tipping is enabled for it
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct EscrowedTip<Balance: Debug, Account: Debug, BlockNumber: Debug> {
+  /// Quantity of tokens tipped
+  pub amount: Balance,
+  /// The user that is tipping
+  pub sender: Account,
+  /// Timestamp of the tip
+  pub created_at: u64,
+  /// Block where the tip was inserted
+  pub block_number: BlockNumber,
+  /// Block after which the tip can no longer be claimed and is refunded to the sender
+  pub expires_at: BlockNumber,
+  /// Optional message from the sender to the receiver
+  pub memo: Option<Memo>,
+}
+
+/// Structure representing the progress of a storage migration that runs across several blocks
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+pub struct MigrationCursor {
+  /// The raw storage key of the last entry that was completely migrated, if any
+  pub last_key: Option<BoundedVec<u8, MaxMigrationKeyLenGet>>,
+  /// The count of the tips already migrated in the entry following `last_key`
+  pub migrated_tips: u32,
+}
+
+/// Structure representing the running aggregates of the tips received for a
+/// [`VerificationContext`], optionally restricted to a single sender. It is updated upon every
+/// new tip, so it accounts for the whole history of tips even after the oldest are discarded
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct TipsAggregate<Balance: Debug, BlockNumber: Debug> {
+  /// Sum of the net amounts of all the tips
+  pub total_received: Balance,
+  /// Count of all the tips
+  pub count: u64,
+  /// Timestamp of the first tip
+  pub first_created_at: u64,
+  /// Block where the first tip was inserted
+  pub first_block_number: BlockNumber,
+  /// Timestamp of the last tip
+  pub last_created_at: u64,
+  /// Block where the last tip was inserted
+  pub last_block_number: BlockNumber,
+}
+
+impl<Balance, BlockNumber> TipsAggregate<Balance, BlockNumber>
+where
+  Balance: Debug + Copy + Saturating,
+  BlockNumber: Debug + Copy,
+{
+  /// Account for a new [`Tip`] in the aggregate
+  ///
+  /// # Arguments
+  ///  * tip - The [`Tip`] to add
+  pub fn add<Account: Debug>(&mut self, tip: &Tip<Balance, Account, BlockNumber>) {
+    if self.count == 0 {
+      self.first_created_at = tip.created_at;
+      self.first_block_number = tip.block_number;
+    }
+    self.total_received = self.total_received.saturating_add(tip.net_amount);
+    self.count = self.count.saturating_add(1);
+    self.last_created_at = tip.created_at;
+    self.last_block_number = tip.block_number;
+  }
+
+  /// Account for a refunded [`Tip`] in the aggregate. Its net amount is no longer received, while
+  /// the tip is still counted since it stays in the history of the context
+  ///
+  /// # Arguments
+  ///  * tip - The refunded [`Tip`]
+  pub fn refund<Account: Debug>(&mut self, tip: &Tip<Balance, Account, BlockNumber>) {
+    self.total_received = self.total_received.saturating_sub(tip.net_amount);
+  }
+
+  /// Deduct from the aggregate an amount that was accounted for but never received, for instance
+  /// because the reserved balance of a pending [`Tip`] was slashed before it settled
+  ///
+  /// # Arguments
+  ///  * amount - The amount to deduct
+  pub fn deduct(&mut self, amount: Balance) {
+    self.total_received = self.total_received.saturating_sub(amount);
+  }
+}
+
+/// Structure representing a tip given by a sender, along with the [`VerificationContext`] it was
+/// addressed to
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct SentTip<Balance: Debug, Account: Debug, BlockNumber: Debug> {
+  /// The tipped verification context
+  pub context: VerificationContext,
+  /// The tip
+  pub tip: Tip<Balance, Account, BlockNumber>,
+}
+
+/// Structure representing a supporter of a [`VerificationContext`], along with the aggregates of
+/// the tips sent to it
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct Supporter<Balance: Debug, Account: Debug, BlockNumber: Debug> {
+  /// The account of the supporter
+  pub account: Account,
+  /// The aggregates of the tips sent by the supporter
+  pub tips: TipsAggregate<Balance, BlockNumber>,
+}
+
+/// Structure representing the collection of supporter badges of a creator
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebug, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupporterBadgeCollection<CollectionId, ItemId> {
+  /// The identifier of the collection
+  pub collection_id: CollectionId,
+  /// The identifier of the next badge minted in the collection
+  pub next_item_id: ItemId,
+}
+
+/// The metadata of a supporter badge, referencing the tip that earned it. It has a fixed size, so
+/// that it fits the metadata limit of the non-fungible tokens pallet however long the tipped
+/// [`VerificationContext`] is
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebugNoBound, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct SupporterBadgeMetadata<Balance: Debug, BlockNumber: Debug> {
+  /// Blake2 256 hash of the encoded tipped verification context
+  pub context_hash: [u8; 32],
+  /// Index of the tip among all the tips received for the verification context
+  pub tip_index: u64,
+  /// Quantity of tokens tipped
+  pub amount: Balance,
+  /// Timestamp of the tip
+  pub created_at: u64,
+  /// Block where the tip was inserted
+  pub block_number: BlockNumber,
+}
+
+/// A trait that allows to define how the supporter badges are minted. The default implementation
+/// [`NaiveSupporterBadgeMinter`] performs no operation, so that runtimes without a non-fungible
+/// tokens pallet can still use the tipping pallet. However, the pallet configuration allow to
+/// define another implementation of this trait, for instance backed by `pallet_uniques`.
+///
+/// # Type arguments
+/// - T: the runtime `Config`
+pub trait SupporterBadgeMinter<T: frame_system::Config>: Clone {
+  /// The identifier of a collection of badges
+  type CollectionId: Member + Parameter + MaxEncodedLen + Copy + Default + AtLeast32BitUnsigned;
+  /// The identifier of a badge inside its collection
+  type ItemId: Member + Parameter + MaxEncodedLen + Copy + Default + AtLeast32BitUnsigned;
+
+  /// Creates the collection of supporter badges of a creator
+  ///
+  /// # Arguments
+  /// * collection - The identifier of the collection
+  /// * creator - The owner of the collection
+  ///
+  /// # Return
+  /// `DispatchResult` containing Unit type
+  fn create_collection(collection: &Self::CollectionId, creator: &T::AccountId) -> DispatchResult;
+
+  /// Mints a supporter badge into the account of a supporter
+  ///
+  /// # Arguments
+  /// * collection - The identifier of the collection
+  /// * item - The identifier of the badge
+  /// * supporter - The account receiving the badge
+  /// * metadata - The encoded [`SupporterBadgeMetadata`]
+  ///
+  /// # Return
+  /// `DispatchResult` containing Unit type
+  fn mint(
+    collection: &Self::CollectionId,
+    item: &Self::ItemId,
+    supporter: &T::AccountId,
+    metadata: Vec<u8>,
+  ) -> DispatchResult;
+}
+
+/// Default implementation for the supporter badge minter
+#[derive(Clone)]
+pub struct NaiveSupporterBadgeMinter<T: frame_system::Config> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: frame_system::Config> SupporterBadgeMinter<T> for NaiveSupporterBadgeMinter<T> {
+  type CollectionId = u32;
+  type ItemId = u32;
+
+  fn create_collection(_collection: &Self::CollectionId, _creator: &T::AccountId) -> DispatchResult {
+    Ok(())
+  }
+
+  fn mint(
+    _collection: &Self::CollectionId,
+    _item: &Self::ItemId,
+    _supporter: &T::AccountId,
+    _metadata: Vec<u8>,
+  ) -> DispatchResult {
+    Ok(())
+  }
+}
+
+/// Implementation for the verification invalidator that removes the tipping settings of the
+/// invalidated context, so that it can no longer be tipped
+#[derive(Clone)]
+pub struct TippingVerificationInvalidator<T: crate::Config> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: crate::Config> VerificationInvalidator<T> for TippingVerificationInvalidator<T> {
+  /// Called when a verification request turns out to be no longer valid
+  ///
+  /// # Arguments
+  /// * request - The verification request
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn invalidate(request: &VerificationRequest<T::AccountId>) -> Result<(), verification::Error<T>> {
+    if crate::TippingSettingsByAccountIdAndVerificationContext::<T>::contains_key(&request.holder, &request.context) {
+      crate::Pallet::<T>::do_remove_settings(&request.holder, &request.context);
+    }
+    Ok(())
+  }
+}
+
+/// The identifier of a matching round
+pub type MatchingRoundId = u32;
+
+/// Structure representing a quadratic funding matching round: the tips received by the
+/// verification contexts while the round is open are matched with the funds of the round, in
+/// proportion to the square of the sum of the square roots of the individual contributions
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchingRound<Balance: Debug, BlockNumber: Debug> {
+  /// First block where the tips are accounted in the round
+  pub start: BlockNumber,
+  /// Last block where the tips are accounted in the round
+  pub end: BlockNumber,
+  /// Number of verification contexts that received contributions in the round
+  pub contexts: u32,
+  /// The amount distributed to the verification contexts, once the round is finalized
+  pub distributed: Option<Balance>,
+}
+
+/// Structure representing the contributions received by a verification context in a
+/// [`MatchingRound`]
+#[derive(Encode, Decode, Clone, Default, PartialEq, Eq, RuntimeDebugNoBound, MaxEncodedLen, TypeInfo)]
+#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
+pub struct MatchingRoundTally<Balance: Debug> {
+  /// Sum of the square roots of the contributions of each sender
+  pub sqrt_sum: Balance,
+  /// Sum of the contributions
+  pub total: Balance,
+  /// Number of distinct senders that contributed
+  pub contributors: u32,
+}
+
+impl<Balance> MatchingRoundTally<Balance>
+where
+  Balance: Debug + Copy + AtLeast32BitUnsigned,
+{
+  /// Account for a contribution in the tally, given the previous total contributed by the same
+  /// sender
+  ///
+  /// # Arguments
+  ///  * previous - The total previously contributed by the sender in the round
+  ///  * amount - The amount of the new contribution
+  pub fn add(&mut self, previous: Balance, amount: Balance) {
+    if previous.is_zero() {
+      self.contributors = self.contributors.saturating_add(1);
+    }
+    self.sqrt_sum = self
+      .sqrt_sum
+      .saturating_sub(previous.integer_sqrt())
+      .saturating_add(previous.saturating_add(amount).integer_sqrt());
+    self.total = self.total.saturating_add(amount);
+  }
+
+  /// The matching weight of the verification context, that is the square of the sum of the square
+  /// roots of the contributions
+  ///
+  /// # Return
+  /// The weight of the tally
+  pub fn weight(&self) -> Balance {
+    self.sqrt_sum.saturating_mul(self.sqrt_sum)
+  }
+}
+
+/// A trait that allows to define which senders are eligible to contribute to the matching rounds,
+/// providing a hook against sybil attacks. [`NaiveMatchingContributorFilter`] accepts every sender
+/// while [`VerifiedMatchingContributorFilter`] only accepts the senders that hold a successful
+/// verification.
+///
+/// # Type arguments
+/// - T: the runtime `Config`
+pub trait MatchingContributorFilter<T: frame_system::Config>: Clone {
+  /// Maximum count of storage reads performed by [`MatchingContributorFilter::is_eligible`],
+  /// charged in the weight of every tip accounted in the matching rounds
+  const MAX_READS: u64;
+
+  /// Tells whether the contributions of a sender are accounted in the matching rounds
+  ///
+  /// # Arguments
+  /// * sender - The sender of the tip
+  ///
+  /// # Return
+  /// True if the sender is eligible, false otherwise
+  fn is_eligible(sender: &T::AccountId) -> bool;
+}
+
+/// Implementation of the matching contributor filter that accepts every sender
+#[derive(Clone)]
+pub struct NaiveMatchingContributorFilter<T: frame_system::Config> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: frame_system::Config> MatchingContributorFilter<T> for NaiveMatchingContributorFilter<T> {
+  const MAX_READS: u64 = 0;
+
+  fn is_eligible(_sender: &T::AccountId) -> bool {
+    true
+  }
+}
+
+/// Implementation of the matching contributor filter that accepts only the senders holding a
+/// successful verification of any context. Only the first `MAX_READS` verifications of the sender,
+/// in storage order, are checked
+#[derive(Clone)]
+pub struct VerifiedMatchingContributorFilter<T: verification::Config> {
+  _marker: PhantomData<T>,
+}
+
+impl<T: verification::Config> MatchingContributorFilter<T> for VerifiedMatchingContributorFilter<T> {
+  const MAX_READS: u64 = 16;
+
+  fn is_eligible(sender: &T::AccountId) -> bool {
+    verification::VerificationRequestByAccountIdAndVerificationContext::<T>::iter_prefix_values(sender)
+      .take(Self::MAX_READS as usize)
+      .any(|request| request.status == VerificationStatus::Success)
+  }
 }
@@ -45,43 +45,435 @@ use frame_support::{
 
 /// Weight functions needed for tipping.
 pub trait WeightInfo {
-  fn update_settings() -> Weight;
-  fn tip() -> Weight;
+  fn update_settings(s: u32) -> Weight;
+  fn remove_settings() -> Weight;
+  fn tip(m: u32) -> Weight;
+  fn tip_remote(m: u32) -> Weight;
+  fn tip_escrow(m: u32) -> Weight;
+  fn claim_escrowed_tips(c: u32) -> Weight;
+  fn refund_escrowed_tips(c: u32) -> Weight;
+  fn set_supporter_badge() -> Weight;
+  fn create_matching_round() -> Weight;
+  fn fund_matching_round() -> Weight;
+  fn finalize_matching_round(c: u32) -> Weight;
+  fn set_refund_window() -> Weight;
+  fn cancel_tip() -> Weight;
+  fn settle_pending_tips(c: u32) -> Weight;
 }
 
 /// Weights for tipping using the Substrate node and recommended hardware.
 pub struct AnagolayWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for AnagolayWeight<T> {
   // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
-  fn update_settings() -> Weight {
-    Weight::from_ref_time(19_480_000).saturating_add(T::DbWeight::get().reads(1))
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:0 w:1)
+  /// The range of component `s` is `[1, 100]`.
+  fn update_settings(s: u32) -> Weight {
+    Weight::from_ref_time(11_240_000)
+      // Standard Error: 4_318
+      .saturating_add(Weight::from_ref_time(14_905_000).saturating_mul(s as u64))
+      .saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(s as u64)))
+      .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(s as u64)))
+  }
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:0 w:1)
+  // Storage: Tipping RefundWindowByAccountIdAndVerificationContext (r:0 w:1)
+  fn remove_settings() -> Weight {
+    Weight::from_ref_time(23_120_000)
+      .saturating_add(T::DbWeight::get().reads(1))
+      .saturating_add(T::DbWeight::get().writes(3))
   }
   // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
   // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
   // Storage: Timestamp Now (r:1 w:0)
+  // Storage: System Account (r:1 w:1)
   // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
-  fn tip() -> Weight {
-    Weight::from_ref_time(64_690_000)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SentTipsCountBySender (r:1 w:1)
+  // Storage: Tipping SentTipSequencesBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContextAndSender (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeCollectionByAccountId (r:1 w:1)
+  // Storage: Tipping NextSupporterBadgeCollectionId (r:1 w:1)
+  // Storage: Tipping CurrentMatchingRound (r:1 w:0)
+  // Storage: Tipping MatchingRounds (r:1 w:1)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping MatchingRoundTallies (r:1 w:1)
+  // Storage: Tipping MatchingRoundContributions (r:1 w:1)
+  // Storage: Tipping RefundWindowByAccountIdAndVerificationContext (r:1 w:0)
+  /// The range of component `m` is `[0, 128]`.
+  fn tip(m: u32) -> Weight {
+    Weight::from_ref_time(118_640_000)
+      .saturating_add(Weight::from_ref_time(2_140).saturating_mul(m as u64))
+      .saturating_add(T::DbWeight::get().reads(20))
+      .saturating_add(T::DbWeight::get().writes(12))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Timestamp Now (r:1 w:0)
+  // Storage: System Account (r:1 w:1)
+  // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SentTipsCountBySender (r:1 w:1)
+  // Storage: Tipping SentTipSequencesBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContextAndSender (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeCollectionByAccountId (r:1 w:1)
+  // Storage: Tipping NextSupporterBadgeCollectionId (r:1 w:1)
+  // Storage: Tipping CurrentMatchingRound (r:1 w:0)
+  // Storage: Tipping MatchingRounds (r:1 w:1)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping MatchingRoundTallies (r:1 w:1)
+  // Storage: Tipping MatchingRoundContributions (r:1 w:1)
+  // Storage: Tipping RefundWindowByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping RemoteLocationByAccountId (r:0 w:1)
+  /// The range of component `m` is `[0, 128]`.
+  fn tip_remote(m: u32) -> Weight {
+    Weight::from_ref_time(120_910_000)
+      .saturating_add(Weight::from_ref_time(2_160).saturating_mul(m as u64))
+      .saturating_add(T::DbWeight::get().reads(20))
+      .saturating_add(T::DbWeight::get().writes(13))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Timestamp Now (r:1 w:0)
+  // Storage: System Account (r:1 w:1)
+  // Storage: Tipping EscrowedTipsByVerificationContext (r:1 w:1)
+  /// The range of component `m` is `[0, 128]`.
+  fn tip_escrow(m: u32) -> Weight {
+    Weight::from_ref_time(71_250_000)
+      .saturating_add(Weight::from_ref_time(2_080).saturating_mul(m as u64))
+      .saturating_add(T::DbWeight::get().reads(6))
+      .saturating_add(T::DbWeight::get().writes(2))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping EscrowedTipsByVerificationContext (r:1 w:1)
+  // Storage: System Account (r:3 w:3)
+  // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SentTipsCountBySender (r:1 w:1)
+  // Storage: Tipping SentTipSequencesBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContextAndSender (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeCollectionByAccountId (r:1 w:1)
+  // Storage: Tipping NextSupporterBadgeCollectionId (r:1 w:1)
+  /// The range of component `c` is `[0, 1000]`.
+  fn claim_escrowed_tips(c: u32) -> Weight {
+    Weight::from_ref_time(41_350_000)
+      .saturating_add(Weight::from_ref_time(5_490_810).saturating_mul(c as u64))
       .saturating_add(T::DbWeight::get().reads(5))
+      .saturating_add(T::DbWeight::get().reads((11 as u64).saturating_mul(c as u64)))
+      .saturating_add(T::DbWeight::get().writes(2))
+      .saturating_add(T::DbWeight::get().writes((10 as u64).saturating_mul(c as u64)))
+  }
+  // Storage: Tipping EscrowedTipsByVerificationContext (r:1 w:1)
+  // Storage: System Account (r:2 w:2)
+  /// The range of component `c` is `[0, 1000]`.
+  fn refund_escrowed_tips(c: u32) -> Weight {
+    Weight::from_ref_time(12_870_000)
+      .saturating_add(Weight::from_ref_time(2_951_440).saturating_mul(c as u64))
+      .saturating_add(T::DbWeight::get().reads(1))
+      .saturating_add(T::DbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+      .saturating_add(T::DbWeight::get().writes(1))
+      .saturating_add(T::DbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:0 w:1)
+  fn set_supporter_badge() -> Weight {
+    Weight::from_ref_time(27_830_000)
+      .saturating_add(T::DbWeight::get().reads(2))
+      .saturating_add(T::DbWeight::get().writes(1))
+  }
+  // Storage: Tipping CurrentMatchingRound (r:1 w:1)
+  // Storage: Tipping NextMatchingRoundId (r:1 w:1)
+  // Storage: Tipping MatchingRounds (r:0 w:1)
+  fn create_matching_round() -> Weight {
+    Weight::from_ref_time(24_150_000)
+      .saturating_add(T::DbWeight::get().reads(2))
+      .saturating_add(T::DbWeight::get().writes(3))
+  }
+  // Storage: Tipping MatchingRounds (r:1 w:0)
+  // Storage: System Account (r:2 w:2)
+  fn fund_matching_round() -> Weight {
+    Weight::from_ref_time(52_370_000)
+      .saturating_add(T::DbWeight::get().reads(3))
+      .saturating_add(T::DbWeight::get().writes(2))
+  }
+  // Storage: Tipping MatchingRounds (r:1 w:1)
+  // Storage: System Account (r:1 w:1)
+  // Storage: Tipping MatchingRoundTallies (r:1 w:0)
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping CurrentMatchingRound (r:0 w:1)
+  /// The range of component `c` is `[1, 500]`.
+  fn finalize_matching_round(c: u32) -> Weight {
+    Weight::from_ref_time(41_820_000)
+      // Standard Error: 9_671
+      .saturating_add(Weight::from_ref_time(63_284_000).saturating_mul(c as u64))
+      .saturating_add(T::DbWeight::get().reads(3))
+      .saturating_add(T::DbWeight::get().reads((4 as u64).saturating_mul(c as u64)))
+      .saturating_add(T::DbWeight::get().writes(3))
+      .saturating_add(T::DbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping RefundWindowByAccountIdAndVerificationContext (r:0 w:1)
+  fn set_refund_window() -> Weight {
+    Weight::from_ref_time(28_410_000)
+      .saturating_add(T::DbWeight::get().reads(2))
+      .saturating_add(T::DbWeight::get().writes(1))
+  }
+  // Storage: Tipping PendingTips (r:1 w:1)
+  // Storage: Tipping PendingTipsBySettlementBlock (r:1 w:1)
+  // Storage: System Account (r:1 w:1)
+  // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContextAndSender (r:1 w:1)
+  fn cancel_tip() -> Weight {
+    Weight::from_ref_time(64_720_000)
+      .saturating_add(T::DbWeight::get().reads(7))
+      .saturating_add(T::DbWeight::get().writes(7))
+  }
+  // Storage: Tipping PendingTipsBySettlementBlock (r:1 w:1)
+  // Storage: Tipping PendingTips (r:1 w:1)
+  // Storage: System Account (r:2 w:2)
+  // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeCollectionByAccountId (r:1 w:1)
+  // Storage: Tipping NextSupporterBadgeCollectionId (r:1 w:1)
+  // Storage: Tipping CurrentMatchingRound (r:1 w:0)
+  // Storage: Tipping MatchingRounds (r:1 w:1)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping MatchingRoundTallies (r:1 w:1)
+  // Storage: Tipping MatchingRoundContributions (r:1 w:1)
+  /// The range of component `c` is `[0, 100]`.
+  fn settle_pending_tips(c: u32) -> Weight {
+    Weight::from_ref_time(6_930_000)
+      // Standard Error: 12_408
+      .saturating_add(Weight::from_ref_time(97_516_000).saturating_mul(c as u64))
+      .saturating_add(T::DbWeight::get().reads(1))
+      .saturating_add(T::DbWeight::get().reads((14 as u64).saturating_mul(c as u64)))
       .saturating_add(T::DbWeight::get().writes(1))
+      .saturating_add(T::DbWeight::get().writes((10 as u64).saturating_mul(c as u64)))
   }
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
   // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
-  fn update_settings() -> Weight {
-    Weight::from_ref_time(19_480_000).saturating_add(RocksDbWeight::get().reads(1))
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:0 w:1)
+  /// The range of component `s` is `[1, 100]`.
+  fn update_settings(s: u32) -> Weight {
+    Weight::from_ref_time(11_240_000)
+      // Standard Error: 4_318
+      .saturating_add(Weight::from_ref_time(14_905_000).saturating_mul(s as u64))
+      .saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(s as u64)))
+      .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(s as u64)))
+  }
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:0 w:1)
+  // Storage: Tipping RefundWindowByAccountIdAndVerificationContext (r:0 w:1)
+  fn remove_settings() -> Weight {
+    Weight::from_ref_time(23_120_000)
+      .saturating_add(RocksDbWeight::get().reads(1))
+      .saturating_add(RocksDbWeight::get().writes(3))
   }
   // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
   // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
   // Storage: Timestamp Now (r:1 w:0)
+  // Storage: System Account (r:1 w:1)
   // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
-  fn tip() -> Weight {
-    Weight::from_ref_time(64_690_000)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SentTipsCountBySender (r:1 w:1)
+  // Storage: Tipping SentTipSequencesBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContextAndSender (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeCollectionByAccountId (r:1 w:1)
+  // Storage: Tipping NextSupporterBadgeCollectionId (r:1 w:1)
+  // Storage: Tipping CurrentMatchingRound (r:1 w:0)
+  // Storage: Tipping MatchingRounds (r:1 w:1)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping MatchingRoundTallies (r:1 w:1)
+  // Storage: Tipping MatchingRoundContributions (r:1 w:1)
+  // Storage: Tipping RefundWindowByAccountIdAndVerificationContext (r:1 w:0)
+  /// The range of component `m` is `[0, 128]`.
+  fn tip(m: u32) -> Weight {
+    Weight::from_ref_time(118_640_000)
+      .saturating_add(Weight::from_ref_time(2_140).saturating_mul(m as u64))
+      .saturating_add(RocksDbWeight::get().reads(20))
+      .saturating_add(RocksDbWeight::get().writes(12))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Timestamp Now (r:1 w:0)
+  // Storage: System Account (r:1 w:1)
+  // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SentTipsCountBySender (r:1 w:1)
+  // Storage: Tipping SentTipSequencesBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContextAndSender (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeCollectionByAccountId (r:1 w:1)
+  // Storage: Tipping NextSupporterBadgeCollectionId (r:1 w:1)
+  // Storage: Tipping CurrentMatchingRound (r:1 w:0)
+  // Storage: Tipping MatchingRounds (r:1 w:1)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping MatchingRoundTallies (r:1 w:1)
+  // Storage: Tipping MatchingRoundContributions (r:1 w:1)
+  // Storage: Tipping RefundWindowByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping RemoteLocationByAccountId (r:0 w:1)
+  /// The range of component `m` is `[0, 128]`.
+  fn tip_remote(m: u32) -> Weight {
+    Weight::from_ref_time(120_910_000)
+      .saturating_add(Weight::from_ref_time(2_160).saturating_mul(m as u64))
+      .saturating_add(RocksDbWeight::get().reads(20))
+      .saturating_add(RocksDbWeight::get().writes(13))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Timestamp Now (r:1 w:0)
+  // Storage: System Account (r:1 w:1)
+  // Storage: Tipping EscrowedTipsByVerificationContext (r:1 w:1)
+  /// The range of component `m` is `[0, 128]`.
+  fn tip_escrow(m: u32) -> Weight {
+    Weight::from_ref_time(71_250_000)
+      .saturating_add(Weight::from_ref_time(2_080).saturating_mul(m as u64))
+      .saturating_add(RocksDbWeight::get().reads(6))
+      .saturating_add(RocksDbWeight::get().writes(2))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping EscrowedTipsByVerificationContext (r:1 w:1)
+  // Storage: System Account (r:3 w:3)
+  // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SentTipsCountBySender (r:1 w:1)
+  // Storage: Tipping SentTipSequencesBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContextAndSender (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeCollectionByAccountId (r:1 w:1)
+  // Storage: Tipping NextSupporterBadgeCollectionId (r:1 w:1)
+  /// The range of component `c` is `[0, 1000]`.
+  fn claim_escrowed_tips(c: u32) -> Weight {
+    Weight::from_ref_time(41_350_000)
+      .saturating_add(Weight::from_ref_time(5_490_810).saturating_mul(c as u64))
       .saturating_add(RocksDbWeight::get().reads(5))
+      .saturating_add(RocksDbWeight::get().reads((11 as u64).saturating_mul(c as u64)))
+      .saturating_add(RocksDbWeight::get().writes(2))
+      .saturating_add(RocksDbWeight::get().writes((10 as u64).saturating_mul(c as u64)))
+  }
+  // Storage: Tipping EscrowedTipsByVerificationContext (r:1 w:1)
+  // Storage: System Account (r:2 w:2)
+  /// The range of component `c` is `[0, 1000]`.
+  fn refund_escrowed_tips(c: u32) -> Weight {
+    Weight::from_ref_time(12_870_000)
+      .saturating_add(Weight::from_ref_time(2_951_440).saturating_mul(c as u64))
+      .saturating_add(RocksDbWeight::get().reads(1))
+      .saturating_add(RocksDbWeight::get().reads((2 as u64).saturating_mul(c as u64)))
+      .saturating_add(RocksDbWeight::get().writes(1))
+      .saturating_add(RocksDbWeight::get().writes((2 as u64).saturating_mul(c as u64)))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:0 w:1)
+  fn set_supporter_badge() -> Weight {
+    Weight::from_ref_time(27_830_000)
+      .saturating_add(RocksDbWeight::get().reads(2))
+      .saturating_add(RocksDbWeight::get().writes(1))
+  }
+  // Storage: Tipping CurrentMatchingRound (r:1 w:1)
+  // Storage: Tipping NextMatchingRoundId (r:1 w:1)
+  // Storage: Tipping MatchingRounds (r:0 w:1)
+  fn create_matching_round() -> Weight {
+    Weight::from_ref_time(24_150_000)
+      .saturating_add(RocksDbWeight::get().reads(2))
+      .saturating_add(RocksDbWeight::get().writes(3))
+  }
+  // Storage: Tipping MatchingRounds (r:1 w:0)
+  // Storage: System Account (r:2 w:2)
+  fn fund_matching_round() -> Weight {
+    Weight::from_ref_time(52_370_000)
+      .saturating_add(RocksDbWeight::get().reads(3))
+      .saturating_add(RocksDbWeight::get().writes(2))
+  }
+  // Storage: Tipping MatchingRounds (r:1 w:1)
+  // Storage: System Account (r:1 w:1)
+  // Storage: Tipping MatchingRoundTallies (r:1 w:0)
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping TippingSettingsByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping CurrentMatchingRound (r:0 w:1)
+  /// The range of component `c` is `[1, 500]`.
+  fn finalize_matching_round(c: u32) -> Weight {
+    Weight::from_ref_time(41_820_000)
+      // Standard Error: 9_671
+      .saturating_add(Weight::from_ref_time(63_284_000).saturating_mul(c as u64))
+      .saturating_add(RocksDbWeight::get().reads(3))
+      .saturating_add(RocksDbWeight::get().reads((4 as u64).saturating_mul(c as u64)))
+      .saturating_add(RocksDbWeight::get().writes(3))
+      .saturating_add(RocksDbWeight::get().writes((1 as u64).saturating_mul(c as u64)))
+  }
+  // Storage: Verification AccountIdsByVerificationContext (r:1 w:0)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping RefundWindowByAccountIdAndVerificationContext (r:0 w:1)
+  fn set_refund_window() -> Weight {
+    Weight::from_ref_time(28_410_000)
+      .saturating_add(RocksDbWeight::get().reads(2))
+      .saturating_add(RocksDbWeight::get().writes(1))
+  }
+  // Storage: Tipping PendingTips (r:1 w:1)
+  // Storage: Tipping PendingTipsBySettlementBlock (r:1 w:1)
+  // Storage: System Account (r:1 w:1)
+  // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsAggregateByAccountIdAndVerificationContextAndSender (r:1 w:1)
+  fn cancel_tip() -> Weight {
+    Weight::from_ref_time(64_720_000)
+      .saturating_add(RocksDbWeight::get().reads(7))
+      .saturating_add(RocksDbWeight::get().writes(7))
+  }
+  // Storage: Tipping PendingTipsBySettlementBlock (r:1 w:1)
+  // Storage: Tipping PendingTips (r:1 w:1)
+  // Storage: System Account (r:2 w:2)
+  // Storage: Tipping TipsByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Tipping TipsBySenderAndVerificationContext (r:1 w:1)
+  // Storage: Tipping SupporterBadgeThresholdByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping SupporterBadgeCollectionByAccountId (r:1 w:1)
+  // Storage: Tipping NextSupporterBadgeCollectionId (r:1 w:1)
+  // Storage: Tipping CurrentMatchingRound (r:1 w:0)
+  // Storage: Tipping MatchingRounds (r:1 w:1)
+  // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:0)
+  // Storage: Tipping MatchingRoundTallies (r:1 w:1)
+  // Storage: Tipping MatchingRoundContributions (r:1 w:1)
+  /// The range of component `c` is `[0, 100]`.
+  fn settle_pending_tips(c: u32) -> Weight {
+    Weight::from_ref_time(6_930_000)
+      // Standard Error: 12_408
+      .saturating_add(Weight::from_ref_time(97_516_000).saturating_mul(c as u64))
+      .saturating_add(RocksDbWeight::get().reads(1))
+      .saturating_add(RocksDbWeight::get().reads((14 as u64).saturating_mul(c as u64)))
       .saturating_add(RocksDbWeight::get().writes(1))
+      .saturating_add(RocksDbWeight::get().writes((10 as u64).saturating_mul(c as u64)))
   }
 }
@@ -0,0 +1,154 @@
+// This file is part of Anagolay Network.
+
+// Copyright (C) 2019-2023 Anagolay Network.
+
+//! Test network of a relay chain and two parachains, both running the tipping pallet, used to test
+//! the cross-chain tips with the xcm-simulator
+
+pub mod parachain;
+pub mod relay_chain;
+
+use polkadot_parachain::primitives::Id as ParaId;
+use sp_runtime::{traits::AccountIdConversion, AccountId32};
+use xcm::latest::prelude::*;
+use xcm_executor::traits::Convert;
+use xcm_simulator::{decl_test_network, decl_test_parachain, decl_test_relay_chain};
+
+pub const ALICE: AccountId32 = AccountId32::new([0u8; 32]);
+pub const BOB: AccountId32 = AccountId32::new([1u8; 32]);
+pub const INITIAL_BALANCE: u128 = 1_000_000_000;
+/// The weight allowed for the remote execution of a call, large enough for any tipping call
+pub const TRANSACT_WEIGHT: u64 = 1_000_000_000;
+
+decl_test_parachain! {
+  pub struct ParaA {
+    Runtime = parachain::Runtime,
+    XcmpMessageHandler = parachain::MsgQueue,
+    DmpMessageHandler = parachain::MsgQueue,
+    new_ext = para_ext(1),
+  }
+}
+
+decl_test_parachain! {
+  pub struct ParaB {
+    Runtime = parachain::Runtime,
+    XcmpMessageHandler = parachain::MsgQueue,
+    DmpMessageHandler = parachain::MsgQueue,
+    new_ext = para_ext(2),
+  }
+}
+
+decl_test_relay_chain! {
+  pub struct Relay {
+    Runtime = relay_chain::Runtime,
+    XcmConfig = relay_chain::XcmConfig,
+    new_ext = relay_ext(),
+  }
+}
+
+decl_test_network! {
+  pub struct MockNet {
+    relay_chain = Relay,
+    parachains = vec![
+      (1, ParaA),
+      (2, ParaB),
+    ],
+  }
+}
+
+/// The account of a parachain on the relay chain
+pub fn para_account_id(id: u32) -> relay_chain::AccountId {
+  ParaId::from(id).into_account_truncating()
+}
+
+/// The local account of a location on the parachains
+pub fn location_account_id(location: MultiLocation) -> parachain::AccountId {
+  parachain::LocationToAccountId::convert(location).unwrap()
+}
+
+/// The local account funding the tips of a remote user on the parachains
+pub fn remote_account_id(location: MultiLocation) -> parachain::AccountId {
+  parachain::RemoteAccountId32Hashed::convert(location).unwrap()
+}
+
+/// The location of an account of the relay chain, as seen by the parachains
+pub fn relay_account_location(account: AccountId32) -> MultiLocation {
+  MultiLocation::new(
+    1,
+    X1(Junction::AccountId32 {
+      network: NetworkId::Any,
+      id: account.into(),
+    }),
+  )
+}
+
+/// The location of an account of a parachain, as seen by its siblings
+pub fn sibling_account_location(para_id: u32, account: AccountId32) -> MultiLocation {
+  MultiLocation::new(
+    1,
+    X2(
+      Parachain(para_id),
+      Junction::AccountId32 {
+        network: NetworkId::Any,
+        id: account.into(),
+      },
+    ),
+  )
+}
+
+pub fn para_ext(para_id: u32) -> sp_io::TestExternalities {
+  use parachain::{MsgQueue, Runtime, System, Tipping};
+
+  let mut t = frame_system::GenesisConfig::default()
+    .build_storage::<Runtime>()
+    .unwrap();
+
+  pallet_balances::GenesisConfig::<Runtime> {
+    balances: vec![
+      (ALICE, INITIAL_BALANCE),
+      (BOB, INITIAL_BALANCE),
+      (location_account_id(MultiLocation::parent()), INITIAL_BALANCE),
+      (
+        location_account_id(MultiLocation::new(1, X1(Parachain(1)))),
+        INITIAL_BALANCE,
+      ),
+      (
+        location_account_id(MultiLocation::new(1, X1(Parachain(2)))),
+        INITIAL_BALANCE,
+      ),
+      (remote_account_id(relay_account_location(ALICE)), INITIAL_BALANCE),
+      (remote_account_id(sibling_account_location(2, ALICE)), INITIAL_BALANCE),
+    ],
+  }
+  .assimilate_storage(&mut t)
+  .unwrap();
+
+  let mut ext = sp_io::TestExternalities::new(t);
+  ext.execute_with(|| {
+    System::set_block_number(1);
+    MsgQueue::set_para_id(para_id.into());
+    crate::STORAGE_VERSION.put::<Tipping>();
+  });
+  ext
+}
+
+pub fn relay_ext() -> sp_io::TestExternalities {
+  use relay_chain::{Runtime, System};
+
+  let mut t = frame_system::GenesisConfig::default()
+    .build_storage::<Runtime>()
+    .unwrap();
+
+  pallet_balances::GenesisConfig::<Runtime> {
+    balances: vec![(ALICE, INITIAL_BALANCE), (para_account_id(1), INITIAL_BALANCE)],
+  }
+  .assimilate_storage(&mut t)
+  .unwrap();
+
+  let mut ext = sp_io::TestExternalities::new(t);
+  ext.execute_with(|| System::set_block_number(1));
+  ext
+}
+
+pub type RelayChainPalletXcm = pallet_xcm::Pallet<relay_chain::Runtime>;
+pub type ParachainPalletXcm = pallet_xcm::Pallet<parachain::Runtime>;
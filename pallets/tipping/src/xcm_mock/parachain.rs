@@ -0,0 +1,425 @@
+// This file is part of Anagolay Network.
+
+// Copyright (C) 2019-2023 Anagolay Network.
+
+//! Parachain runtime mock, running the tipping pallet
+
+use crate as tipping;
+use crate::types::{NaiveMatchingContributorFilter, NaiveSupporterBadgeMinter};
+use codec::{Compact, Decode, Encode};
+use core::{borrow::Borrow, time::Duration};
+use frame_support::{
+  construct_runtime, parameter_types,
+  traits::{EnsureOrigin, Everything, Nothing, UnixTime},
+  weights::Weight,
+  PalletId,
+};
+use sp_core::{hashing::blake2_256, H256};
+use sp_runtime::{
+  testing::{Header, TestXt, UintAuthorityId},
+  traits::{BlakeTwo256, Hash, IdentityLookup, Verify},
+  AccountId32, MultiSignature, Permill,
+};
+
+use pallet_xcm::XcmPassthrough;
+use polkadot_core_primitives::BlockNumber as RelayBlockNumber;
+use polkadot_parachain::primitives::{DmpMessageHandler, Id as ParaId, Sibling, XcmpMessageFormat, XcmpMessageHandler};
+use xcm::{latest::prelude::*, VersionedXcm};
+use xcm_builder::{
+  AccountId32Aliases, AllowUnpaidExecutionFrom, CurrencyAdapter as XcmCurrencyAdapter, EnsureXcmOrigin,
+  FixedWeightBounds, IsConcrete, LocationInverter, NativeAsset, ParentIsPreset, SiblingParachainConvertsVia,
+  SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation,
+};
+use xcm_executor::{traits::Convert, Config, XcmExecutor};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+type Extrinsic = TestXt<RuntimeCall, ()>;
+
+parameter_types! {
+  pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+  type RuntimeOrigin = RuntimeOrigin;
+  type RuntimeCall = RuntimeCall;
+  type Index = u64;
+  type BlockNumber = u64;
+  type Hash = H256;
+  type Hashing = BlakeTwo256;
+  type AccountId = AccountId;
+  type Lookup = IdentityLookup<Self::AccountId>;
+  type Header = Header;
+  type RuntimeEvent = RuntimeEvent;
+  type BlockHashCount = BlockHashCount;
+  type BlockWeights = ();
+  type BlockLength = ();
+  type Version = ();
+  type PalletInfo = PalletInfo;
+  type AccountData = pallet_balances::AccountData<Balance>;
+  type OnNewAccount = ();
+  type OnKilledAccount = ();
+  type DbWeight = ();
+  type BaseCallFilter = Everything;
+  type SystemWeightInfo = ();
+  type SS58Prefix = ();
+  type OnSetCode = ();
+  type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+  pub ExistentialDeposit: Balance = 1;
+  pub const MaxLocks: u32 = 50;
+  pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Runtime {
+  type MaxLocks = MaxLocks;
+  type Balance = Balance;
+  type RuntimeEvent = RuntimeEvent;
+  type DustRemoval = ();
+  type ExistentialDeposit = ExistentialDeposit;
+  type AccountStore = System;
+  type WeightInfo = ();
+  type MaxReserves = MaxReserves;
+  type ReserveIdentifier = [u8; 8];
+}
+
+parameter_types! {
+  pub const Period: u32 = 360000;
+  pub const Offset: u32 = 0;
+}
+
+impl pallet_session::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type ValidatorId = AccountId;
+  type ValidatorIdOf = ();
+  type ShouldEndSession = pallet_session::PeriodicSessions<Period, Offset>;
+  type NextSessionRotation = pallet_session::PeriodicSessions<Period, Offset>;
+  type SessionManager = ();
+  type SessionHandler = pallet_session::TestSessionHandler;
+  type Keys = UintAuthorityId;
+  type WeightInfo = ();
+}
+
+impl verification::Config for Runtime {
+  type AuthorityId = verification::crypto::VerificationAuthId;
+  type RuntimeEvent = RuntimeEvent;
+  type VerificationKeyGenerator = verification::types::NaiveVerificationKeyGenerator<Self>;
+  type VerificationInvalidator = tipping::types::TippingVerificationInvalidator<Self>;
+  type WeightInfo = ();
+  type Currency = Balances;
+
+  const REGISTRATION_FEE: Balance = 10;
+  const MAX_REQUESTS_PER_CONTEXT: u32 = 1;
+}
+
+impl<VerificationCall> frame_system::offchain::SendTransactionTypes<VerificationCall> for Runtime
+where
+  RuntimeCall: From<VerificationCall>,
+{
+  type OverarchingCall = RuntimeCall;
+  type Extrinsic = Extrinsic;
+}
+
+impl frame_system::offchain::SigningTypes for Runtime {
+  type Public = <MultiSignature as Verify>::Signer;
+  type Signature = MultiSignature;
+}
+
+pub struct MockTime {}
+
+impl UnixTime for MockTime {
+  fn now() -> Duration {
+    core::time::Duration::from_millis(1000)
+  }
+}
+
+/// Accepts the XCM origins of the users of the relay chain and of the sibling parachains as remote
+/// tippers, funding the tips from their hashed local account
+pub struct EnsureXcmTipper;
+
+impl EnsureOrigin<RuntimeOrigin> for EnsureXcmTipper {
+  type Success = (MultiLocation, AccountId);
+
+  fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+    let location = pallet_xcm::EnsureXcm::<Everything>::try_origin(o)?;
+    match RemoteAccountId32Hashed::convert_ref(&location) {
+      Ok(account) => Ok((location, account)),
+      Err(()) => Err(pallet_xcm::Origin::Xcm(location).into()),
+    }
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn successful_origin() -> RuntimeOrigin {
+    let tipper = Junction::AccountId32 {
+      network: NetworkId::Any,
+      id: [0u8; 32],
+    };
+    pallet_xcm::Origin::Xcm(MultiLocation::new(1, X1(tipper))).into()
+  }
+}
+
+impl tipping::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type Currency = Balances;
+  type TimeProvider = MockTime;
+  type ProtocolFeeHandler = ();
+  type SupporterBadgeMinter = NaiveSupporterBadgeMinter<Self>;
+  type FirstSupporterBadgeCollectionId = frame_support::traits::ConstU32<0>;
+  type RemoteLocation = MultiLocation;
+  type RemoteTipOrigin = EnsureXcmTipper;
+  type MatchingRoundOrigin = frame_system::EnsureRoot<AccountId>;
+  type MatchingContributorFilter = NaiveMatchingContributorFilter<Self>;
+  type WeightInfo = ();
+
+  const MAX_TIPS_PER_VERIFICATION_CONTEXT: u32 = 3;
+  const MEMO_FEE_PER_BYTE: Balance = 1;
+  const PALLET_ID: PalletId = PalletId(*b"anag/tip");
+  const ESCROW_TIMEOUT: u64 = 10;
+  const MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT: u32 = 2;
+  const PROTOCOL_FEE: Permill = Permill::from_percent(10);
+  const MAX_CONTEXTS_PER_MATCHING_ROUND: u32 = 10;
+  const MAX_REFUND_WINDOW: u64 = 5;
+  const MAX_PENDING_TIPS_PER_BLOCK: u32 = 2;
+}
+
+parameter_types! {
+  pub const KsmLocation: MultiLocation = MultiLocation::parent();
+  pub const RelayNetwork: NetworkId = NetworkId::Kusama;
+  pub Ancestry: MultiLocation = Parachain(MsgQueue::parachain_id().into()).into();
+}
+
+pub type LocationToAccountId = (
+  ParentIsPreset<AccountId>,
+  SiblingParachainConvertsVia<Sibling, AccountId>,
+  AccountId32Aliases<RelayNetwork, AccountId>,
+);
+
+/// Converts the accounts of the relay chain and of the sibling parachains into local accounts, as
+/// the runtime does only for the remote tippers
+pub struct RemoteAccountId32Hashed;
+
+impl Convert<MultiLocation, AccountId> for RemoteAccountId32Hashed {
+  fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+    let description = match location.borrow() {
+      MultiLocation {
+        parents: 1,
+        interior: X1(Junction::AccountId32 { id, .. }),
+      } => (b"ParentChain", (b"AccountId32", id).encode()).encode(),
+      MultiLocation {
+        parents: 1,
+        interior: X2(Parachain(para_id), Junction::AccountId32 { id, .. }),
+      } => (
+        b"SiblingChain",
+        Compact::<u32>::from(*para_id),
+        (b"AccountId32", id).encode(),
+      )
+        .encode(),
+      _ => return Err(()),
+    };
+    Ok(blake2_256(&description).into())
+  }
+}
+
+pub type XcmOriginToCallOrigin = (
+  SovereignSignedViaLocation<LocationToAccountId, RuntimeOrigin>,
+  SignedAccountId32AsNative<RelayNetwork, RuntimeOrigin>,
+  XcmPassthrough<RuntimeOrigin>,
+);
+
+parameter_types! {
+  pub const UnitWeightCost: u64 = 1;
+  pub const MaxInstructions: u32 = 100;
+}
+
+pub type LocalAssetTransactor =
+  XcmCurrencyAdapter<Balances, IsConcrete<KsmLocation>, LocationToAccountId, AccountId, ()>;
+
+pub type XcmRouter = super::ParachainXcmRouter<MsgQueue>;
+pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
+
+pub struct XcmConfig;
+impl Config for XcmConfig {
+  type RuntimeCall = RuntimeCall;
+  type XcmSender = XcmRouter;
+  type AssetTransactor = LocalAssetTransactor;
+  type OriginConverter = XcmOriginToCallOrigin;
+  type IsReserve = NativeAsset;
+  type IsTeleporter = ();
+  type LocationInverter = LocationInverter<Ancestry>;
+  type Barrier = Barrier;
+  type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+  type Trader = ();
+  type ResponseHandler = ();
+  type AssetTrap = ();
+  type AssetClaims = ();
+  type SubscriptionService = ();
+}
+
+#[frame_support::pallet]
+pub mod mock_msg_queue {
+  use super::*;
+  use frame_support::pallet_prelude::*;
+
+  #[pallet::config]
+  pub trait Config: frame_system::Config {
+    type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+    type XcmExecutor: ExecuteXcm<Self::RuntimeCall>;
+  }
+
+  #[pallet::call]
+  impl<T: Config> Pallet<T> {}
+
+  #[pallet::pallet]
+  #[pallet::generate_store(pub(super) trait Store)]
+  #[pallet::without_storage_info]
+  pub struct Pallet<T>(_);
+
+  #[pallet::storage]
+  #[pallet::getter(fn parachain_id)]
+  pub(super) type ParachainId<T: Config> = StorageValue<_, ParaId, ValueQuery>;
+
+  impl<T: Config> Get<ParaId> for Pallet<T> {
+    fn get() -> ParaId {
+      Self::parachain_id()
+    }
+  }
+
+  pub type MessageId = [u8; 32];
+
+  #[pallet::event]
+  #[pallet::generate_deposit(pub(super) fn deposit_event)]
+  pub enum Event<T: Config> {
+    /// Some XCM was executed OK.
+    Success(Option<T::Hash>),
+    /// Some XCM failed.
+    Fail(Option<T::Hash>, XcmError),
+    /// Bad XCM version used.
+    BadVersion(Option<T::Hash>),
+    /// Downward message is invalid XCM.
+    InvalidFormat(MessageId),
+    /// Downward message is unsupported version of XCM.
+    UnsupportedVersion(MessageId),
+    /// Downward message executed with the given outcome.
+    ExecutedDownward(MessageId, Outcome),
+  }
+
+  impl<T: Config> Pallet<T> {
+    pub fn set_para_id(para_id: ParaId) {
+      ParachainId::<T>::put(para_id);
+    }
+
+    fn handle_xcmp_message(
+      sender: ParaId,
+      _sent_at: RelayBlockNumber,
+      xcm: VersionedXcm<T::RuntimeCall>,
+      max_weight: Weight,
+    ) -> Result<Weight, XcmError> {
+      let hash = Encode::using_encoded(&xcm, T::Hashing::hash);
+      let (result, event) = match Xcm::<T::RuntimeCall>::try_from(xcm) {
+        Ok(xcm) => {
+          let location = (1, Parachain(sender.into()));
+          match T::XcmExecutor::execute_xcm(location, xcm, max_weight.ref_time()) {
+            Outcome::Error(e) => (Err(e.clone()), Event::Fail(Some(hash), e)),
+            Outcome::Complete(w) => (Ok(Weight::from_ref_time(w)), Event::Success(Some(hash))),
+            // As far as the caller is concerned, this was dispatched without error, so
+            // we just report the weight used.
+            Outcome::Incomplete(w, e) => (Ok(Weight::from_ref_time(w)), Event::Fail(Some(hash), e)),
+          }
+        }
+        Err(()) => (Err(XcmError::UnhandledXcmVersion), Event::BadVersion(Some(hash))),
+      };
+      Self::deposit_event(event);
+      result
+    }
+  }
+
+  impl<T: Config> XcmpMessageHandler for Pallet<T> {
+    fn handle_xcmp_messages<'a, I: Iterator<Item = (ParaId, RelayBlockNumber, &'a [u8])>>(
+      iter: I,
+      max_weight: Weight,
+    ) -> Weight {
+      for (sender, sent_at, data) in iter {
+        let mut data_ref = data;
+        let _ = XcmpMessageFormat::decode(&mut data_ref).expect("Simulator encodes with versioned xcm format; qed");
+
+        let mut remaining_fragments = data_ref;
+        while !remaining_fragments.is_empty() {
+          if let Ok(xcm) = VersionedXcm::<T::RuntimeCall>::decode(&mut remaining_fragments) {
+            let _ = Self::handle_xcmp_message(sender, sent_at, xcm, max_weight);
+          } else {
+            debug_assert!(false, "Invalid incoming XCMP message data");
+          }
+        }
+      }
+      max_weight
+    }
+  }
+
+  impl<T: Config> DmpMessageHandler for Pallet<T> {
+    fn handle_dmp_messages(iter: impl Iterator<Item = (RelayBlockNumber, Vec<u8>)>, limit: Weight) -> Weight {
+      for (_sent_at, data) in iter {
+        let id = sp_io::hashing::blake2_256(&data[..]);
+        let maybe_msg = VersionedXcm::<T::RuntimeCall>::decode(&mut &data[..]).map(Xcm::<T::RuntimeCall>::try_from);
+        match maybe_msg {
+          Err(_) => {
+            Self::deposit_event(Event::InvalidFormat(id));
+          }
+          Ok(Err(())) => {
+            Self::deposit_event(Event::UnsupportedVersion(id));
+          }
+          Ok(Ok(x)) => {
+            let outcome = T::XcmExecutor::execute_xcm(Parent, x, limit.ref_time());
+            Self::deposit_event(Event::ExecutedDownward(id, outcome));
+          }
+        }
+      }
+      limit
+    }
+  }
+}
+
+impl mock_msg_queue::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type XcmExecutor = XcmExecutor<XcmConfig>;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, RelayNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type SendXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+  type XcmRouter = XcmRouter;
+  type ExecuteXcmOrigin = EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+  type XcmExecuteFilter = Everything;
+  type XcmExecutor = XcmExecutor<XcmConfig>;
+  type XcmTeleportFilter = Nothing;
+  type XcmReserveTransferFilter = Everything;
+  type Weigher = FixedWeightBounds<UnitWeightCost, RuntimeCall, MaxInstructions>;
+  type LocationInverter = LocationInverter<Ancestry>;
+  type RuntimeOrigin = RuntimeOrigin;
+  type RuntimeCall = RuntimeCall;
+  const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+  type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+  pub enum Runtime where
+    Block = Block,
+    NodeBlock = Block,
+    UncheckedExtrinsic = UncheckedExtrinsic,
+  {
+    System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+    Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+    Session: pallet_session::{Pallet, Call, Storage, Event, Config<T>},
+    Verification: verification::{Pallet, Call, Storage, Event<T>, ValidateUnsigned},
+    MsgQueue: mock_msg_queue::{Pallet, Storage, Event<T>},
+    PolkadotXcm: pallet_xcm::{Pallet, Call, Event<T>, Origin},
+    Tipping: tipping::{Pallet, Call, Storage, Event<T>},
+  }
+);
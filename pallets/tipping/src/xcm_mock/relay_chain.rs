@@ -0,0 +1,181 @@
+// This file is part of Anagolay Network.
+
+// Copyright (C) 2019-2023 Anagolay Network.
+
+//! Relay chain runtime mock
+
+use frame_support::{
+  construct_runtime, parameter_types,
+  traits::{Everything, Nothing},
+};
+use sp_core::H256;
+use sp_runtime::{testing::Header, traits::IdentityLookup, AccountId32};
+
+use polkadot_parachain::primitives::Id as ParaId;
+use polkadot_runtime_parachains::{configuration, origin, shared, ump};
+use xcm::latest::prelude::*;
+use xcm_builder::{
+  AccountId32Aliases, AllowUnpaidExecutionFrom, ChildParachainAsNative, ChildParachainConvertsVia,
+  ChildSystemParachainAsSuperuser, CurrencyAdapter as XcmCurrencyAdapter, FixedRateOfFungible, FixedWeightBounds,
+  IsConcrete, LocationInverter, SignedAccountId32AsNative, SignedToAccountId32, SovereignSignedViaLocation,
+};
+use xcm_executor::{Config, XcmExecutor};
+
+pub type AccountId = AccountId32;
+pub type Balance = u128;
+
+parameter_types! {
+  pub const BlockHashCount: u64 = 250;
+}
+
+impl frame_system::Config for Runtime {
+  type RuntimeOrigin = RuntimeOrigin;
+  type RuntimeCall = RuntimeCall;
+  type Index = u64;
+  type BlockNumber = u64;
+  type Hash = H256;
+  type Hashing = ::sp_runtime::traits::BlakeTwo256;
+  type AccountId = AccountId;
+  type Lookup = IdentityLookup<Self::AccountId>;
+  type Header = Header;
+  type RuntimeEvent = RuntimeEvent;
+  type BlockHashCount = BlockHashCount;
+  type BlockWeights = ();
+  type BlockLength = ();
+  type Version = ();
+  type PalletInfo = PalletInfo;
+  type AccountData = pallet_balances::AccountData<Balance>;
+  type OnNewAccount = ();
+  type OnKilledAccount = ();
+  type DbWeight = ();
+  type BaseCallFilter = Everything;
+  type SystemWeightInfo = ();
+  type SS58Prefix = ();
+  type OnSetCode = ();
+  type MaxConsumers = frame_support::traits::ConstU32<16>;
+}
+
+parameter_types! {
+  pub ExistentialDeposit: Balance = 1;
+  pub const MaxLocks: u32 = 50;
+  pub const MaxReserves: u32 = 50;
+}
+
+impl pallet_balances::Config for Runtime {
+  type MaxLocks = MaxLocks;
+  type Balance = Balance;
+  type RuntimeEvent = RuntimeEvent;
+  type DustRemoval = ();
+  type ExistentialDeposit = ExistentialDeposit;
+  type AccountStore = System;
+  type WeightInfo = ();
+  type MaxReserves = MaxReserves;
+  type ReserveIdentifier = [u8; 8];
+}
+
+impl shared::Config for Runtime {}
+
+impl configuration::Config for Runtime {
+  type WeightInfo = configuration::TestWeightInfo;
+}
+
+parameter_types! {
+  pub const KsmLocation: MultiLocation = Here.into();
+  pub const KusamaNetwork: NetworkId = NetworkId::Kusama;
+  pub const AnyNetwork: NetworkId = NetworkId::Any;
+  pub Ancestry: MultiLocation = Here.into();
+  pub UnitWeightCost: u64 = 1_000;
+}
+
+pub type SovereignAccountOf = (
+  ChildParachainConvertsVia<ParaId, AccountId>,
+  AccountId32Aliases<KusamaNetwork, AccountId>,
+);
+
+pub type LocalAssetTransactor =
+  XcmCurrencyAdapter<Balances, IsConcrete<KsmLocation>, SovereignAccountOf, AccountId, ()>;
+
+type LocalOriginConverter = (
+  SovereignSignedViaLocation<SovereignAccountOf, RuntimeOrigin>,
+  ChildParachainAsNative<origin::Origin, RuntimeOrigin>,
+  SignedAccountId32AsNative<KusamaNetwork, RuntimeOrigin>,
+  ChildSystemParachainAsSuperuser<ParaId, RuntimeOrigin>,
+);
+
+parameter_types! {
+  pub const BaseXcmWeight: u64 = 1_000;
+  pub KsmPerSecond: (AssetId, u128) = (Concrete(KsmLocation::get()), 1);
+  pub const MaxInstructions: u32 = 100;
+}
+
+pub type XcmRouter = super::RelayChainXcmRouter;
+pub type Barrier = AllowUnpaidExecutionFrom<Everything>;
+
+pub struct XcmConfig;
+impl Config for XcmConfig {
+  type RuntimeCall = RuntimeCall;
+  type XcmSender = XcmRouter;
+  type AssetTransactor = LocalAssetTransactor;
+  type OriginConverter = LocalOriginConverter;
+  type IsReserve = ();
+  type IsTeleporter = ();
+  type LocationInverter = LocationInverter<Ancestry>;
+  type Barrier = Barrier;
+  type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
+  type Trader = FixedRateOfFungible<KsmPerSecond, ()>;
+  type ResponseHandler = ();
+  type AssetTrap = ();
+  type AssetClaims = ();
+  type SubscriptionService = XcmPallet;
+}
+
+pub type LocalOriginToLocation = SignedToAccountId32<RuntimeOrigin, AccountId, KusamaNetwork>;
+
+impl pallet_xcm::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type SendXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+  type XcmRouter = XcmRouter;
+  // Anyone can execute XCM messages locally...
+  type ExecuteXcmOrigin = xcm_builder::EnsureXcmOrigin<RuntimeOrigin, LocalOriginToLocation>;
+  type XcmExecuteFilter = Nothing;
+  type XcmExecutor = XcmExecutor<XcmConfig>;
+  type XcmTeleportFilter = Everything;
+  type XcmReserveTransferFilter = Everything;
+  type Weigher = FixedWeightBounds<BaseXcmWeight, RuntimeCall, MaxInstructions>;
+  type LocationInverter = LocationInverter<Ancestry>;
+  type RuntimeOrigin = RuntimeOrigin;
+  type RuntimeCall = RuntimeCall;
+  const VERSION_DISCOVERY_QUEUE_SIZE: u32 = 100;
+  type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
+}
+
+parameter_types! {
+  pub const FirstMessageFactorPercent: u64 = 100;
+}
+
+impl ump::Config for Runtime {
+  type RuntimeEvent = RuntimeEvent;
+  type UmpSink = ump::XcmSink<XcmExecutor<XcmConfig>, Runtime>;
+  type FirstMessageFactorPercent = FirstMessageFactorPercent;
+  type ExecuteOverweightOrigin = frame_system::EnsureRoot<AccountId>;
+  type WeightInfo = ump::TestWeightInfo;
+}
+
+impl origin::Config for Runtime {}
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
+type Block = frame_system::mocking::MockBlock<Runtime>;
+
+construct_runtime!(
+  pub enum Runtime where
+    Block = Block,
+    NodeBlock = Block,
+    UncheckedExtrinsic = UncheckedExtrinsic,
+  {
+    System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+    Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+    ParasOrigin: origin::{Pallet, Origin},
+    ParasUmp: ump::{Pallet, Call, Storage, Event},
+    XcmPallet: pallet_xcm::{Pallet, Call, Storage, Event<T>, Origin},
+  }
+);
@@ -320,6 +320,8 @@ pub mod pallet {
     /// There are already a number of accounts attempting to verify the same context and no more
     /// will be accepted
     MaxVerificationRequestsPerContextLimitReached,
+    /// The verification confirmation callback failed
+    VerificationConfirmationError,
   }
 
   /// Events of the Poe pallet
@@ -498,6 +500,7 @@ pub mod pallet {
     /// * `NoSuchVerificationRequest` - if the request context is not associated to any stored
     ///   [`VerificationRequest`]
     /// * `InvalidVerificationStatus` - if the request does not have status `Success` or `Failure`
+    /// * `VerificationConfirmationError` - if the key generator cannot confirm the successful request
     ///
     /// # Events
     /// * `VerificationSuccessful` - for the verifier account to indicate that his verification
@@ -534,6 +537,9 @@ pub mod pallet {
       // registration fee
       match &stored_request.status {
         VerificationStatus::Success => {
+          // Callback to the generator in order to notify the confirmation of the key
+          T::VerificationKeyGenerator::confirm(&stored_request)?;
+
           // Emit an event that the verification is successful
           Self::deposit_event(Event::VerificationSuccessful(
             verification_data.verifier,
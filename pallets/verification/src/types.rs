@@ -205,6 +205,17 @@ impl<T: crate::Config> VerificationKeyGenerator<T> for NaiveVerificationKeyGener
       .map(|byte| hex::encode([byte]).as_bytes().to_vec())
       .ok_or(crate::Error::<T>::VerificationKeyGenerationError)
   }
+
+  /// Called when a verification request turns out to be successful
+  ///
+  /// # Arguments
+  /// * request - The verification request (unused)
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn confirm(_request: &VerificationRequest<T::AccountId>) -> Result<(), crate::Error<T>> {
+    Ok(())
+  }
 }
 
 /// Default implementation for the verification invalidator
@@ -226,6 +237,26 @@ impl<T: crate::Config> VerificationInvalidator<T> for NaiveVerificationInvalidat
   }
 }
 
+/// Implementation for a pair of verification invalidators, called one after the other
+impl<T, A, B> VerificationInvalidator<T> for (A, B)
+where
+  T: crate::Config,
+  A: VerificationInvalidator<T>,
+  B: VerificationInvalidator<T>,
+{
+  /// Called when a verification request turns out to be no longer valid
+  ///
+  /// # Arguments
+  /// * request - The verification request
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn invalidate(request: &VerificationRequest<T::AccountId>) -> Result<(), crate::Error<T>> {
+    A::invalidate(request)?;
+    B::invalidate(request)
+  }
+}
+
 /// A trait that mimics the behavior of verification strategies on a VerificationContext trait,
 /// providing the common methods
 ///
@@ -297,6 +328,15 @@ pub trait VerificationKeyGenerator<T: frame_system::Config>: Clone {
     context: &VerificationContext,
     identifier: Vec<u8>,
   ) -> Result<Vec<u8>, crate::Error<T>>;
+
+  /// Called when a verification request turns out to be successful
+  ///
+  /// # Arguments
+  /// * request - The verification request
+  ///
+  /// # Return
+  /// Result having the unit type if ok, an Error otherwise
+  fn confirm(request: &VerificationRequest<T::AccountId>) -> Result<(), crate::Error<T>>;
 }
 
 /// A trait that allows to define a callback whenever a [`VerificationRequest`] is invalidated.
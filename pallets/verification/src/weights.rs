@@ -52,19 +52,23 @@ impl<T: frame_system::Config> WeightInfo for AnagolayWeight<T> {
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:1)
   // Storage: Poe ProofTotal (r:1 w:1)
   // Storage: Verification AccountIdsByVerificationContext (r:1 w:1)
-  // Storage: Poe ProofByProofIdAndAccountId (r:0 w:1)
-  // Storage: Poe ProofIdsByVerificationContext (r:0 w:1)
+  // Storage: Poe ProofByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe PendingProofIdByAccountIdAndVerificationContext (r:0 w:1)
+  // Storage: Poe ProofIdsByAccountId (r:0 w:1)
   // Storage: Verification AccountIdByVerificationContext (r:0 w:1)
   fn request_verification() -> Weight {
     Weight::from_ref_time(35_400_000)
-      .saturating_add(T::DbWeight::get().reads(3))
-      .saturating_add(T::DbWeight::get().writes(5))
+      .saturating_add(T::DbWeight::get().reads(4))
+      .saturating_add(T::DbWeight::get().writes(6))
   }
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Poe PendingProofIdByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Poe ProofIdsByVerificationContext (r:1 w:1)
+  // Storage: Poe ProofByProofIdAndAccountId (r:1 w:0)
   fn submit_verification_status() -> Weight {
-    Weight::from_ref_time(19_930_000)
-      .saturating_add(T::DbWeight::get().reads(1))
-      .saturating_add(T::DbWeight::get().writes(1))
+    Weight::from_ref_time(27_460_000)
+      .saturating_add(T::DbWeight::get().reads(4))
+      .saturating_add(T::DbWeight::get().writes(3))
   }
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:1)
   fn perform_verification() -> Weight {
@@ -79,19 +83,23 @@ impl WeightInfo for () {
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:1)
   // Storage: Poe ProofTotal (r:1 w:1)
   // Storage: Verification AccountIdsByVerificationContext (r:1 w:1)
-  // Storage: Poe ProofByProofIdAndAccountId (r:0 w:1)
-  // Storage: Poe ProofIdsByVerificationContext (r:0 w:1)
+  // Storage: Poe ProofByProofIdAndAccountId (r:1 w:1)
+  // Storage: Poe PendingProofIdByAccountIdAndVerificationContext (r:0 w:1)
+  // Storage: Poe ProofIdsByAccountId (r:0 w:1)
   // Storage: Verification AccountIdByVerificationContext (r:0 w:1)
   fn request_verification() -> Weight {
     Weight::from_ref_time(35_400_000)
-      .saturating_add(RocksDbWeight::get().reads(3))
-      .saturating_add(RocksDbWeight::get().writes(5))
+      .saturating_add(RocksDbWeight::get().reads(4))
+      .saturating_add(RocksDbWeight::get().writes(6))
   }
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Poe PendingProofIdByAccountIdAndVerificationContext (r:1 w:1)
+  // Storage: Poe ProofIdsByVerificationContext (r:1 w:1)
+  // Storage: Poe ProofByProofIdAndAccountId (r:1 w:0)
   fn submit_verification_status() -> Weight {
-    Weight::from_ref_time(19_930_000)
-      .saturating_add(RocksDbWeight::get().reads(1))
-      .saturating_add(RocksDbWeight::get().writes(1))
+    Weight::from_ref_time(27_460_000)
+      .saturating_add(RocksDbWeight::get().reads(4))
+      .saturating_add(RocksDbWeight::get().writes(3))
   }
   // Storage: Verification VerificationRequestByAccountIdAndVerificationContext (r:1 w:1)
   fn perform_verification() -> Weight {
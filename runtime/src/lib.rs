@@ -33,7 +33,9 @@ use frame_support::{
   pallet_prelude::*,
   parameter_types,
   traits::{
-    AsEnsureOriginWithArg, EqualPrivilegeOnly, Everything, Imbalance, InstanceFilter, OnUnbalanced, WithdrawReasons,
+    tokens::nonfungibles::{Inspect, Mutate},
+    AsEnsureOriginWithArg, EnsureOriginWithArg, EqualPrivilegeOnly, Everything, Imbalance, InstanceFilter,
+    OnUnbalanced, WithdrawReasons,
   },
   weights::{
     constants::WEIGHT_PER_SECOND, Weight, WeightToFeeCoefficient, WeightToFeeCoefficients, WeightToFeePolynomial,
@@ -190,10 +192,11 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
   //   `spec_version`, and `authoring_version` are the same between Wasm and native.
   // This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
   //   the compatible custom types.
-  spec_version: 117,
+  spec_version: 118,
   impl_version: 1,
   apis: RUNTIME_API_VERSIONS,
-  transaction_version: 1,
+  // The encoding of the tipping, poe and statements calls changed
+  transaction_version: 2,
   state_version: 1,
 };
 
@@ -634,10 +637,31 @@ impl pallet_uniques::Config for Runtime {
   type WeightInfo = pallet_uniques::weights::SubstrateWeight<Runtime>;
   #[cfg(feature = "runtime-benchmarks")]
   type Helper = ();
-  type CreateOrigin = AsEnsureOriginWithArg<EnsureSigned<AccountId>>;
+  type CreateOrigin = EnsureSignedUnreservedCollection;
   type Locker = ();
 }
 
+/// Lets signed accounts create `pallet_uniques` collections, except the ones from
+/// `FirstReservedCollectionId` on, which are reserved to the supporter badges of the tipping
+/// pallet and to the Proof NFTs of the poe pallet
+pub struct EnsureSignedUnreservedCollection;
+
+impl EnsureOriginWithArg<RuntimeOrigin, u32> for EnsureSignedUnreservedCollection {
+  type Success = AccountId;
+
+  fn try_origin(o: RuntimeOrigin, collection: &u32) -> Result<Self::Success, RuntimeOrigin> {
+    if *collection >= FirstReservedCollectionId::get() {
+      return Err(o);
+    }
+    EnsureSigned::<AccountId>::try_origin(o)
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn successful_origin(_collection: &u32) -> RuntimeOrigin {
+    EnsureSigned::<AccountId>::successful_origin()
+  }
+}
+
 parameter_types! {
   // One storage item; key size 32, value size 8; .
   pub const ProxyDepositBase: Balance = deposit(1, 8);
@@ -765,20 +789,69 @@ impl statements::Config for Runtime {
   type WeightInfo = statements::weights::AnagolayWeight<Runtime>;
 
   const MAX_STATEMENTS_PER_PROOF: u32 = 16;
+  const MAX_CHILD_STATEMENTS_PER_STATEMENT: u32 = 16;
+}
+
+/// Mints the Proof NFTs as `pallet_uniques` items. The collection and the NFTs are created with
+/// the force origin, so that they are free of deposits
+#[derive(Clone)]
+pub struct UniquesProofNftMinter;
+
+impl poe::types::ProofNftMinter<Runtime> for UniquesProofNftMinter {
+  type CollectionId = u32;
+  type ItemId = u32;
+
+  fn create_collection(collection: &u32, owner: &AccountId) -> DispatchResult {
+    Uniques::force_create(RuntimeOrigin::root(), *collection, MultiAddress::Id(owner.clone()), true)
+  }
+
+  fn mint(collection: &u32, item: &u32, owner: &AccountId, metadata: Vec<u8>) -> DispatchResult {
+    <Uniques as Mutate<AccountId>>::mint_into(collection, item, owner)?;
+    let metadata = BoundedVec::try_from(metadata).map_err(|_| DispatchError::Other("Proof NFT metadata too long"))?;
+    Uniques::set_metadata(RuntimeOrigin::root(), *collection, *item, metadata, true)
+  }
+
+  fn owner(collection: &u32, item: &u32) -> Option<AccountId> {
+    <Uniques as Inspect<AccountId>>::owner(collection, item)
+  }
+}
+
+parameter_types! {
+  // Collections from here on can only be created by the pallets, the supporter badges are
+  // allocated upwards from the first one
+  pub const FirstReservedCollectionId: u32 = 1 << 31;
+  // Far from the collections of supporter badges
+  pub const ProofNftCollectionId: u32 = u32::MAX;
 }
 
 impl poe::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type WeightInfo = poe::weights::AnagolayWeight<Runtime>;
+  type ProofRevocationHandler = statements::types::StatementsProofRevocationHandler<Runtime>;
+  type ProofNftMinter = UniquesProofNftMinter;
+  type ProofNftCollectionId = ProofNftCollectionId;
+
+  const PALLET_ID: PalletId = PalletId(*b"anag/poe");
 
-  const MAX_PROOFS_PER_WORKFLOW: u32 = 1;
+  const MAX_PROOFS_PER_VERIFICATION_CONTEXT: u32 = 1000;
+  const MAX_PHASHES_PER_INDEX_BAND: u32 = 1000;
+  const MAX_PHASHES_PER_PROOF: u32 = 16;
+  const MAX_DERIVED_PROOFS_PER_PROOF: u32 = 100;
 }
 
 impl verification::Config for Runtime {
   type AuthorityId = verification::crypto::VerificationAuthId;
   type RuntimeEvent = RuntimeEvent;
   type VerificationKeyGenerator = poe::types::PoeVerificationKeyGenerator<Runtime>;
-  type VerificationInvalidator = statements::types::StatementsVerificationInvalidator<Runtime>;
+  // The Proofs of the holder are removed from the context last, since the Statements are found
+  // through them
+  type VerificationInvalidator = (
+    statements::types::StatementsVerificationInvalidator<Runtime>,
+    (
+      tipping::types::TippingVerificationInvalidator<Runtime>,
+      poe::types::PoeVerificationInvalidator<Runtime>,
+    ),
+  );
   type WeightInfo = verification::weights::AnagolayWeight<Runtime>;
   type Currency = Balances;
 
@@ -786,13 +859,49 @@ impl verification::Config for Runtime {
   const MAX_REQUESTS_PER_CONTEXT: u32 = 1000;
 }
 
+/// Mints the tipping supporter badges as `pallet_uniques` items. Collections and badges are
+/// created with the force origin, so that they are free of deposits. The badge metadata has a fixed
+/// size that fits `UniquesStringLimit`
+#[derive(Clone)]
+pub struct UniquesSupporterBadgeMinter;
+
+impl tipping::types::SupporterBadgeMinter<Runtime> for UniquesSupporterBadgeMinter {
+  type CollectionId = u32;
+  type ItemId = u32;
+
+  fn create_collection(collection: &u32, creator: &AccountId) -> DispatchResult {
+    Uniques::force_create(RuntimeOrigin::root(), *collection, MultiAddress::Id(creator.clone()), true)
+  }
+
+  fn mint(collection: &u32, item: &u32, supporter: &AccountId, metadata: Vec<u8>) -> DispatchResult {
+    <Uniques as Mutate<AccountId>>::mint_into(collection, item, supporter)?;
+    let metadata = BoundedVec::try_from(metadata).map_err(|_| DispatchError::Other("Badge metadata too long"))?;
+    Uniques::set_metadata(RuntimeOrigin::root(), *collection, *item, metadata, true)
+  }
+}
+
 impl tipping::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type Currency = Balances;
   type TimeProvider = pallet_timestamp::Pallet<Runtime>;
+  type ProtocolFeeHandler = Treasury;
+  type SupporterBadgeMinter = UniquesSupporterBadgeMinter;
+  type FirstSupporterBadgeCollectionId = FirstReservedCollectionId;
+  type RemoteLocation = xcm::latest::MultiLocation;
+  type RemoteTipOrigin = xcm_config::EnsureXcmTipper;
+  type MatchingRoundOrigin = EnsureRoot<AccountId>;
+  type MatchingContributorFilter = tipping::types::VerifiedMatchingContributorFilter<Runtime>;
   type WeightInfo = tipping::weights::AnagolayWeight<Runtime>;
 
   const MAX_TIPS_PER_VERIFICATION_CONTEXT: u32 = 10000;
+  const MEMO_FEE_PER_BYTE: u128 = STORAGE_BYTE_FEE;
+  const PALLET_ID: PalletId = PalletId(*b"anag/tip");
+  const ESCROW_TIMEOUT: BlockNumber = 30 * DAYS;
+  const MAX_ESCROWED_TIPS_PER_VERIFICATION_CONTEXT: u32 = 1000;
+  const PROTOCOL_FEE: Permill = Permill::from_percent(1);
+  const MAX_CONTEXTS_PER_MATCHING_ROUND: u32 = 500;
+  const MAX_REFUND_WINDOW: BlockNumber = 7 * DAYS;
+  const MAX_PENDING_TIPS_PER_BLOCK: u32 = 100;
 }
 
 impl frame_system::offchain::SigningTypes for Runtime {
@@ -1048,6 +1157,63 @@ impl_runtime_apis! {
     }
   }
 
+  impl poe_rpc_runtime_api::PoeApi<Block, AccountId, BlockNumber, u32, u32> for Runtime {
+    fn get_proofs_by_ids(
+      proof_ids: Vec<poe::types::ProofId>,
+      offset: u64,
+      limit: u16,
+    ) -> Vec<poe::types::ProofInfo<AccountId, BlockNumber>> {
+      Poe::get_proofs_by_ids(proof_ids, offset, limit)
+    }
+    fn get_proofs_by_account(
+      account_id: AccountId,
+      offset: u64,
+      limit: u16,
+    ) -> Vec<poe::types::ProofInfo<AccountId, BlockNumber>> {
+      Poe::get_proofs_by_account(account_id, offset, limit)
+    }
+    fn get_proofs_by_context(
+      context: verification::types::VerificationContext,
+      offset: u64,
+      limit: u16,
+    ) -> Vec<poe::types::ProofInfo<AccountId, BlockNumber>> {
+      Poe::get_proofs_by_context(context, offset, limit)
+    }
+    fn get_proof_tree(proof_id: poe::types::ProofId) -> Vec<poe::types::ProofInfo<AccountId, BlockNumber>> {
+      Poe::get_proof_tree(proof_id)
+    }
+    fn get_phashes(
+      account_id: Option<AccountId>,
+      proof_id: Option<poe::types::ProofId>,
+      offset: u64,
+      limit: u16,
+    ) -> Vec<poe::types::PhashInfo> {
+      Poe::get_phashes(account_id, proof_id, offset, limit)
+    }
+    fn find_similar_phashes(
+      algorithm: poe::types::PhashAlgorithm,
+      p_hash: Vec<u8>,
+      max_hamming_distance: u32,
+    ) -> Vec<poe::types::SimilarPhash<AccountId>> {
+      Poe::find_similar_phashes(algorithm, p_hash, max_hamming_distance)
+    }
+    fn verify_inclusion(
+      proof_cid: poe::types::ProofId,
+      merkle_path: Vec<poe::types::MerkleRoot>,
+    ) -> Vec<poe::types::ProofBatchInfo<AccountId, BlockNumber>> {
+      Poe::verify_inclusion(proof_cid, merkle_path)
+    }
+    fn get_proof_nft(
+      proof_id: poe::types::ProofId,
+      account_id: AccountId,
+    ) -> Option<poe::types::ProofNftInfo<AccountId, BlockNumber, u32, u32>> {
+      Poe::get_proof_nft(proof_id, account_id)
+    }
+    fn get_proof_by_nft(item_id: u32) -> Option<poe::types::ProofNftInfo<AccountId, BlockNumber, u32, u32>> {
+      Poe::get_proof_by_nft(item_id)
+    }
+  }
+
   impl verification_rpc_runtime_api::VerificationApi<Block, AccountId> for Runtime {
     fn get_requests(
       contexts: Vec<verification::types::VerificationContext>,
@@ -1082,6 +1248,41 @@ impl_runtime_apis! {
     ) -> Vec<tipping::types::Tip<Balance, AccountId, BlockNumber>> {
       Tipping::get_tips(account_id, verification_context, offset, limit)
     }
+    fn get_tips_page(
+      account_id: AccountId,
+      verification_context: verification::types::VerificationContext,
+      sort: tipping::types::SortTips,
+      filter: tipping::types::TipsFilter<Balance, AccountId, BlockNumber>,
+      cursor: Option<u64>,
+      limit: u16,
+    ) -> tipping::types::TipsPage<tipping::types::Tip<Balance, AccountId, BlockNumber>> {
+      Tipping::get_tips_page(account_id, verification_context, sort, filter, cursor, limit)
+    }
+    fn escrowed_balance(verification_context: verification::types::VerificationContext) -> Balance {
+      Tipping::escrowed_balance(verification_context)
+    }
+    fn get_tips_aggregate(
+      account_id: AccountId,
+      verification_context: verification::types::VerificationContext,
+      sender: Option<AccountId>,
+    ) -> tipping::types::TipsAggregate<Balance, BlockNumber> {
+      Tipping::get_tips_aggregate(account_id, verification_context, sender)
+    }
+    fn get_tips_by_sender(
+      sender: AccountId,
+      sort: tipping::types::SortTips,
+      cursor: Option<u64>,
+      limit: u16,
+    ) -> tipping::types::TipsPage<tipping::types::SentTip<Balance, AccountId, BlockNumber>> {
+      Tipping::get_tips_by_sender(sender, sort, cursor, limit)
+    }
+    fn top_supporters(
+      account_id: AccountId,
+      verification_context: verification::types::VerificationContext,
+      count: u16,
+    ) -> Vec<tipping::types::Supporter<Balance, AccountId, BlockNumber>> {
+      Tipping::top_supporters(account_id, verification_context, count)
+    }
   }
 
  impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
@@ -2,14 +2,16 @@ use super::{
   AccountId, Balances, ParachainInfo, ParachainSystem, PolkadotXcm, Runtime, RuntimeCall, RuntimeEvent, RuntimeOrigin,
   WeightToFee, XcmpQueue,
 };
-use core::marker::PhantomData;
+use codec::{Compact, Encode};
+use core::{borrow::Borrow, marker::PhantomData};
 use frame_support::{
   log, match_types, parameter_types,
-  traits::{Everything, Nothing},
+  traits::{EnsureOrigin, Everything, Nothing},
 };
 use pallet_xcm::XcmPassthrough;
 use polkadot_parachain::primitives::Sibling;
 use polkadot_runtime_common::impls::ToAuthor;
+use sp_core::hashing::blake2_256;
 use xcm::latest::{prelude::*, Weight as XCMWeight};
 use xcm_builder::{
   AccountId32Aliases, AllowTopLevelPaidExecutionFrom, AllowUnpaidExecutionFrom, CurrencyAdapter, EnsureXcmOrigin,
@@ -17,7 +19,10 @@ use xcm_builder::{
   SiblingParachainAsNative, SiblingParachainConvertsVia, SignedAccountId32AsNative, SignedToAccountId32,
   SovereignSignedViaLocation, TakeWeightCredit, UsingComponents,
 };
-use xcm_executor::{traits::ShouldExecute, XcmExecutor};
+use xcm_executor::{
+  traits::{Convert, ShouldExecute},
+  XcmExecutor,
+};
 
 parameter_types! {
   pub const RelayLocation: MultiLocation = MultiLocation::parent();
@@ -38,6 +43,38 @@ pub type LocationToAccountId = (
   AccountId32Aliases<RelayNetwork, AccountId>,
 );
 
+/// Converts the `AccountId32` locations of the relay chain and of the sibling parachains into
+/// local accounts derived from the hash of the location, so that each remote user controls its
+/// own account instead of the sovereign account of its chain. The derivation is the same as
+/// `HashedDescription<AccountId, DescribeFamily<DescribeAccountId32Terminal>>` of later XCM
+/// versions.
+///
+/// It is only used by `EnsureXcmTipper` and is deliberately left out of `LocationToAccountId`, so
+/// that a `Transact` of a remote user is never dispatched as a signed origin of this account.
+pub struct RemoteAccountId32Hashed<AccountId>(PhantomData<AccountId>);
+
+impl<AccountId: From<[u8; 32]> + Clone> Convert<MultiLocation, AccountId> for RemoteAccountId32Hashed<AccountId> {
+  fn convert_ref(location: impl Borrow<MultiLocation>) -> Result<AccountId, ()> {
+    let description = match location.borrow() {
+      MultiLocation {
+        parents: 1,
+        interior: X1(AccountId32 { id, .. }),
+      } => (b"ParentChain", (b"AccountId32", id).encode()).encode(),
+      MultiLocation {
+        parents: 1,
+        interior: X2(Parachain(para_id), AccountId32 { id, .. }),
+      } => (
+        b"SiblingChain",
+        Compact::<u32>::from(*para_id),
+        (b"AccountId32", id).encode(),
+      )
+        .encode(),
+      _ => return Err(()),
+    };
+    Ok(blake2_256(&description).into())
+  }
+}
+
 /// Means for transacting assets on this chain.
 pub type LocalAssetTransactor = CurrencyAdapter<
   // Use this currency:
@@ -232,6 +269,32 @@ impl pallet_xcm::Config for Runtime {
   type AdvertisedXcmVersion = pallet_xcm::CurrentXcmVersion;
 }
 
+/// Origin of the tips sent by the users of other consensus systems with an XCM `Transact` of kind
+/// `Xcm`. It resolves to the location of the tipper and to its local account, as given by
+/// `RemoteAccountId32Hashed`, which funds the tip. The chains themselves cannot tip, so that their
+/// sovereign accounts are never spent by a `Transact`.
+pub struct EnsureXcmTipper;
+impl EnsureOrigin<RuntimeOrigin> for EnsureXcmTipper {
+  type Success = (MultiLocation, AccountId);
+
+  fn try_origin(o: RuntimeOrigin) -> Result<Self::Success, RuntimeOrigin> {
+    let location = pallet_xcm::EnsureXcm::<Everything>::try_origin(o)?;
+    match RemoteAccountId32Hashed::<AccountId>::convert_ref(&location) {
+      Ok(account) => Ok((location, account)),
+      Err(()) => Err(pallet_xcm::Origin::Xcm(location).into()),
+    }
+  }
+
+  #[cfg(feature = "runtime-benchmarks")]
+  fn successful_origin() -> RuntimeOrigin {
+    let tipper = AccountId32 {
+      network: NetworkId::Any,
+      id: [0u8; 32],
+    };
+    pallet_xcm::Origin::Xcm(MultiLocation::new(1, X1(tipper))).into()
+  }
+}
+
 impl cumulus_pallet_xcm::Config for Runtime {
   type RuntimeEvent = RuntimeEvent;
   type XcmExecutor = XcmExecutor<XcmConfig>;